@@ -0,0 +1,79 @@
+//! Preview pane of a ratatui application: the model on the left, turned with
+//! the arrow keys, and what it shows on the right.
+//!
+//! cargo run --example ratatui_preview --features ratatui -- examples/cow.obj
+
+use ratatui::backend::TermionBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::termion::event::Key;
+use ratatui::termion::input::TermRead;
+use ratatui::termion::raw::IntoRawMode;
+use ratatui::termion::screen::IntoAlternateScreen;
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Terminal;
+use std::env::args;
+use std::error::Error;
+use std::io::stdin;
+use std::io::stdout;
+use sx3d::input_output::{read_mesh, GlyphMode, SceneView};
+use sx3d::{Color, Object, Scene, Vector3D};
+
+const STEP: f32 = std::f32::consts::PI / 20.0;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/torus.stl".to_string());
+    let mut object = Object::new(read_mesh(path.clone())?);
+    object.set_name(path.clone());
+    object.set_color(Some(Color::palette(0)));
+    let mut scene = Scene::new(
+        object,
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    );
+    let mut view = SceneView::new(GlyphMode::Braille, true);
+
+    let screen = stdout().into_raw_mode()?.into_alternate_screen()?;
+    let mut terminal = Terminal::new(TermionBackend::new(screen))?;
+    let mut keys = stdin().keys();
+
+    loop {
+        terminal.draw(|frame| {
+            let [preview, info] =
+                Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)])
+                    .areas(frame.area());
+
+            let block = Block::default().borders(Borders::ALL).title(path.as_str());
+            frame.render_stateful_widget(&view, block.inner(preview), &mut scene);
+            frame.render_widget(block, preview);
+
+            let text = format!(
+                "Triangles: {}\nGlyphs: {:?}\nColor: {}\n\nArrows: rotate\nb: braille or ASCII\nc: color\nq: quit",
+                scene.get_objects()[0].get_mesh().triangles.len(),
+                view.glyph_mode,
+                view.colored,
+            );
+            let help = Paragraph::new(text).block(Block::default().borders(Borders::ALL));
+            frame.render_widget(help, info);
+        })?;
+
+        match keys.next() {
+            Some(Ok(Key::Up)) => scene.rotate_delta_x(&STEP),
+            Some(Ok(Key::Down)) => scene.rotate_delta_x(&-STEP),
+            Some(Ok(Key::Left)) => scene.rotate_delta_y(&STEP),
+            Some(Ok(Key::Right)) => scene.rotate_delta_y(&-STEP),
+            Some(Ok(Key::Char('b'))) => {
+                view.glyph_mode = match view.glyph_mode {
+                    GlyphMode::Ascii => GlyphMode::Braille,
+                    GlyphMode::Braille => GlyphMode::Ascii,
+                }
+            }
+            Some(Ok(Key::Char('c'))) => view.colored = !view.colored,
+            Some(Ok(Key::Char('q'))) | None => break,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// Edges of the image found from the depth and the normal seen through each cell.
+use crate::entities::{Vector2D, Vector3D};
+
+// Depth step between neighbouring cells, in cells, beyond the one explained by the
+// slope of the surface, that makes a silhouette.
+const DEPTH_JUMP: f32 = 1.5;
+
+// Cosine of the angle between the normals of neighbouring cells, about 40 degrees,
+// beyond which they are on both sides of a crease.
+const CREASE_COSINE: f32 = 0.77;
+
+// Steepest slope trusted when the depth of a neighbour is predicted from a normal.
+const MAXIMUM_SLOPE: f32 = 4.0;
+
+// Neighbours of a cell as (x, y) steps, with y going up. Diagonal neighbours are
+// left out so that edges come out one cell thick.
+const NEIGHBOURS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Surface seen through a canvas cell.
+#[derive(Debug, Clone, Copy)]
+pub struct Surface {
+    /// Depth at the center of the cell. Larger is closer to the observer.
+    pub depth: f32,
+    pub normal: Vector3D,
+}
+
+/// Silhouette or crease that crosses a cell.
+#[derive(Debug, Clone, Copy)]
+pub struct Edge {
+    /// Points from the cell to the other side of the edge, with y going up.
+    pub direction: Vector2D,
+    /// Angle of the edge line in degrees, counterclockwise from the x axis, in [0, 180).
+    pub angle: f32,
+}
+
+impl Edge {
+    /// Glyph that follows the edge. Horizontal edges use `_` when the surface is
+    /// above them and `-` when it is below.
+    pub fn glyph(&self) -> char {
+        match self.angle {
+            angle if !(22.5..157.5).contains(&angle) => {
+                if self.direction[1] < 0.0 {
+                    '_'
+                } else {
+                    '-'
+                }
+            }
+            angle if angle < 67.5 => '/',
+            angle if angle < 112.5 => '|',
+            _ => '\\',
+        }
+    }
+}
+
+/// Edges of a grid of `cols` cells per row, the top row first, whose cells are
+/// `cell_size` wide.
+///
+/// Between two cells there is an edge when only one of them has a surface, when
+/// their depths differ more than the slope of the surface explains, or when their
+/// normals meet at a crease. The edge is given to the closer cell, so it is one
+/// cell thick, and its line runs perpendicular to the sum of the steps that
+/// cross it.
+pub fn find_edges(surfaces: &[Option<Surface>], cols: usize, cell_size: f32) -> Vec<Option<Edge>> {
+    let rows = surfaces.len().checked_div(cols).unwrap_or(0);
+
+    (0..surfaces.len())
+        .map(|index| {
+            let surface = surfaces[index]?;
+            let (col, row) = ((index % cols) as isize, (index / cols) as isize);
+
+            let (mut sum_x, mut sum_y) = (0.0, 0.0);
+            let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+            for (step_x, step_y) in NEIGHBOURS {
+                // Rows grow downwards in the grid.
+                let (neighbour_col, neighbour_row) = (col + step_x, row - step_y);
+                let neighbour = if neighbour_col < 0
+                    || neighbour_row < 0
+                    || neighbour_col >= cols as isize
+                    || neighbour_row >= rows as isize
+                {
+                    None
+                } else {
+                    surfaces[neighbour_row as usize * cols + neighbour_col as usize]
+                };
+
+                let step = (step_x as f32, step_y as f32);
+                if !is_edge_between(&surface, neighbour.as_ref(), step, cell_size) {
+                    continue;
+                }
+
+                let length = (step.0 * step.0 + step.1 * step.1).sqrt();
+                let (x, y) = (step.0 / length, step.1 / length);
+                sum_x += x;
+                sum_y += y;
+                xx += x * x;
+                xy += x * y;
+                yy += y * y;
+            }
+
+            if xx + yy == 0.0 {
+                return None;
+            }
+
+            // The steps point across the edge. On lines one cell thick they cancel
+            // out, and the main axis of the steps is used instead of their sum.
+            let across = if (sum_x * sum_x + sum_y * sum_y).sqrt() > 0.5 {
+                f32::atan2(sum_y, sum_x)
+            } else {
+                0.5 * f32::atan2(2.0 * xy, xx - yy)
+            };
+            let angle = (across.to_degrees() + 90.0).rem_euclid(180.0);
+
+            Some(Edge {
+                direction: Vector2D::new(sum_x, sum_y),
+                angle,
+            })
+        })
+        .collect()
+}
+
+fn is_edge_between(
+    surface: &Surface,
+    neighbour: Option<&Surface>,
+    step: (f32, f32),
+    cell_size: f32,
+) -> bool {
+    let neighbour = match neighbour {
+        Some(neighbour) => neighbour,
+        None => return true,
+    };
+
+    // A silhouette leaves each side far from the plane of the other. On a crease
+    // the plane of a steep side alone can overshoot the other one.
+    let jump = DEPTH_JUMP * cell_size;
+    let back = (-step.0, -step.1);
+    let in_front = extrapolate(surface, step, cell_size) - neighbour.depth > jump
+        && surface.depth - extrapolate(neighbour, back, cell_size) > jump;
+    let behind = neighbour.depth - extrapolate(surface, step, cell_size) > jump
+        && extrapolate(neighbour, back, cell_size) - surface.depth > jump;
+    if in_front || behind {
+        return in_front;
+    }
+
+    let norms = surface.normal.norm() * neighbour.normal.norm();
+    norms > 0.0
+        && surface.normal.dot_product(&neighbour.normal) < CREASE_COSINE * norms
+        && surface.depth >= neighbour.depth
+}
+
+/// Depth of the plane of `surface` one `step` away.
+pub(super) fn extrapolate(surface: &Surface, step: (f32, f32), cell_size: f32) -> f32 {
+    let normal = surface.normal;
+    if normal[2].abs() <= f32::EPSILON {
+        return surface.depth;
+    }
+
+    let slope = |component: f32| (-component / normal[2]).clamp(-MAXIMUM_SLOPE, MAXIMUM_SLOPE);
+    surface.depth + (slope(normal[0]) * step.0 + slope(normal[1]) * step.1) * cell_size
+}
+
+/// Cells of outlines two cells thick: the cells of `edges`, and their neighbours
+/// on the other side of the edges, which may have no surface.
+pub fn thicken_edges(edges: &[Option<Edge>], cols: usize) -> Vec<bool> {
+    let rows = edges.len().checked_div(cols).unwrap_or(0);
+    let mut outline = vec![false; edges.len()];
+
+    for (index, edge) in edges.iter().enumerate() {
+        let edge = match edge {
+            Some(edge) => edge,
+            None => continue,
+        };
+        outline[index] = true;
+
+        let (col, row) = (index % cols, index / cols);
+        let (x, y) = (edge.direction[0], edge.direction[1]);
+        // Rows grow downwards in the grid.
+        let neighbours = [
+            (x >= 0.5 && col + 1 < cols, index + 1),
+            (x <= -0.5 && col > 0, index.wrapping_sub(1)),
+            (y >= 0.5 && row > 0, index.wrapping_sub(cols)),
+            (y <= -0.5 && row + 1 < rows, index + cols),
+        ];
+        for (inside, neighbour) in neighbours {
+            if inside {
+                outline[neighbour] = true;
+            }
+        }
+    }
+
+    outline
+}
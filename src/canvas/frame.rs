@@ -0,0 +1,170 @@
+use crate::entities::Color;
+
+/// What a cell of a frame shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub char: char,
+    /// Color of the glyph. The default color of the output when None.
+    pub color: Option<Color>,
+    /// Light of the surface seen through the cell, from 0 to 1, as the glyph
+    /// stands for it. None for empty cells and fixed glyphs such as outlines.
+    pub light: Option<f32>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            char: ' ',
+            color: None,
+            light: None,
+        }
+    }
+}
+
+/// A finished picture, independent of where it is shown: a grid of cells
+/// stored row by row from the top left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub cols: usize,
+    pub rows: usize,
+    pub cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// Frame of empty cells.
+    pub fn new(cols: usize, rows: usize) -> Frame {
+        Frame {
+            cols,
+            rows,
+            cells: vec![Cell::default(); cols * rows],
+        }
+    }
+
+    /// Cell at `col` from the left and `row` from the top, if there is one.
+    pub fn get(&self, col: usize, row: usize) -> Option<&Cell> {
+        if col >= self.cols || row >= self.rows {
+            return None;
+        }
+        self.cells.get(col + row * self.cols)
+    }
+
+    /// Frame of a text as `AsciiTarget` and `BrailleTarget` write it, with a
+    /// cell for every character of its lines, taking the colors of its
+    /// foreground escapes. Other escapes are left out, and the lines shorter
+    /// than the longest one are filled with empty cells.
+    pub fn from_text(text: &str) -> Frame {
+        let mut lines: Vec<Vec<Cell>> = Vec::new();
+
+        for line in text.lines() {
+            let mut cells = Vec::new();
+            let mut color = None;
+            let mut chars = line.chars();
+            while let Some(char) = chars.next() {
+                if char != '\x1b' {
+                    cells.push(Cell {
+                        char,
+                        color,
+                        light: None,
+                    });
+                    continue;
+                }
+                if chars.next() != Some('[') {
+                    continue;
+                }
+                // The parameters of control sequences end with a character
+                // from '@' to '~'.
+                let mut parameters = String::new();
+                for char in chars.by_ref() {
+                    if ('@'..='~').contains(&char) {
+                        if char == 'm' {
+                            color = get_foreground(&parameters).unwrap_or(color);
+                        }
+                        break;
+                    }
+                    parameters.push(char);
+                }
+            }
+            lines.push(cells);
+        }
+
+        let cols = lines.iter().map(Vec::len).max().unwrap_or(0);
+        let mut frame = Frame::new(cols, lines.len());
+        for (row, cells) in lines.into_iter().enumerate() {
+            let start = row * cols;
+            frame.cells[start..start + cells.len()].copy_from_slice(&cells);
+        }
+        frame
+    }
+
+    /// Frame as a terminal of `cols` by `rows` characters shows it from its
+    /// top left corner: the rows wider than the terminal go on in the next
+    /// ones, and the rows below its bottom are left out.
+    pub fn fit(&self, cols: usize, rows: usize) -> Frame {
+        let mut lines: Vec<&[Cell]> = Vec::new();
+        for line in self.lines() {
+            // Trailing empty cells do not take up rows of their own.
+            let width = line
+                .iter()
+                .rposition(|cell| cell.char != ' ')
+                .map_or(0, |last| last + 1);
+            match width {
+                0 => lines.push(&[]),
+                _ => lines.extend(line[..width].chunks(cols.max(1))),
+            }
+        }
+
+        let mut frame = Frame::new(cols, lines.len().min(rows));
+        for (row, cells) in lines.into_iter().take(rows).enumerate() {
+            let start = row * cols;
+            frame.cells[start..start + cells.len()].copy_from_slice(cells);
+        }
+        frame
+    }
+
+    /// Writes the characters of `text` in the default color from `col` and `row`
+    /// on, making the frame larger where they do not fit.
+    pub fn write_text(&mut self, col: usize, row: usize, text: &str) {
+        let chars: Vec<char> = text.chars().collect();
+        self.grow((col + chars.len()).max(self.cols), (row + 1).max(self.rows));
+        let start = col + row * self.cols;
+        for (cell, char) in self.cells[start..].iter_mut().zip(chars) {
+            *cell = Cell {
+                char,
+                ..Cell::default()
+            };
+        }
+    }
+
+    // Makes the frame `cols` by `rows`, keeping the cells where they are.
+    fn grow(&mut self, cols: usize, rows: usize) {
+        if (cols, rows) == (self.cols, self.rows) {
+            return;
+        }
+        let mut grown = Frame::new(cols, rows);
+        for (row, cells) in self.lines().enumerate() {
+            let start = row * cols;
+            grown.cells[start..start + cells.len()].copy_from_slice(cells);
+        }
+        *self = grown;
+    }
+
+    /// The rows of cells from the top.
+    pub fn lines(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+}
+
+// Foreground color set by the parameters of a select graphic rendition
+// sequence, None when it resets it, or no change for other parameters.
+fn get_foreground(parameters: &str) -> Option<Option<Color>> {
+    let values: Vec<&str> = parameters.split(';').collect();
+    match values.as_slice() {
+        ["38", "2", r, g, b] => Some(Some(Color::new(
+            r.parse().ok()?,
+            g.parse().ok()?,
+            b.parse().ok()?,
+        ))),
+        ["39"] | ["0"] | [""] => Some(None),
+        _ => None,
+    }
+}
@@ -0,0 +1,116 @@
+use super::frame::{Cell, Frame};
+use crate::entities::Color;
+
+// Unchanged cells between two changed runs of a line up to which the runs are
+// written as one, which is shorter than moving the cursor over them.
+const MERGED_GAP: usize = 4;
+
+/// Terminal output of a sequence of frames that keeps the frame written last
+/// and writes only the runs of cells that changed since, with cursor moves
+/// between them and a color escape only where the color changes. The frames
+/// are placed at the top left corner of the screen, one cell per character,
+/// and the cells they stop covering are erased.
+#[derive(Debug, Clone, Default)]
+pub struct FrameDiff {
+    previous: Option<Frame>,
+}
+
+impl FrameDiff {
+    pub fn new() -> FrameDiff {
+        FrameDiff { previous: None }
+    }
+
+    /// Forgets the frame written last, so the next one clears the screen and
+    /// is written whole, as when the screen was changed by something else.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    /// Escapes and characters taking the screen from the frame written last
+    /// to `frame`. Empty when nothing changed.
+    pub fn update(&mut self, frame: &Frame) -> String {
+        let mut output = String::new();
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => {
+                output.push_str("\x1b[2J");
+                Frame::new(0, 0)
+            }
+        };
+
+        let mut writer = Writer {
+            output,
+            position: None,
+            color: None,
+        };
+        for row in 0..frame.rows.max(previous.rows) {
+            let cols = frame.cols.max(previous.cols);
+            let changed: Vec<usize> = (0..cols)
+                .filter(|&col| {
+                    !looks_same(&get_cell(frame, col, row), &get_cell(&previous, col, row))
+                })
+                .collect();
+
+            let mut runs = changed.iter();
+            let Some(&first) = runs.next() else {
+                continue;
+            };
+            let (mut start, mut end) = (first, first);
+            for &col in runs {
+                if col - end > MERGED_GAP {
+                    writer.write_run(frame, row, start, end);
+                    start = col;
+                }
+                end = col;
+            }
+            writer.write_run(frame, row, start, end);
+        }
+        if writer.color.is_some() {
+            writer.output.push_str("\x1b[39m");
+        }
+
+        self.previous = Some(frame.clone());
+        writer.output
+    }
+}
+
+struct Writer {
+    output: String,
+    /// Column and row of the cursor, when they are known.
+    position: Option<(usize, usize)>,
+    color: Option<Color>,
+}
+
+impl Writer {
+    /// Writes the cells of `row` from `start` to `end`, both included.
+    fn write_run(&mut self, frame: &Frame, row: usize, start: usize, end: usize) {
+        if self.position != Some((start, row)) {
+            self.output
+                .push_str(&format!("\x1b[{};{}H", row + 1, start + 1));
+        }
+        for col in start..=end {
+            let cell = get_cell(frame, col, row);
+            // Spaces look the same in any color.
+            if cell.char != ' ' && cell.color != self.color {
+                match cell.color {
+                    Some(Color { r, g, b }) => {
+                        self.output.push_str(&format!("\x1b[38;2;{r};{g};{b}m"))
+                    }
+                    None => self.output.push_str("\x1b[39m"),
+                }
+                self.color = cell.color;
+            }
+            self.output.push(cell.char);
+        }
+        self.position = Some((end + 1, row));
+    }
+}
+
+// Cell at a position, empty beyond the frame.
+fn get_cell(frame: &Frame, col: usize, row: usize) -> Cell {
+    frame.get(col, row).copied().unwrap_or_default()
+}
+
+fn looks_same(cell: &Cell, other: &Cell) -> bool {
+    cell.char == other.char && (cell.char == ' ' || cell.color == other.color)
+}
@@ -3,3 +3,27 @@ pub use squared_canvas::*;
 
 pub mod shading;
 pub use shading::*;
+
+pub mod edges;
+pub use edges::*;
+
+pub mod occlusion;
+pub use occlusion::*;
+
+pub mod ray_tracer;
+pub use ray_tracer::*;
+
+pub mod frame;
+pub use frame::*;
+
+pub mod traits;
+pub use traits::*;
+
+pub mod rasterizer;
+pub use rasterizer::*;
+
+pub mod target;
+pub use target::*;
+
+pub mod frame_diff;
+pub use frame_diff::*;
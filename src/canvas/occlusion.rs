@@ -0,0 +1,68 @@
+// Ambient occlusion approximated from the depth and the normal seen through each cell.
+use super::edges::{extrapolate, Surface};
+
+// Cells around a cell whose surfaces may block its ambient light.
+const SAMPLE_RADIUS: isize = 3;
+
+// Height, in cells, above which a neighbour is taken for a separate object in
+// front and blocks nothing.
+const MAXIMUM_HEIGHT: f32 = 4.0;
+
+/// Share of the ambient light blocked at each cell of a grid of `cols` cells per
+/// row, the top row first, whose cells are `cell_size` wide.
+///
+/// Every neighbour within a few cells that rises above the plane of the surface
+/// blocks as much as the slope from the cell up to it, so the insides of
+/// creases and the feet of walls get darker while flat and convex areas do not.
+pub fn screen_space_occlusion(
+    surfaces: &[Option<Surface>],
+    cols: usize,
+    cell_size: f32,
+) -> Vec<f32> {
+    let rows = surfaces.len().checked_div(cols).unwrap_or(0);
+
+    (0..surfaces.len())
+        .map(|index| {
+            let surface = match surfaces[index] {
+                Some(surface) => surface,
+                None => return 0.0,
+            };
+            let (col, row) = ((index % cols) as isize, (index / cols) as isize);
+
+            let mut blocked = 0.0;
+            let mut samples = 0;
+            for step_y in -SAMPLE_RADIUS..=SAMPLE_RADIUS {
+                for step_x in -SAMPLE_RADIUS..=SAMPLE_RADIUS {
+                    let distance = ((step_x * step_x + step_y * step_y) as f32).sqrt();
+                    if distance == 0.0 || distance > SAMPLE_RADIUS as f32 {
+                        continue;
+                    }
+                    samples += 1;
+
+                    // Rows grow downwards in the grid.
+                    let (neighbour_col, neighbour_row) = (col + step_x, row - step_y);
+                    if neighbour_col < 0
+                        || neighbour_row < 0
+                        || neighbour_col >= cols as isize
+                        || neighbour_row >= rows as isize
+                    {
+                        continue;
+                    }
+                    let neighbour =
+                        match surfaces[neighbour_row as usize * cols + neighbour_col as usize] {
+                            Some(neighbour) => neighbour,
+                            None => continue,
+                        };
+
+                    let step = (step_x as f32, step_y as f32);
+                    let height = neighbour.depth - extrapolate(&surface, step, cell_size);
+                    if height > 0.0 && height <= MAXIMUM_HEIGHT * cell_size {
+                        blocked += (height / (distance * cell_size)).min(1.0);
+                    }
+                }
+            }
+
+            blocked / samples as f32
+        })
+        .collect()
+}
@@ -0,0 +1,207 @@
+use super::edges::Surface;
+use super::shading::{Shade, Visualization, CAP_CHAR};
+use super::squared_canvas::{OCCLUSION_DARKENING, SHADOW_DARKENING};
+use super::traits::{polygon_contains_point, Canvas, Renderer, Sample};
+use crate::entities::{Cap, Color, Scene, ShadowMap, Vector2D, Vector3D, VisibleTriangle2D};
+
+/// Draws the triangles of the scene one after the other, keeping the closest
+/// one on every cell. Fast enough for the interactive viewer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rasterizer {
+    /// What the faces show.
+    pub visualization: Visualization,
+}
+
+impl Rasterizer {
+    pub fn new(visualization: Visualization) -> Rasterizer {
+        Rasterizer { visualization }
+    }
+}
+
+impl Renderer for Rasterizer {
+    fn render(&self, scene: &Scene, canvas: &dyn Canvas) {
+        canvas.clear();
+
+        let rasterization = Rasterization {
+            canvas,
+            visualization: self.visualization,
+            shadow_map: scene.get_shadow_map(),
+            axes: scene.get_axes(),
+        };
+        for visible_mesh in scene.get_visible_meshes().iter() {
+            visible_mesh
+                .iter()
+                .for_each(|triangle| rasterization.set_triangle(&triangle));
+        }
+
+        if let Some(cap) = scene.get_cap() {
+            set_cap(canvas, &cap);
+        }
+    }
+}
+
+/// What is drawn with a frame of the rasterizer.
+struct Rasterization<'a> {
+    canvas: &'a dyn Canvas,
+    visualization: Visualization,
+    /// Shadows cast on the faces.
+    shadow_map: Option<ShadowMap>,
+    /// Axes of the model as seen by the observer, to show the normals in them.
+    axes: [Vector3D; 3],
+}
+
+impl Rasterization<'_> {
+    /// Shade and color of `point` in the analysis views, or None when the faces
+    /// are shaded.
+    fn visualize(
+        &self,
+        triangle_2d: &VisibleTriangle2D,
+        point: &Vector2D,
+        depth: f32,
+    ) -> Option<(f32, Color)> {
+        let maximum_diameter = self.canvas.get_maximum_diameter();
+        let radius = maximum_diameter / 2.0;
+        // Squeezes a value of any size into [0, 1], with 0 in the middle.
+        let squeeze = |value: f32| 0.5 + 0.5 * value / (1.0 + value.abs());
+
+        let value = match self.visualization {
+            Visualization::Shaded => return None,
+            Visualization::Depth => (0.5 + depth / maximum_diameter).clamp(0.0, 1.0),
+            Visualization::Normal => {
+                let normal = triangle_2d.normal;
+                let norm = normal.norm().max(f32::EPSILON);
+                let [x, y, z] = self.axes.map(|axis| normal.dot_product(&axis) / norm);
+                let color = Color::from_unit((x + 1.0) / 2.0, (y + 1.0) / 2.0, (z + 1.0) / 2.0);
+                return Some((normal[2] / norm, color));
+            }
+            Visualization::MeanCurvature => {
+                let curvature = triangle_2d.curvature_at(point).unwrap_or_default();
+                squeeze(curvature.mean * radius)
+            }
+            Visualization::GaussianCurvature => {
+                let curvature = triangle_2d.curvature_at(point).unwrap_or_default();
+                squeeze(curvature.gaussian * radius * radius)
+            }
+        };
+
+        Some((value, Color::diverging(value)))
+    }
+
+    fn set_triangle(&self, triangle_2d: &VisibleTriangle2D) {
+        let [min_point, max_point] = triangle_2d.get_bounding_box_2d();
+        let back_face = triangle_2d.back_face;
+
+        let canvas = self.canvas;
+        let (min_col, min_row) = canvas.coordinates_to_indexes(&min_point[0], &min_point[1]);
+        let (max_col, max_row) = canvas.coordinates_to_indexes(&max_point[0], &max_point[1]);
+        let range = canvas.get_cell_range();
+        let clamp = |index: isize| index.clamp(*range.start(), *range.end());
+
+        for row in clamp(min_row)..=clamp(max_row) {
+            for col in clamp(min_col)..=clamp(max_col) {
+                let point = canvas.indexes_to_coordinates(col, row);
+                if !triangle_2d.contains_point(&point) {
+                    continue;
+                }
+                let depth = triangle_2d.depth_at(&point);
+                let mut sample = Sample {
+                    shade: Shade {
+                        value: triangle_2d.shadow_value,
+                        back_face,
+                    },
+                    depth,
+                    normal: triangle_2d.normal,
+                    color: triangle_2d.color,
+                    light: None,
+                };
+
+                if let Some((value, color)) = self.visualize(triangle_2d, &point, depth) {
+                    sample.shade.value = value;
+                    sample.color = Some(color);
+                    canvas.set_sample(col, row, &sample);
+                    continue;
+                }
+
+                let mut shadow_value = triangle_2d.shadow_value;
+                if let Some(shadow_map) = &self.shadow_map {
+                    if shadow_value > 0.0 {
+                        let light = shadow_map.light_at(
+                            &Vector3D::new(point[0], point[1], depth),
+                            &triangle_2d.normal,
+                        );
+                        shadow_value *= 1.0 - SHADOW_DARKENING * (1.0 - light);
+                    }
+                }
+                shadow_value *= 1.0 - OCCLUSION_DARKENING * triangle_2d.occlusion_at(&point);
+                sample.shade.value = shadow_value;
+                sample.light = Some(shadow_value);
+
+                // On textured cells the glyph follows the light times the brightness
+                // of the texel, and the color is the lit texel.
+                if let Some(texel) = triangle_2d.sample_texture(&point) {
+                    sample.shade.value = shadow_value * texel.luminance();
+                    sample.color = Some(texel);
+                }
+                canvas.set_sample(col, row, &sample);
+            }
+        }
+    }
+}
+
+/// Fills the closed contours of `cap` over what the canvas shows.
+fn set_cap(canvas: &dyn Canvas, cap: &Cap) {
+    let normal = cap.plane.normal;
+    if normal[2].abs() < 1e-3 {
+        return;
+    }
+
+    let polygons: Vec<Vec<Vector2D>> = cap
+        .contours
+        .iter()
+        .filter(|contour| contour.closed)
+        .map(|contour| contour.points.iter().map(|point| (*point).into()).collect())
+        .collect();
+
+    let points = polygons.iter().flatten();
+    let (min_x, min_y, max_x, max_y) = points.fold(
+        (
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ),
+        |(min_x, min_y, max_x, max_y), point| {
+            (
+                min_x.min(point[0]),
+                min_y.min(point[1]),
+                max_x.max(point[0]),
+                max_y.max(point[1]),
+            )
+        },
+    );
+    if min_x > max_x {
+        return;
+    }
+
+    let (min_col, min_row) = canvas.coordinates_to_indexes(&min_x, &min_y);
+    let (max_col, max_row) = canvas.coordinates_to_indexes(&max_x, &max_y);
+    let range = canvas.get_cell_range();
+    let clamp = |index: isize| index.clamp(*range.start(), *range.end());
+
+    for row in clamp(min_row)..=clamp(max_row) {
+        for col in clamp(min_col)..=clamp(max_col) {
+            let point = canvas.indexes_to_coordinates(col, row);
+            let crossings = polygons
+                .iter()
+                .filter(|polygon| polygon_contains_point(polygon, &point))
+                .count();
+
+            if crossings % 2 == 1 {
+                let z =
+                    (cap.plane.offset - normal[0] * point[0] - normal[1] * point[1]) / normal[2];
+                let surface = Surface { depth: z, normal };
+                canvas.set_char(col, row, CAP_CHAR, z, None, Some(surface));
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use super::shading::Shade;
+use super::squared_canvas::{OCCLUSION_DARKENING, SHADOW_DARKENING};
+use super::traits::{self, Canvas, Renderer};
+use crate::entities::ambient_occlusion::hemisphere_directions;
+use crate::entities::{Color, Pick, Scene, Vector2D, Vector3D};
+use crate::parallel::*;
+
+// Reach of the occlusion rays, as a share of the diameter of the scene, as far
+// as the ones of the baked ambient occlusion.
+const OCCLUSION_DISTANCE: f32 = 0.25;
+
+// Distance the secondary rays start away from the surface, as a share of the
+// diameter of the scene, so they do not hit the face they leave.
+const RAY_OFFSET: f32 = 1e-4;
+
+/// Settings of the ray traced renderer.
+#[derive(Debug, Clone, Copy)]
+pub struct RayTracing {
+    /// Rays per side of every cell, which casts the square of this number.
+    pub samples: usize,
+    /// Whether the faces block the light to the ones behind them.
+    pub shadows: bool,
+    /// Share of the light the faces reflect like a mirror, from 0 to 1.
+    pub reflectivity: f32,
+    /// Times a ray is reflected at most.
+    pub bounces: usize,
+    /// Rays cast over the hemisphere of every hit to find how much of the
+    /// ambient light is blocked. None are cast with zero.
+    pub occlusion_rays: usize,
+}
+
+impl Default for RayTracing {
+    fn default() -> Self {
+        RayTracing {
+            samples: 2,
+            shadows: true,
+            reflectivity: 0.0,
+            bounces: 2,
+            occlusion_rays: 0,
+        }
+    }
+}
+
+/// Casts rays through the cells of the canvas instead of rasterizing the
+/// triangles, which is slower but follows shadows and reflections exactly. The
+/// objects of the scene need their hierarchies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RayTracer {
+    pub ray_tracing: RayTracing,
+}
+
+impl RayTracer {
+    pub fn new(ray_tracing: RayTracing) -> RayTracer {
+        RayTracer { ray_tracing }
+    }
+}
+
+impl Renderer for RayTracer {
+    fn render(&self, scene: &Scene, canvas: &dyn Canvas) {
+        canvas.clear();
+
+        let cells: Vec<(isize, isize)> = canvas
+            .get_cell_range()
+            .flat_map(|row| canvas.get_cell_range().map(move |col| (col, row)))
+            .collect();
+        let centers: Vec<Vector2D> = cells
+            .iter()
+            .map(|(col, row)| canvas.indexes_to_coordinates(*col, *row))
+            .collect();
+
+        let traced = ray_trace(scene, &centers, canvas.get_cell_size(), &self.ray_tracing);
+        for ((col, row), cell) in cells.iter().zip(traced) {
+            if let Some(cell) = cell {
+                let sample = traits::Sample {
+                    shade: Shade {
+                        value: cell.light,
+                        back_face: cell.back_face,
+                    },
+                    depth: cell.depth,
+                    normal: cell.normal,
+                    color: cell.color,
+                    light: Some(cell.light),
+                };
+                canvas.set_sample(*col, *row, &sample);
+            }
+        }
+    }
+}
+
+/// What the rays cast through a cell see.
+#[derive(Debug, Clone, Copy)]
+pub struct TracedCell {
+    /// Light averaged over the rays, as the `value` of a shade.
+    pub light: f32,
+    /// Base color averaged over the rays that hit something.
+    pub color: Option<Color>,
+    /// Whether the closest face is seen from behind.
+    pub back_face: bool,
+    /// Depth of the closest hit. Larger is closer to the observer.
+    pub depth: f32,
+    /// Normal of the closest face.
+    pub normal: Vector3D,
+}
+
+/// What a single ray sees.
+struct Sample {
+    light: f32,
+    color: Option<[f32; 3]>,
+    back_face: bool,
+    point: Vector3D,
+    normal: Vector3D,
+}
+
+/// Casts rays along -Z through the cells of size `cell_size` centered at
+/// `centers`, spread evenly over each cell, and returns what they see or None
+/// where they all miss. The objects of `scene` need their hierarchies.
+pub fn ray_trace(
+    scene: &Scene,
+    centers: &[Vector2D],
+    cell_size: f32,
+    ray_tracing: &RayTracing,
+) -> Vec<Option<TracedCell>> {
+    let radius = scene.get_maximum_radius();
+    let light = scene.get_light();
+    let light_norm = light.norm().max(f32::EPSILON);
+    let tracer = Tracer {
+        scene,
+        ray_tracing,
+        light: Vector3D::new(
+            light[0] / light_norm,
+            light[1] / light_norm,
+            light[2] / light_norm,
+        ),
+        diameter: 2.0 * radius,
+    };
+    let samples = ray_tracing.samples.max(1);
+    let direction = Vector3D::new(0.0, 0.0, -1.0);
+
+    centers
+        .par_iter()
+        .enumerate()
+        .map(|(index, center)| {
+            let hits: Vec<Sample> = (0..samples * samples)
+                .filter_map(|sample| {
+                    let offset =
+                        |step: usize| ((step as f32 + 0.5) / samples as f32 - 0.5) * cell_size;
+                    // The ray starts in front of everything.
+                    let origin = Vector3D::new(
+                        center[0] + offset(sample % samples),
+                        center[1] + offset(sample / samples),
+                        2.0 * radius + 1.0,
+                    );
+                    tracer.trace(&origin, &direction, ray_tracing.bounces, index)
+                })
+                .collect();
+            if hits.is_empty() {
+                return None;
+            }
+
+            // Rays that miss add darkness, which smooths the silhouettes.
+            let light = hits.iter().map(|hit| hit.light).sum::<f32>() / (samples * samples) as f32;
+            let colors: Vec<[f32; 3]> = hits.iter().filter_map(|hit| hit.color).collect();
+            let color = (!colors.is_empty()).then(|| {
+                let count = colors.len() as f32;
+                let [r, g, b] = [0, 1, 2]
+                    .map(|channel| colors.iter().map(|color| color[channel]).sum::<f32>() / count);
+                Color::from_unit(r, g, b)
+            });
+            let closest = hits
+                .iter()
+                .max_by(|a, b| a.point[2].total_cmp(&b.point[2]))?;
+
+            Some(TracedCell {
+                light,
+                color,
+                back_face: closest.back_face,
+                depth: closest.point[2],
+                normal: closest.normal,
+            })
+        })
+        .collect()
+}
+
+struct Tracer<'a> {
+    scene: &'a Scene,
+    ray_tracing: &'a RayTracing,
+    /// Unit direction the light travels along.
+    light: Vector3D,
+    diameter: f32,
+}
+
+impl Tracer<'_> {
+    /// What the ray from `origin` along the unit `direction` sees, following up
+    /// to `bounces` reflections. `seed` turns the occlusion rays of each cell.
+    fn trace(
+        &self,
+        origin: &Vector3D,
+        direction: &Vector3D,
+        bounces: usize,
+        seed: usize,
+    ) -> Option<Sample> {
+        let pick = self
+            .scene
+            .cast(origin, direction, 2.0 * self.diameter + 2.0)?;
+        let object = &self.scene.get_objects()[pick.object];
+        let mesh = object.get_mesh();
+        let triangle = &mesh.triangles[pick.triangle];
+
+        let norm = triangle.normal.norm().max(f32::EPSILON);
+        let normal = Vector3D::new(
+            triangle.normal[0] / norm,
+            triangle.normal[1] / norm,
+            triangle.normal[2] / norm,
+        );
+        // Faces seen from behind are lit as seen from behind, with the normal flipped.
+        let back_face = normal.dot_product(direction) > 0.0;
+        let facing = if back_face {
+            Vector3D::new(-normal[0], -normal[1], -normal[2])
+        } else {
+            normal
+        };
+        let offset = RAY_OFFSET * self.diameter;
+        let start = Vector3D::new(
+            pick.point[0] + offset * facing[0],
+            pick.point[1] + offset * facing[1],
+            pick.point[2] + offset * facing[2],
+        );
+
+        let mut light = -self.light.dot_product(&facing);
+        if self.ray_tracing.shadows && light > 0.0 {
+            let towards_light = Vector3D::new(-self.light[0], -self.light[1], -self.light[2]);
+            if self
+                .scene
+                .cast(&start, &towards_light, 2.0 * self.diameter)
+                .is_some()
+            {
+                light *= 1.0 - SHADOW_DARKENING;
+            }
+        }
+        if self.ray_tracing.occlusion_rays > 0 {
+            let rays = self.ray_tracing.occlusion_rays;
+            let blocked = hemisphere_directions(&facing, rays, seed)
+                .filter(|direction| {
+                    self.scene
+                        .cast(&start, direction, OCCLUSION_DISTANCE * self.diameter)
+                        .is_some()
+                })
+                .count();
+            light *= 1.0 - OCCLUSION_DARKENING * blocked as f32 / rays as f32;
+        }
+
+        let material = mesh.get_material(triangle.group);
+        let mut color = triangle
+            .color
+            .or(material.map(|material| material.diffuse))
+            .or(object.get_color());
+        // On textured faces the light follows the brightness of the texel, and
+        // the color is the texel.
+        if let Some(texel) = self.sample_texture(&pick) {
+            light *= texel.luminance();
+            color = Some(texel);
+        }
+        let mut color =
+            color.map(|color| [color.r, color.g, color.b].map(|channel| channel as f32 / 255.0));
+
+        let reflectivity = self.ray_tracing.reflectivity.clamp(0.0, 1.0);
+        if bounces > 0 && reflectivity > 0.0 {
+            let along = 2.0 * direction.dot_product(&facing);
+            let reflected = Vector3D::new(
+                direction[0] - along * facing[0],
+                direction[1] - along * facing[1],
+                direction[2] - along * facing[2],
+            );
+            let reflection = self.trace(&start, &reflected, bounces - 1, seed);
+
+            let reflected_light = reflection.as_ref().map_or(0.0, |sample| sample.light);
+            light = (1.0 - reflectivity) * light + reflectivity * reflected_light;
+            if let (Some(own), Some(other)) = (color, reflection.and_then(|sample| sample.color)) {
+                color = Some([0, 1, 2].map(|channel| {
+                    (1.0 - reflectivity) * own[channel] + reflectivity * other[channel]
+                }));
+            }
+        }
+
+        Some(Sample {
+            light,
+            color,
+            back_face,
+            point: pick.point,
+            normal,
+        })
+    }
+
+    /// Texel of the material of the picked triangle at the picked point, if it
+    /// is textured.
+    fn sample_texture(&self, pick: &Pick) -> Option<Color> {
+        let mesh = self.scene.get_objects()[pick.object].get_mesh();
+        let triangle = &mesh.triangles[pick.triangle];
+        let texture = mesh.get_material(triangle.group)?.texture.as_ref()?;
+        let uvs = triangle.uvs?;
+
+        let [a, b, c] = triangle.vertices_indices.map(|index| mesh.vertices[index]);
+        let area = (b - a).cross_product(&(c - a));
+        let area_squared = area.dot_product(&area);
+        if area_squared <= f32::EPSILON {
+            return None;
+        }
+        let weight = |from: Vector3D, to: Vector3D| {
+            (to - from)
+                .cross_product(&(pick.point - from))
+                .dot_product(&area)
+                / area_squared
+        };
+        let weights = [weight(b, c), weight(c, a), weight(a, b)];
+
+        let uv = Vector2D::new(
+            weights[0] * uvs[0][0] + weights[1] * uvs[1][0] + weights[2] * uvs[2][0],
+            weights[0] * uvs[0][1] + weights[1] * uvs[1][1] + weights[2] * uvs[2][1],
+        );
+        Some(texture.sample(&uv))
+    }
+}
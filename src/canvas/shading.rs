@@ -1,23 +1,304 @@
 // Centralized shading lookup table and saturating mapping from [0,1] to ASCII chars.
+use crate::entities::Color;
 
 // Keep this sorted from darkest to brightest for consistency with shading values.
 pub const GRAYSCALE_LUT: &[char] = &[
     '.', ':', '-', '"', '+', '=', 'c', 'o', '*', '%', '#', 'M', '@',
 ];
 
+// Ramp used for the inner side of faces when culling is disabled. It shares no
+// glyph with GRAYSCALE_LUT so the inside of a model is easy to tell apart.
+pub const BACKFACE_LUT: &[char] = &['`', ',', ';', '!', 'i', 'l', 'x', 'X', '$'];
+
+// Hatch drawn over the section left by a clipping plane.
+pub const CAP_CHAR: char = '/';
+
+// Outline and inside of a layer in the layer view.
+pub const LAYER_EDGE_CHAR: char = '#';
+pub const LAYER_FILL_CHAR: char = '.';
+
+// Picked triangle and picking cursor.
+pub const HIGHLIGHT_CHAR: char = '&';
+pub const CURSOR_CHAR: char = '+';
+
+// Segments between measured points, measured face and bounding boxes.
+pub const MEASURE_CHAR: char = '~';
+pub const MEASURE_FACE_CHAR: char = '^';
+pub const BOX_CHAR: char = '\'';
+
+// Color of the marks drawn over everything else, such as the picking cursor.
+pub const HIGHLIGHT_COLOR: Color = Color::new(255, 0, 255);
+
 /// Maps a shading value in [0.0, 1.0] to a character using the LUT.
 /// Values outside the range are clamped to [0.0, 1.0].
 #[inline]
 pub fn shade_to_char(value: f32) -> char {
-    if GRAYSCALE_LUT.is_empty() {
+    shade_to_char_with(GRAYSCALE_LUT, value)
+}
+
+/// Same as `shade_to_char` but using the back face ramp.
+#[inline]
+pub fn shade_to_back_char(value: f32) -> char {
+    shade_to_char_with(BACKFACE_LUT, value)
+}
+
+#[inline]
+fn shade_to_char_with(lut: &[char], value: f32) -> char {
+    if lut.is_empty() {
         return ' ';
     }
 
     // Clamp value to [0, 1]
-    let v = value.max(0.0).min(1.0);
+    let v = value.clamp(0.0, 1.0);
 
     // Map to [0, len-1], biased so 1.0 goes to the last index
-    let len = GRAYSCALE_LUT.len() as f32;
+    let len = lut.len() as f32;
     let idx = (v * (len - 1.0)).round() as usize;
-    GRAYSCALE_LUT[idx]
+    lut[idx]
+}
+
+// Paul Bourke's 70 character ramp, from the darkest to the brightest.
+const CLASSIC_RAMP: &str =
+    " .'`^\",:;Il!i><~+_-?][}{1)(|\\/tfjrxnuvczXYUJCLQ0OZmwqpdbkhao*#MW&8%B@$";
+const BLOCKS_RAMP: &str = "░▒▓█";
+const DIGITS_RAMP: &str = "0123456789";
+
+// 4x4 Bayer matrix for ordered dithering.
+const BAYER_MATRIX: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+/// Built in glyph ramps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ramp {
+    /// `GRAYSCALE_LUT`.
+    #[default]
+    Standard,
+    /// The 70 character ramp by Paul Bourke.
+    Classic,
+    /// Unicode shade blocks.
+    Blocks,
+    Digits,
+}
+
+impl Ramp {
+    pub fn from_name(name: &str) -> Option<Ramp> {
+        match name {
+            "standard" => Some(Ramp::Standard),
+            "classic" => Some(Ramp::Classic),
+            "blocks" => Some(Ramp::Blocks),
+            "digits" => Some(Ramp::Digits),
+            _ => None,
+        }
+    }
+
+    /// Glyphs from the darkest to the brightest.
+    pub fn chars(&self) -> Vec<char> {
+        match self {
+            Ramp::Standard => GRAYSCALE_LUT.to_vec(),
+            Ramp::Classic => CLASSIC_RAMP.chars().collect(),
+            Ramp::Blocks => BLOCKS_RAMP.chars().collect(),
+            Ramp::Digits => DIGITS_RAMP.chars().collect(),
+        }
+    }
+}
+
+/// How shades between two glyphs of the ramp are spread over the cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dithering {
+    /// Round every cell to the nearest glyph.
+    #[default]
+    None,
+    /// Ordered dithering with a 4x4 Bayer matrix.
+    Bayer,
+    /// Floyd-Steinberg error diffusion.
+    FloydSteinberg,
+}
+
+impl Dithering {
+    pub fn from_name(name: &str) -> Option<Dithering> {
+        match name {
+            "none" => Some(Dithering::None),
+            "bayer" => Some(Dithering::Bayer),
+            "floyd-steinberg" => Some(Dithering::FloydSteinberg),
+            _ => None,
+        }
+    }
+}
+
+/// Light reaching a canvas cell, turned into a glyph when the frame is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shade {
+    pub value: f32,
+    /// Drawn with the back face ramp.
+    pub back_face: bool,
+}
+
+/// Cel shading: the light split into a few flat bands, with thick outlines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Toon {
+    pub bands: usize,
+    /// Glyph of the outlines.
+    pub outline: char,
+    /// Color of the outlines when drawing in color. The default color of the
+    /// terminal when None.
+    pub outline_color: Option<Color>,
+}
+
+/// What the shades of the faces show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visualization {
+    /// The light that reaches the faces.
+    #[default]
+    Shaded,
+    /// The distance to the observer, brighter when closer.
+    Depth,
+    /// How squarely the faces look at the observer, and in color their direction
+    /// in the axes of the model, as red, green and blue.
+    Normal,
+    /// The mean curvature, brighter on bulges and darker in hollows.
+    MeanCurvature,
+    /// The Gaussian curvature, brighter on domes and bowls and darker on saddles.
+    GaussianCurvature,
+}
+
+impl Visualization {
+    /// Whether the curvature of the meshes has to be baked for it.
+    pub fn uses_curvature(&self) -> bool {
+        matches!(
+            self,
+            Visualization::MeanCurvature | Visualization::GaussianCurvature
+        )
+    }
+}
+
+/// How shades become glyphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shading {
+    /// Glyphs of the front faces, from the darkest to the brightest.
+    pub ramp: Vec<char>,
+    /// Glyphs of the back faces when culling is disabled.
+    pub back_ramp: Vec<char>,
+    pub dithering: Dithering,
+    /// Values above 1 brighten the mid tones, and below 1 darken them.
+    pub gamma: f32,
+    /// Values above 1 stretch the shades away from the middle gray.
+    pub contrast: f32,
+    /// Draw silhouettes and creases with glyphs that follow their direction.
+    pub edges: bool,
+    pub toon: Option<Toon>,
+    /// Darken the cells whose ambient light is blocked by the surfaces around
+    /// them, as estimated from the depth of the image.
+    pub ssao: bool,
+    pub visualization: Visualization,
+}
+
+impl Default for Shading {
+    fn default() -> Self {
+        Shading {
+            ramp: GRAYSCALE_LUT.to_vec(),
+            back_ramp: BACKFACE_LUT.to_vec(),
+            dithering: Dithering::default(),
+            gamma: 1.0,
+            contrast: 1.0,
+            edges: false,
+            toon: None,
+            ssao: false,
+            visualization: Visualization::default(),
+        }
+    }
+}
+
+impl Shading {
+    /// Applies contrast and then gamma to a shade, clamped to [0, 1].
+    pub fn apply_curve(&self, value: f32) -> f32 {
+        let value = ((value - 0.5) * self.contrast + 0.5).clamp(0.0, 1.0);
+        value.powf(1.0 / self.gamma.max(f32::EPSILON))
+    }
+
+    /// Rounds a shade down to the bottom of its toon band. The darkest band is 0
+    /// and the brightest 1, so the ends of the ramp are used.
+    pub fn band(&self, value: f32) -> f32 {
+        let bands = match self.toon {
+            Some(toon) => toon.bands.max(1),
+            None => return value,
+        };
+        if bands == 1 {
+            return 1.0;
+        }
+
+        let band = (value.clamp(0.0, 1.0) * bands as f32)
+            .floor()
+            .min(bands as f32 - 1.0);
+        band / (bands - 1) as f32
+    }
+
+    /// Glyph of a single shade, without dithering.
+    pub fn shade_to_char(&self, shade: &Shade) -> char {
+        shade_to_char_with(self.get_ramp(shade), self.get_value(shade))
+    }
+
+    fn get_value(&self, shade: &Shade) -> f32 {
+        self.band(self.apply_curve(shade.value))
+    }
+
+    fn get_ramp(&self, shade: &Shade) -> &[char] {
+        if shade.back_face {
+            &self.back_ramp
+        } else {
+            &self.ramp
+        }
+    }
+
+    /// Glyphs of a grid of `cols` cells per row, the top row first. Cells without
+    /// a shade get no glyph and take no part in the dithering.
+    pub fn to_glyphs(&self, shades: &[Option<Shade>], cols: usize) -> Vec<Option<char>> {
+        let mut errors = vec![0.0; shades.len()];
+        let mut glyphs = vec![None; shades.len()];
+
+        for (index, shade) in shades.iter().enumerate() {
+            let shade = match shade {
+                Some(shade) => shade,
+                None => continue,
+            };
+            let ramp = self.get_ramp(shade);
+            if ramp.is_empty() {
+                glyphs[index] = Some(' ');
+                continue;
+            }
+
+            let (col, row) = (index % cols, index / cols);
+            let levels = (ramp.len() - 1) as f32;
+            let target = self.get_value(shade) * levels;
+            let level = match self.dithering {
+                Dithering::None => target.round(),
+                Dithering::Bayer => {
+                    (target + BAYER_MATRIX[row % 4][col % 4] / 16.0 - 15.0 / 32.0).round()
+                }
+                Dithering::FloydSteinberg => (target + errors[index]).round(),
+            }
+            .clamp(0.0, levels);
+            glyphs[index] = Some(ramp[level as usize]);
+
+            if self.dithering == Dithering::FloydSteinberg {
+                let error = target + errors[index] - level;
+                let neighbours = [
+                    (col + 1 < cols, index + 1, 7.0),
+                    (col > 0, index + cols - 1, 3.0),
+                    (true, index + cols, 5.0),
+                    (col + 1 < cols, index + cols + 1, 1.0),
+                ];
+                for (inside, neighbour, weight) in neighbours {
+                    if inside && matches!(shades.get(neighbour), Some(Some(_))) {
+                        errors[neighbour] += error * weight / 16.0;
+                    }
+                }
+            }
+        }
+
+        glyphs
+    }
 }
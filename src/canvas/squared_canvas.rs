@@ -1,159 +1,276 @@
-use crate::entities::VisibleIndexedMesh3D;
-use crate::entities::{Vector2D, VisibleTriangle2D};
-use super::shading::shade_to_char;
+use super::edges::{find_edges, thicken_edges, Surface};
+use super::frame::{Cell, Frame};
+use super::occlusion::screen_space_occlusion;
+use super::shading::{Shade, Shading, HIGHLIGHT_COLOR};
+use super::traits::{Canvas, Sample};
+use crate::entities::Color;
 use std::char;
-use std::ops::Range;
 use std::sync::{Arc, Mutex};
 
+/// Canvas of glyphs chosen from the light of the cells, with the effects of
+/// its `Shading`.
 #[derive(Debug, Clone)]
 pub struct SquaredCanvas {
     pub cols: usize,
     pub zero_col: usize,
     pub maximum_diameter: f32,
-    pub chars_per_row: usize,
-    pub data_length: usize,
+    /// Cells stored row by row from the bottom left one.
     pub data: Arc<Mutex<Vec<char>>>,
-    pub mean_z: Arc<Mutex<Vec<f32>>>,
+    /// Depth of what each cell shows. Larger is closer to the observer.
+    pub depths: Arc<Mutex<Vec<f32>>>,
+    pub colors: Arc<Mutex<Vec<Option<Color>>>>,
+    /// Light of the cells covered by faces, whose glyphs are chosen in `get_frame`.
+    pub shades: Arc<Mutex<Vec<Option<Shade>>>>,
+    /// Depth and normal of the surfaces seen through the cells, to find edges.
+    pub surfaces: Arc<Mutex<Vec<Option<Surface>>>>,
+    pub shading: Shading,
 }
 
+// Share of the base color kept on the darkest faces, so colors stay recognisable.
+const AMBIENT_COLOR: f32 = 0.35;
+
+// Share of the light taken away from the points in shadow.
+pub(super) const SHADOW_DARKENING: f32 = 0.7;
+
+// Share of the light taken away from the points whose ambient light is all blocked.
+pub(super) const OCCLUSION_DARKENING: f32 = 0.6;
+
 impl SquaredCanvas {
     pub fn new(cols: usize, maximum_diameter: f32) -> SquaredCanvas {
-        if cols % 2 == 0 {
+        if cols.is_multiple_of(2) {
             panic!("cols must be odd");
         }
 
-        let rows = cols;
-        let chars_per_row = 3 * cols - 2;
-        let data_length = chars_per_row * rows;
+        let length = cols * cols;
         let zero_col = (cols - 1) / 2;
 
         SquaredCanvas {
             cols,
             zero_col,
             maximum_diameter,
-            chars_per_row,
-            data_length,
-            data: Arc::new(Mutex::new(vec![' '; data_length])),
-            mean_z: Arc::new(Mutex::new(vec![-f32::INFINITY; data_length])),
+            data: Arc::new(Mutex::new(vec![' '; length])),
+            depths: Arc::new(Mutex::new(vec![-f32::INFINITY; length])),
+            colors: Arc::new(Mutex::new(vec![None; length])),
+            shades: Arc::new(Mutex::new(vec![None; length])),
+            surfaces: Arc::new(Mutex::new(vec![None; length])),
+            shading: Shading::default(),
         }
     }
 
-    pub fn get_frame(&self) -> String {
-        let mut frame = String::with_capacity(self.data_length + 2 * self.cols);
-        let data = self.get_data();
+    pub fn set_shading(&mut self, shading: Shading) {
+        self.shading = shading;
+    }
 
-        let mut line = String::with_capacity(self.chars_per_row);
-        for (i, char) in data.iter().enumerate() {
-            line.push(*char);
+    fn get_index(&self, col: &isize, row: &isize) -> Option<usize> {
+        let transported_col = *col + self.zero_col as isize;
+        let transported_row = *row + self.zero_col as isize;
 
-            if (i + 1) % self.chars_per_row == 0 {
-                line = line.chars().rev().collect();
-                line.push('\r');
-                line.push('\n');
-                frame.push_str(&line);
-                line.clear();
-            }
+        if transported_col < 0
+            || transported_row < 0
+            || transported_col >= self.cols as isize
+            || transported_row >= self.cols as isize
+        {
+            return None;
         }
 
-        frame
+        Some(transported_col as usize + transported_row as usize * self.cols)
+    }
+
+    fn set_pixel(
+        &self,
+        index: usize,
+        char: char,
+        shade: Option<Shade>,
+        color: Option<Color>,
+        surface: Option<Surface>,
+    ) {
+        self.data.lock().unwrap()[index] = char;
+        self.shades.lock().unwrap()[index] = shade;
+        self.colors.lock().unwrap()[index] = color;
+        self.surfaces.lock().unwrap()[index] = surface;
+    }
+
+    /// `color` lit with `shadow_value`, in the toon bands if there are any.
+    fn light_color(&self, shadow_value: f32, color: Option<Color>) -> Option<Color> {
+        let shadow_value = self.shading.band(shadow_value);
+        color.map(|color| color.scale(AMBIENT_COLOR + (1.0 - AMBIENT_COLOR) * shadow_value))
+    }
+}
+
+impl Canvas for SquaredCanvas {
+    fn get_cols(&self) -> usize {
+        self.cols
     }
 
-    pub fn set_mesh(&self, visible_mesh: &VisibleIndexedMesh3D) {
+    fn get_maximum_diameter(&self) -> f32 {
+        self.maximum_diameter
+    }
+
+    fn clear(&self) {
         self.data.lock().unwrap().iter_mut().for_each(|char| {
             *char = ' ';
         });
 
-        self.mean_z.lock().unwrap().iter_mut().for_each(|z| {
-            *z = -f32::INFINITY;
+        self.depths.lock().unwrap().iter_mut().for_each(|depth| {
+            *depth = -f32::INFINITY;
         });
 
-        visible_mesh.iter().for_each(|triangle| {
-            self.set_triangle(&triangle);
+        self.colors.lock().unwrap().iter_mut().for_each(|color| {
+            *color = None;
         });
-    }
 
-    fn get_data(&self) -> Vec<char> {
-        let data_reversed = self.data.lock().unwrap().to_vec();
+        self.shades.lock().unwrap().iter_mut().for_each(|shade| {
+            *shade = None;
+        });
 
-        data_reversed.into_iter().rev().collect()
+        self.surfaces
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .for_each(|surface| {
+                *surface = None;
+            });
     }
 
-    fn get_index(&self, col: &isize, row: &isize) -> Option<usize> {
-        let transported_col = *col + self.zero_col as isize;
-        let transported_row = *row + self.zero_col as isize;
-
-        if transported_col < 0
-            || transported_row < 0
-            || transported_col >= self.cols as isize
-            || transported_row >= self.cols as isize
+    fn set_sample(&self, col: isize, row: isize, sample: &Sample) {
+        let index = match self.get_index(&col, &row) {
+            Some(index) => index,
+            None => return,
+        };
         {
-            return None;
+            let mut depths = self.depths.lock().unwrap();
+            if sample.depth <= depths[index] {
+                return;
+            }
+            depths[index] = sample.depth;
         }
 
-        let transported_col = transported_col as usize;
-        let transported_row = transported_row as usize;
-        Some(3 * transported_col + transported_row * self.chars_per_row)
+        let color = match sample.light {
+            Some(light) => self.light_color(light, sample.color),
+            None => sample.color,
+        };
+        let surface = Surface {
+            depth: sample.depth,
+            normal: sample.normal,
+        };
+        self.set_pixel(
+            index,
+            self.shading.shade_to_char(&sample.shade),
+            Some(sample.shade),
+            color,
+            Some(surface),
+        );
     }
 
-    fn set_pixel(&self, col: &isize, row: &isize, shadow_char: &char, mean_z: &f32) {
-        if let Some(index) = self.get_index(col, row) {
-            let current_mean_z = self.mean_z.lock().unwrap()[index];
-            if *mean_z > current_mean_z {
-                self.mean_z.lock().unwrap()[index] = *mean_z;
-                self.data.lock().unwrap()[index] = *shadow_char;
+    fn set_char(
+        &self,
+        col: isize,
+        row: isize,
+        char: char,
+        depth: f32,
+        color: Option<Color>,
+        surface: Option<Surface>,
+    ) {
+        let index = match self.get_index(&col, &row) {
+            Some(index) => index,
+            None => return,
+        };
+        {
+            let mut depths = self.depths.lock().unwrap();
+            if depth <= depths[index] {
+                return;
             }
+            depths[index] = depth;
         }
-    }
 
-    fn coordinates_to_indexes(&self, x: &f32, y: &f32) -> (isize, isize) {
-        let col = ((x / self.maximum_diameter) * (self.cols) as f32).ceil() as isize;
-        let row = ((y / self.maximum_diameter) * (self.cols) as f32).ceil() as isize;
-        (col, row)
+        let surface = surface.or(self.surfaces.lock().unwrap()[index]);
+        self.set_pixel(index, char, None, color, surface);
     }
 
-    fn indexes_to_coordinates(&self, col: isize, row: isize) -> Vector2D {
-        let x = (col as f32 / (self.cols) as f32) * self.maximum_diameter;
-        let y = (row as f32 / (self.cols) as f32) * self.maximum_diameter;
-        Vector2D::new(x, y)
+    /// Keeps the surface of the cell so the edges around it do not change.
+    fn set_mark(&self, col: isize, row: isize, char: char) {
+        if let Some(index) = self.get_index(&col, &row) {
+            self.depths.lock().unwrap()[index] = f32::INFINITY;
+            let surface = self.surfaces.lock().unwrap()[index];
+            self.set_pixel(index, char, None, Some(HIGHLIGHT_COLOR), surface);
+        }
     }
 
-    fn set_triangle(&self, triangle_2d: &VisibleTriangle2D) {
-        let [min_point, max_point] = triangle_2d.get_bounding_box_2d();
-        let shadow_value = triangle_2d.shadow_value;
-        let shadow_char = shade_to_char(shadow_value);
-        let mean_z = triangle_2d.mean_z;
+    /// The glyphs of the shaded cells, with the edges and the ambient occlusion
+    /// of the shading in place.
+    fn get_frame(&self) -> Frame {
+        let data = self.data.lock().unwrap();
+        let colors = self.colors.lock().unwrap();
+        let shades = self.shades.lock().unwrap();
+        let surfaces = self.surfaces.lock().unwrap();
+
+        // Frames go from the top row, and dithering runs over the cells in that order.
+        let indexes: Vec<usize> = (0..self.cols)
+            .rev()
+            .flat_map(|row| (0..self.cols).map(move |col| col + row * self.cols))
+            .collect();
+        let mut frame = Frame::new(self.cols, self.cols);
+        for (cell, index) in frame.cells.iter_mut().zip(indexes.iter()) {
+            cell.char = data[*index];
+            cell.color = colors[*index];
+        }
+        let mut cell_shades: Vec<Option<Shade>> =
+            indexes.iter().map(|index| shades[*index]).collect();
+        let cell_surfaces: Vec<Option<Surface>> =
+            indexes.iter().map(|index| surfaces[*index]).collect();
+        let cell_size = self.get_cell_size();
 
-        let (min_col, min_row) = self.coordinates_to_indexes(&min_point[0], &min_point[1]);
-        let (max_col, max_row) = self.coordinates_to_indexes(&max_point[0], &max_point[1]);
+        if self.shading.ssao {
+            let occlusion = screen_space_occlusion(&cell_surfaces, self.cols, cell_size);
+            for ((cell, shade), occlusion) in frame
+                .cells
+                .iter_mut()
+                .zip(cell_shades.iter_mut())
+                .zip(occlusion)
+            {
+                if let Some(shade) = shade {
+                    let light = 1.0 - OCCLUSION_DARKENING * occlusion;
+                    shade.value *= light;
+                    cell.color = cell.color.map(|color| color.scale(light));
+                }
+            }
+        }
 
-        let bound = self.zero_col as isize;
-        let min_row_c = min_row.clamp(-bound, bound);
-        let max_row_c = max_row.clamp(-bound, bound);
-        let min_col_c = min_col.clamp(-bound, bound);
-        let max_col_c = max_col.clamp(-bound, bound);
+        let glyphs = self.shading.to_glyphs(&cell_shades, self.cols);
+        for ((cell, glyph), shade) in frame.cells.iter_mut().zip(glyphs).zip(cell_shades) {
+            if let (Some(glyph), Some(shade)) = (glyph, shade) {
+                cell.char = glyph;
+                cell.light = Some(self.shading.band(self.shading.apply_curve(shade.value)));
+            }
+        }
 
-        if min_row_c > max_row_c || min_col_c > max_col_c {
-            return;
+        if !self.shading.edges && self.shading.toon.is_none() {
+            return frame;
         }
 
-        let row_range = Self::get_asc_range(min_row_c, max_row_c);
-        let col_range = Self::get_asc_range(min_col_c, max_col_c);
+        let edges = find_edges(&cell_surfaces, self.cols, cell_size);
 
-        for row in row_range {
-            for col in col_range.clone() {
-                let point = self.indexes_to_coordinates(col, row);
-                if triangle_2d.contains_point(&point) {
-                    self.set_pixel(&col, &row, &shadow_char, &mean_z);
+        if let Some(toon) = self.shading.toon {
+            let outline = thicken_edges(&edges, self.cols);
+            for (cell, outline) in frame.cells.iter_mut().zip(outline) {
+                if outline {
+                    *cell = Cell {
+                        char: toon.outline,
+                        color: toon.outline_color,
+                        light: None,
+                    };
                 }
             }
         }
-    }
 
-    fn get_asc_range(number_1: isize, number_2: isize) -> Range<isize> {
-        if number_1 < number_2 {
-            number_1..(number_2 + 1)
-        } else {
-            number_2..(number_1 + 1)
+        if self.shading.edges {
+            for (cell, edge) in frame.cells.iter_mut().zip(edges) {
+                if let Some(edge) = edge {
+                    cell.char = edge.glyph();
+                    cell.light = None;
+                }
+            }
         }
+        frame
     }
 }
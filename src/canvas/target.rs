@@ -0,0 +1,338 @@
+use super::frame::{Cell, Frame};
+use super::traits::{RenderTarget, TextTarget};
+use crate::entities::Color;
+
+// Light under which the dots of a braille cell are off, for the 2 by 4 dots of
+// a character, so that the dots come on one after the other as the light grows.
+const BRAILLE_THRESHOLDS: [[f32; 2]; 4] = [
+    [0.0625, 0.5625],
+    [0.8125, 0.3125],
+    [0.1875, 0.6875],
+    [0.9375, 0.4375],
+];
+
+// Bits of the braille dots, in the same order as the thresholds.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Text where every cell is a glyph followed by two spaces, so the cells come
+/// out about as wide as they are tall, optionally colored with ANSI escapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciiTarget {
+    pub colored: bool,
+    /// Appended to every line, such as "\r\n" for a terminal in raw mode.
+    pub line_ending: String,
+}
+
+impl Default for AsciiTarget {
+    fn default() -> Self {
+        AsciiTarget {
+            colored: false,
+            line_ending: String::from("\n"),
+        }
+    }
+}
+
+impl AsciiTarget {
+    pub fn new(colored: bool, line_ending: &str) -> AsciiTarget {
+        AsciiTarget {
+            colored,
+            line_ending: line_ending.to_string(),
+        }
+    }
+}
+
+impl RenderTarget for AsciiTarget {
+    type Output = String;
+
+    fn draw(&self, frame: &Frame) -> String {
+        write_text(&self.draw_cells(frame), self.colored, &self.line_ending)
+    }
+
+    fn get_size(&self, cols: usize, rows: usize) -> (usize, usize) {
+        ((3 * cols).saturating_sub(2), rows)
+    }
+
+    fn get_cell_at(
+        &self,
+        column: usize,
+        line: usize,
+        cols: usize,
+        rows: usize,
+    ) -> Option<(usize, usize)> {
+        let (col, row) = (column / 3, line);
+        (col < cols && row < rows).then_some((col, row))
+    }
+}
+
+impl TextTarget for AsciiTarget {
+    fn draw_cells(&self, frame: &Frame) -> Frame {
+        let (width, rows) = self.get_size(frame.cols, frame.rows);
+        let mut text = Frame::new(width, rows);
+        for (index, cell) in frame.cells.iter().enumerate() {
+            let (col, row) = (index % frame.cols, index / frame.cols);
+            text.cells[3 * col + row * width] = *cell;
+        }
+        text
+    }
+}
+
+/// Text of braille characters, each showing 2 by 4 cells as dots, for four
+/// times the resolution of `AsciiTarget` in the same space. The light of the
+/// cells chooses which dots are on, and the cells of fixed glyphs are all on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrailleTarget {
+    /// Whether the characters take the average color of their cells.
+    pub colored: bool,
+    pub line_ending: String,
+}
+
+impl Default for BrailleTarget {
+    fn default() -> Self {
+        BrailleTarget {
+            colored: false,
+            line_ending: String::from("\n"),
+        }
+    }
+}
+
+impl BrailleTarget {
+    pub fn new(colored: bool, line_ending: &str) -> BrailleTarget {
+        BrailleTarget {
+            colored,
+            line_ending: line_ending.to_string(),
+        }
+    }
+
+    /// Braille character at `column` and `line` of the text of `frame`, with
+    /// the average color of its cells, or None when all its dots are off.
+    pub fn get_glyph(frame: &Frame, column: usize, line: usize) -> Option<(char, Option<Color>)> {
+        let mut dots = 0;
+        let mut colors = Vec::new();
+        for (dy, row_dots) in BRAILLE_DOTS.iter().enumerate() {
+            for (dx, dot) in row_dots.iter().enumerate() {
+                let cell = match frame.get(2 * column + dx, 4 * line + dy) {
+                    Some(cell) => cell,
+                    None => continue,
+                };
+                if is_dot_on(cell, BRAILLE_THRESHOLDS[dy][dx]) {
+                    dots |= dot;
+                }
+                if cell.char != ' ' {
+                    colors.extend(cell.color);
+                }
+            }
+        }
+
+        if dots == 0 {
+            return None;
+        }
+        Some((char::from_u32(0x2800 + dots)?, average(&colors)))
+    }
+}
+
+impl RenderTarget for BrailleTarget {
+    type Output = String;
+
+    fn draw(&self, frame: &Frame) -> String {
+        write_text(&self.draw_cells(frame), self.colored, &self.line_ending)
+    }
+
+    fn get_size(&self, cols: usize, rows: usize) -> (usize, usize) {
+        (cols.div_ceil(2), rows.div_ceil(4))
+    }
+
+    /// The top left cell of those the character shows.
+    fn get_cell_at(
+        &self,
+        column: usize,
+        line: usize,
+        cols: usize,
+        rows: usize,
+    ) -> Option<(usize, usize)> {
+        let (col, row) = (2 * column, 4 * line);
+        (col < cols && row < rows).then_some((col, row))
+    }
+}
+
+impl TextTarget for BrailleTarget {
+    fn draw_cells(&self, frame: &Frame) -> Frame {
+        let (cols, rows) = self.get_size(frame.cols, frame.rows);
+        let mut text = Frame::new(cols, rows);
+        for line in 0..rows {
+            for column in 0..cols {
+                if let Some((char, color)) = Self::get_glyph(frame, column, line) {
+                    text.cells[column + line * cols] = Cell {
+                        char,
+                        color,
+                        light: None,
+                    };
+                }
+            }
+        }
+        text
+    }
+}
+
+/// Binary PPM image, in which every cell is a square of `cell_size` pixels
+/// painted with its color, or with its light in gray when it has none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PpmTarget {
+    pub cell_size: usize,
+}
+
+impl Default for PpmTarget {
+    fn default() -> Self {
+        PpmTarget { cell_size: 4 }
+    }
+}
+
+impl RenderTarget for PpmTarget {
+    type Output = Vec<u8>;
+
+    fn draw(&self, frame: &Frame) -> Vec<u8> {
+        let (width, height) = (frame.cols * self.cell_size, frame.rows * self.cell_size);
+        let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+        image.reserve(3 * width * height);
+
+        for line in frame.lines() {
+            let pixels: Vec<u8> = line
+                .iter()
+                .flat_map(|cell| {
+                    let Color { r, g, b } = cell_color(cell);
+                    [r, g, b].repeat(self.cell_size)
+                })
+                .collect();
+            for _ in 0..self.cell_size {
+                image.extend_from_slice(&pixels);
+            }
+        }
+
+        image
+    }
+}
+
+/// SVG image with every cell as a glyph of `font_size` pixels on a black
+/// background, so the frames keep their look at any scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgTarget {
+    pub font_size: f32,
+}
+
+impl Default for SvgTarget {
+    fn default() -> Self {
+        SvgTarget { font_size: 12.0 }
+    }
+}
+
+impl RenderTarget for SvgTarget {
+    type Output = String;
+
+    fn draw(&self, frame: &Frame) -> String {
+        let size = self.font_size;
+        let (width, height) = (frame.cols as f32 * size, frame.rows as f32 * size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"black\"/>\n\
+             <g font-family=\"monospace\" font-size=\"{size}\" text-anchor=\"middle\" \
+             dominant-baseline=\"central\">\n"
+        );
+
+        for (row, line) in frame.lines().enumerate() {
+            for (col, cell) in line.iter().enumerate() {
+                if cell.char == ' ' {
+                    continue;
+                }
+                let Color { r, g, b } = cell.color.unwrap_or(Color::new(255, 255, 255));
+                let (x, y) = ((col as f32 + 0.5) * size, (row as f32 + 0.5) * size);
+                svg.push_str(&format!(
+                    "<text x=\"{x}\" y=\"{y}\" fill=\"rgb({r},{g},{b})\">{}</text>\n",
+                    escape(cell.char)
+                ));
+            }
+        }
+
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+}
+
+// Characters of a text frame, line after line, with a foreground escape where
+// the color of the glyphs changes if `colored`.
+fn write_text(text: &Frame, colored: bool, line_ending: &str) -> String {
+    let mut output = String::with_capacity(text.rows * (text.cols + line_ending.len()));
+
+    for line in text.lines() {
+        let mut current_color = None;
+
+        for cell in line {
+            if colored && cell.char != ' ' && cell.color != current_color {
+                output.push_str(&foreground(cell.color));
+                current_color = cell.color;
+            }
+            output.push(cell.char);
+        }
+
+        if current_color.is_some() {
+            output.push_str(&foreground(None));
+        }
+        output.push_str(line_ending);
+    }
+
+    output
+}
+
+fn foreground(color: Option<Color>) -> String {
+    match color {
+        Some(Color { r, g, b }) => format!("\x1b[38;2;{r};{g};{b}m"),
+        None => String::from("\x1b[39m"),
+    }
+}
+
+fn is_dot_on(cell: &Cell, threshold: f32) -> bool {
+    match cell.light {
+        Some(light) => light > threshold,
+        None => cell.char != ' ',
+    }
+}
+
+fn average(colors: &[Color]) -> Option<Color> {
+    if colors.is_empty() {
+        return None;
+    }
+
+    let count = colors.len() as u32;
+    let sum = colors.iter().fold([0u32; 3], |[r, g, b], color| {
+        [r + color.r as u32, g + color.g as u32, b + color.b as u32]
+    });
+    Some(Color::new(
+        (sum[0] / count) as u8,
+        (sum[1] / count) as u8,
+        (sum[2] / count) as u8,
+    ))
+}
+
+// What a cell looks like as a pixel: its color, else its light in gray, else
+// white for fixed glyphs and black for empty cells.
+fn cell_color(cell: &Cell) -> Color {
+    match (cell.color, cell.light) {
+        (Some(color), _) => color,
+        (None, Some(light)) => {
+            let gray = (light.clamp(0.0, 1.0) * 255.0).round() as u8;
+            Color::new(gray, gray, gray)
+        }
+        (None, None) if cell.char != ' ' => Color::new(255, 255, 255),
+        (None, None) => Color::new(0, 0, 0),
+    }
+}
+
+fn escape(char: char) -> String {
+    match char {
+        '&' => String::from("&amp;"),
+        '<' => String::from("&lt;"),
+        '>' => String::from("&gt;"),
+        '"' => String::from("&quot;"),
+        '\'' => String::from("&apos;"),
+        char => char.to_string(),
+    }
+}
@@ -0,0 +1,247 @@
+use super::edges::Surface;
+use super::frame::Frame;
+use super::shading::{Shade, LAYER_EDGE_CHAR, LAYER_FILL_CHAR};
+use crate::entities::{Color, Layer, Scene, Vector2D, Vector3D};
+use std::ops::RangeInclusive;
+
+/// Surface seen through a cell, as worked out by a renderer.
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    /// Light the glyph is chosen from.
+    pub shade: Shade,
+    /// Depth of the surface at the center of the cell. Larger is closer to the
+    /// observer.
+    pub depth: f32,
+    pub normal: Vector3D,
+    pub color: Option<Color>,
+    /// Light that darkens `color`, or None when the color is shown as it is.
+    pub light: Option<f32>,
+}
+
+/// Square grid of cells that renderers draw on, each showing the closest
+/// thing drawn on it. Cells are counted from the one at the center, with rows
+/// going up, and cover the square of side `get_maximum_diameter` around the
+/// origin of the scene.
+pub trait Canvas {
+    /// Cells per side, an odd number so that one is at the center.
+    fn get_cols(&self) -> usize;
+
+    fn get_maximum_diameter(&self) -> f32;
+
+    fn clear(&self);
+
+    /// Draws a surface on a cell if it is closer than what the cell shows.
+    fn set_sample(&self, col: isize, row: isize, sample: &Sample);
+
+    /// Draws a fixed glyph on a cell if `depth` is closer than what the cell
+    /// shows. The cell keeps its surface unless `surface` gives another one.
+    fn set_char(
+        &self,
+        col: isize,
+        row: isize,
+        char: char,
+        depth: f32,
+        color: Option<Color>,
+        surface: Option<Surface>,
+    );
+
+    /// Draws a glyph in the highlight color on a cell over everything else.
+    fn set_mark(&self, col: isize, row: isize, char: char);
+
+    /// The glyphs and colors of the cells, once every effect is applied.
+    fn get_frame(&self) -> Frame;
+
+    fn get_cell_size(&self) -> f32 {
+        self.get_maximum_diameter() / self.get_cols() as f32
+    }
+
+    /// Indexes of the columns, which are those of the rows as well.
+    fn get_cell_range(&self) -> RangeInclusive<isize> {
+        let bound = (self.get_cols() as isize - 1) / 2;
+        -bound..=bound
+    }
+
+    /// Center of a cell.
+    fn indexes_to_coordinates(&self, col: isize, row: isize) -> Vector2D {
+        let cell_size = self.get_cell_size();
+        Vector2D::new(col as f32 * cell_size, row as f32 * cell_size)
+    }
+
+    /// Cell that `x` and `y` fall on.
+    fn coordinates_to_indexes(&self, x: &f32, y: &f32) -> (isize, isize) {
+        let cell_size = self.get_cell_size();
+        let col = (x / cell_size).ceil() as isize;
+        let row = (y / cell_size).ceil() as isize;
+        (col, row)
+    }
+
+    /// Cells along a segment, with how far along it each one is, from 0 to 1.
+    fn get_segment_cells(&self, start: &Vector2D, end: &Vector2D) -> Vec<(isize, isize, f32)> {
+        let length = (*end - *start).norm();
+        let steps = (2.0 * length / self.get_cell_size()).ceil().max(1.0) as usize;
+
+        (0..=steps)
+            .map(|step| {
+                let t = step as f32 / steps as f32;
+                let x = start[0] + t * (end[0] - start[0]);
+                let y = start[1] + t * (end[1] - start[1]);
+                let (col, row) = self.coordinates_to_indexes(&x, &y);
+                (col, row, t)
+            })
+            .collect()
+    }
+
+    /// Marks the cells along a segment over everything else.
+    fn set_line_mark(&self, start: &Vector2D, end: &Vector2D, char: char) {
+        for (col, row, _) in self.get_segment_cells(start, end) {
+            self.set_mark(col, row, char);
+        }
+    }
+
+    /// Marks the cell of `point` over everything else.
+    fn set_point_mark(&self, point: &Vector2D, char: char) {
+        let (col, row) = self.coordinates_to_indexes(&point[0], &point[1]);
+        self.set_mark(col, row, char);
+    }
+
+    /// Marks the cells covered by `triangle` over everything else.
+    fn set_triangle_mark(&self, triangle: &[Vector2D; 3], char: char) {
+        let [p1, p2, p3] = *triangle;
+        let (min_col, min_row) =
+            self.coordinates_to_indexes(&p1[0].min(p2[0]).min(p3[0]), &p1[1].min(p2[1]).min(p3[1]));
+        let (max_col, max_row) =
+            self.coordinates_to_indexes(&p1[0].max(p2[0]).max(p3[0]), &p1[1].max(p2[1]).max(p3[1]));
+        let range = self.get_cell_range();
+        let clamp = |index: isize| index.clamp(*range.start(), *range.end());
+        let area = (p2 - p1).cross_product(&(p3 - p1));
+
+        for row in clamp(min_row)..=clamp(max_row) {
+            for col in clamp(min_col)..=clamp(max_col) {
+                let point = self.indexes_to_coordinates(col, row);
+                // Either winding, as the triangle may be seen from behind.
+                let sides = [(p1, p2), (p2, p3), (p3, p1)]
+                    .map(|(a, b)| (b - a).cross_product(&(point - a)) * area.signum());
+                if sides.iter().all(|side| *side >= 0.0) {
+                    self.set_mark(col, row, char);
+                }
+            }
+        }
+    }
+
+    /// Draws a segment, hidden where what the cells show is in front of it.
+    fn set_depth_line(&self, start: &Vector3D, end: &Vector3D, char: char, color: Option<Color>) {
+        let start_2d = Vector2D::new(start[0], start[1]);
+        let end_2d = Vector2D::new(end[0], end[1]);
+        for (col, row, t) in self.get_segment_cells(&start_2d, &end_2d) {
+            let depth = start[2] + t * (end[2] - start[2]);
+            self.set_char(col, row, char, depth, color, None);
+        }
+    }
+
+    /// Draws a layer seen from above, instead of anything else: the inside of
+    /// the closed contours filled and every contour outlined on top.
+    fn set_layer(&self, layer: &Layer) {
+        self.clear();
+
+        let polygons: Vec<&Vec<Vector2D>> = layer
+            .contours
+            .iter()
+            .filter(|contour| contour.closed)
+            .map(|contour| &contour.points)
+            .collect();
+
+        for row in self.get_cell_range() {
+            for col in self.get_cell_range() {
+                let point = self.indexes_to_coordinates(col, row);
+                let crossings = polygons
+                    .iter()
+                    .filter(|polygon| polygon_contains_point(polygon, &point))
+                    .count();
+
+                if crossings % 2 == 1 {
+                    self.set_char(col, row, LAYER_FILL_CHAR, 0.0, None, None);
+                }
+            }
+        }
+
+        for contour in layer.contours.iter() {
+            let segments = contour.points.windows(2).map(|pair| (pair[0], pair[1]));
+            let closing = match (
+                contour.closed,
+                contour.points.first(),
+                contour.points.last(),
+            ) {
+                (true, Some(first), Some(last)) => Some((*last, *first)),
+                _ => None,
+            };
+
+            for (start, end) in segments.chain(closing) {
+                for (col, row, _) in self.get_segment_cells(&start, &end) {
+                    self.set_char(col, row, LAYER_EDGE_CHAR, 1.0, None, None);
+                }
+            }
+        }
+    }
+}
+
+/// Draws the visible objects of a scene on a canvas, replacing what it showed.
+pub trait Renderer {
+    fn render(&self, scene: &Scene, canvas: &dyn Canvas);
+}
+
+/// Turns frames into something to show or save, such as text or an image.
+pub trait RenderTarget {
+    type Output;
+
+    fn draw(&self, frame: &Frame) -> Self::Output;
+
+    /// Characters and lines the output of a frame of `cols` by `rows` cells
+    /// takes when shown as text, one per cell unless the target says otherwise.
+    fn get_size(&self, cols: usize, rows: usize) -> (usize, usize) {
+        (cols, rows)
+    }
+
+    /// Column and row of the cell of a frame of `cols` by `rows` cells shown at
+    /// a character and line of the output, counted from zero, or None when
+    /// nothing of the frame is shown there.
+    fn get_cell_at(
+        &self,
+        column: usize,
+        line: usize,
+        cols: usize,
+        rows: usize,
+    ) -> Option<(usize, usize)> {
+        (column < cols && line < rows).then_some((column, line))
+    }
+}
+
+/// Target writing text, which also gives the characters it writes as a frame
+/// of one cell each, to place them on a screen with other text.
+pub trait TextTarget: RenderTarget<Output = String> {
+    /// The characters of the text of `frame` and their colors, in a frame as
+    /// large as `get_size` says.
+    fn draw_cells(&self, frame: &Frame) -> Frame;
+}
+
+// Even-odd rule, so holes come out right when the results of several polygons are combined.
+pub(super) fn polygon_contains_point(polygon: &[Vector2D], point: &Vector2D) -> bool {
+    let mut inside = false;
+    let mut previous = match polygon.last() {
+        Some(previous) => previous,
+        None => return false,
+    };
+
+    for current in polygon.iter() {
+        if (current[1] > point[1]) != (previous[1] > point[1]) {
+            let x = previous[0]
+                + (point[1] - previous[1]) / (current[1] - previous[1])
+                    * (current[0] - previous[0]);
+            if point[0] < x {
+                inside = !inside;
+            }
+        }
+        previous = current;
+    }
+
+    inside
+}
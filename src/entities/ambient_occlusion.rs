@@ -0,0 +1,121 @@
+use super::{Aabb, Bvh, IndexedMesh3D, Vector3D};
+use crate::parallel::*;
+use std::f32::consts::PI;
+
+// Reach of the occlusion rays, as a share of the diagonal of the bounding box.
+const OCCLUSION_DISTANCE: f32 = 0.25;
+
+// Distance the rays start away from the surface, as a share of the diagonal, so
+// they do not hit the faces around their own vertex.
+const RAY_OFFSET: f32 = 1e-4;
+
+impl IndexedMesh3D {
+    /// Computes how much of the sky every vertex sees, casting `rays` rays over the
+    /// hemisphere around its normal, and stores the blocked share in the `occlusion`
+    /// of the corners of the triangles.
+    pub fn bake_ambient_occlusion(&mut self, rays: usize) {
+        if rays == 0 || self.triangles.is_empty() {
+            return;
+        }
+
+        let bounds = Aabb::from_points(&self.vertices);
+        let diagonal = (bounds.max - bounds.min).norm();
+        let offset = RAY_OFFSET * diagonal;
+        let bvh = Bvh::new(self);
+        let normals = self.get_vertex_normals();
+
+        let occlusion: Vec<f32> = self
+            .vertices
+            .par_iter()
+            .zip(normals.par_iter())
+            .enumerate()
+            .map(|(index, (vertex, normal))| {
+                let normal = match normal {
+                    Some(normal) => normal,
+                    None => return 0.0,
+                };
+                let origin = Vector3D::new(
+                    vertex[0] + offset * normal[0],
+                    vertex[1] + offset * normal[1],
+                    vertex[2] + offset * normal[2],
+                );
+
+                let blocked = hemisphere_directions(normal, rays, index)
+                    .filter(|direction| {
+                        bvh.is_hit(
+                            self,
+                            &origin,
+                            direction,
+                            offset,
+                            OCCLUSION_DISTANCE * diagonal,
+                        )
+                    })
+                    .count();
+                blocked as f32 / rays as f32
+            })
+            .collect();
+
+        self.triangles.iter_mut().for_each(|triangle| {
+            triangle.occlusion = Some(triangle.vertices_indices.map(|index| occlusion[index]));
+        });
+    }
+
+    /// Unit normal of every vertex, as the sum of the faces around it weighted by
+    /// their area. None where the faces cancel out.
+    fn get_vertex_normals(&self) -> Vec<Option<Vector3D>> {
+        let mut sums = vec![[0.0; 3]; self.vertices.len()];
+        for triangle in self.triangles.iter() {
+            let [a, b, c] = triangle.vertices_indices.map(|index| self.vertices[index]);
+            let cross = (b - a).cross_product(&(c - a));
+            for index in triangle.vertices_indices {
+                for axis in 0..3 {
+                    sums[index][axis] += cross[axis];
+                }
+            }
+        }
+
+        sums.into_iter()
+            .map(|[x, y, z]| {
+                let norm = (x * x + y * y + z * z).sqrt();
+                (norm > f32::EPSILON).then(|| Vector3D::new(x / norm, y / norm, z / norm))
+            })
+            .collect()
+    }
+}
+
+/// `count` unit directions over the hemisphere around `normal`, denser towards it
+/// as the light that reaches a surface is, along a spiral turned by `seed` so that
+/// neighbouring vertices do not share their gaps.
+pub(crate) fn hemisphere_directions(
+    normal: &Vector3D,
+    count: usize,
+    seed: usize,
+) -> impl Iterator<Item = Vector3D> + '_ {
+    let golden_angle = PI * (3.0 - 5.0_f32.sqrt());
+    let helper = if normal[0].abs() < 0.9 {
+        Vector3D::new(1.0, 0.0, 0.0)
+    } else {
+        Vector3D::new(0.0, 1.0, 0.0)
+    };
+    let tangent = helper.cross_product(normal);
+    let norm = tangent.norm();
+    let tangent = Vector3D::new(tangent[0] / norm, tangent[1] / norm, tangent[2] / norm);
+    let bitangent = normal.cross_product(&tangent);
+
+    (0..count).map(move |sample| {
+        let height = (sample as f32 + 0.5) / count as f32;
+        let radius = height.sqrt();
+        let angle = (sample + seed) as f32 * golden_angle;
+        let (x, y, z) = (
+            radius * angle.cos(),
+            radius * angle.sin(),
+            (1.0 - height).sqrt(),
+        );
+
+        Vector3D::new(
+            x * tangent[0] + y * bitangent[0] + z * normal[0],
+            x * tangent[1] + y * bitangent[1] + z * normal[1],
+            x * tangent[2] + y * bitangent[2] + z * normal[2],
+        )
+    })
+}
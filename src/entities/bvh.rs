@@ -0,0 +1,483 @@
+use super::{IndexedMesh3D, Vector3D};
+
+// Most triangles kept in a leaf.
+const LEAF_SIZE: usize = 4;
+
+/// Axis aligned bounding box.
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb {
+    pub min: Vector3D,
+    pub max: Vector3D,
+}
+
+impl Aabb {
+    /// Box that contains nothing, to grow from.
+    pub fn empty() -> Aabb {
+        Aabb {
+            min: Vector3D::new(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+            max: Vector3D::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+        }
+    }
+
+    pub fn from_points(points: &[Vector3D]) -> Aabb {
+        let mut aabb = Aabb::empty();
+        points.iter().for_each(|point| aabb.grow_to(point));
+        aabb
+    }
+
+    pub fn grow_to(&mut self, point: &Vector3D) {
+        for axis in 0..3 {
+            self.min[axis] = self.min[axis].min(point[axis]);
+            self.max[axis] = self.max[axis].max(point[axis]);
+        }
+    }
+
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        let mut aabb = *self;
+        aabb.grow_to(&other.min);
+        aabb.grow_to(&other.max);
+        aabb
+    }
+
+    pub fn center(&self) -> Vector3D {
+        Vector3D::new(
+            (self.min[0] + self.max[0]) / 2.0,
+            (self.min[1] + self.max[1]) / 2.0,
+            (self.min[2] + self.max[2]) / 2.0,
+        )
+    }
+
+    /// Index of the axis along which the box is the longest.
+    pub fn longest_axis(&self) -> usize {
+        let size = self.max - self.min;
+        (0..3)
+            .max_by(|a, b| size[*a].total_cmp(&size[*b]))
+            .unwrap_or(0)
+    }
+
+    /// Whether the two boxes share any point, borders included.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        (0..3).all(|axis| self.min[axis] <= other.max[axis] && other.min[axis] <= self.max[axis])
+    }
+
+    /// Squared distance from `point` to the closest point of the box, zero inside.
+    pub fn distance_squared(&self, point: &Vector3D) -> f32 {
+        (0..3)
+            .map(|axis| {
+                let outside = (self.min[axis] - point[axis]).max(point[axis] - self.max[axis]);
+                outside.max(0.0).powi(2)
+            })
+            .sum()
+    }
+
+    /// Whether the ray from `origin` along `direction` enters the box before
+    /// `max_distance`, measured in lengths of `direction`.
+    fn is_hit(&self, origin: &Vector3D, direction: &Vector3D, max_distance: f32) -> bool {
+        let mut near: f32 = 0.0;
+        let mut far = max_distance;
+
+        for axis in 0..3 {
+            let inverse = 1.0 / direction[axis];
+            let t1 = (self.min[axis] - origin[axis]) * inverse;
+            let t2 = (self.max[axis] - origin[axis]) * inverse;
+            // NaN from a ray inside a flat box is ignored by min and max.
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+        }
+
+        near <= far
+    }
+}
+
+#[derive(Debug, Clone)]
+enum BvhContent {
+    /// Range of `Bvh::triangles`.
+    Leaf { first: usize, count: usize },
+    /// Indexes of the children in `Bvh::nodes`.
+    Inner { left: usize, right: usize },
+}
+
+#[derive(Debug, Clone)]
+struct BvhNode {
+    bounds: Aabb,
+    content: BvhContent,
+}
+
+/// Closest triangle crossed by a ray.
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    /// Index into the triangles of the mesh.
+    pub triangle: usize,
+    /// Distance from the origin of the ray, in lengths of its direction.
+    pub distance: f32,
+    pub point: Vector3D,
+}
+
+/// Point of a mesh closest to another point.
+#[derive(Debug, Clone, Copy)]
+pub struct NearestPoint {
+    /// Index into the triangles of the mesh.
+    pub triangle: usize,
+    pub distance: f32,
+    pub point: Vector3D,
+}
+
+/// Bounding volume hierarchy over the triangles of a mesh, to find what a ray
+/// hits without testing every triangle. The mesh is passed to the queries, and
+/// must be the one the hierarchy was built for.
+///
+/// When the vertices of the mesh move, `refit` updates the boxes without
+/// building the tree again. The queries stay exact, but after large changes the
+/// boxes overlap more and a new hierarchy is faster.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    /// Triangle indexes, grouped by leaf.
+    triangles: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn new(mesh: &IndexedMesh3D) -> Bvh {
+        let bounds: Vec<Aabb> = (0..mesh.triangles.len())
+            .map(|triangle| triangle_bounds(mesh, triangle))
+            .collect();
+
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            triangles: (0..mesh.triangles.len()).collect(),
+        };
+        if !bvh.triangles.is_empty() {
+            bvh.build(&bounds, 0, bvh.triangles.len());
+        }
+        bvh
+    }
+
+    /// Grows or shrinks the boxes to the current vertices of `mesh`, keeping the
+    /// tree. The mesh must have the same triangles as when it was built.
+    pub fn refit(&mut self, mesh: &IndexedMesh3D) {
+        // Children are always added after their parent, so going backwards
+        // visits them first.
+        for index in (0..self.nodes.len()).rev() {
+            self.nodes[index].bounds = match self.nodes[index].content {
+                BvhContent::Leaf { first, count } => self.triangles[first..first + count]
+                    .iter()
+                    .fold(Aabb::empty(), |aabb, triangle| {
+                        aabb.merge(&triangle_bounds(mesh, *triangle))
+                    }),
+                BvhContent::Inner { left, right } => {
+                    self.nodes[left].bounds.merge(&self.nodes[right].bounds)
+                }
+            };
+        }
+    }
+
+    /// Adds the node of `triangles[first..first + count]` and returns its index.
+    fn build(&mut self, bounds: &[Aabb], first: usize, count: usize) -> usize {
+        let range = first..first + count;
+        let node_bounds = self.triangles[range.clone()]
+            .iter()
+            .fold(Aabb::empty(), |aabb, triangle| {
+                aabb.merge(&bounds[*triangle])
+            });
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds: node_bounds,
+            content: BvhContent::Leaf { first, count },
+        });
+
+        if count <= LEAF_SIZE {
+            return index;
+        }
+
+        // Median split of the centers along the longest axis of the node.
+        let axis = node_bounds.longest_axis();
+        let middle = count / 2;
+        self.triangles[range].select_nth_unstable_by(middle, |a, b| {
+            bounds[*a].center()[axis].total_cmp(&bounds[*b].center()[axis])
+        });
+
+        let left = self.build(bounds, first, middle);
+        let right = self.build(bounds, first + middle, count - middle);
+        self.nodes[index].content = BvhContent::Inner { left, right };
+        index
+    }
+
+    /// Whether the ray from `origin` along `direction` hits a triangle of `mesh`
+    /// between `min_distance` and `max_distance`, measured in lengths of `direction`.
+    pub fn is_hit(
+        &self,
+        mesh: &IndexedMesh3D,
+        origin: &Vector3D,
+        direction: &Vector3D,
+        min_distance: f32,
+        max_distance: f32,
+    ) -> bool {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.is_hit(origin, direction, max_distance) {
+                continue;
+            }
+
+            match node.content {
+                BvhContent::Inner { left, right } => stack.extend([left, right]),
+                BvhContent::Leaf { first, count } => {
+                    let hit = self.triangles[first..first + count].iter().any(|triangle| {
+                        let vertices = triangle_vertices(mesh, *triangle);
+                        matches!(
+                            intersect_triangle(origin, direction, &vertices),
+                            Some(distance) if distance >= min_distance && distance <= max_distance
+                        )
+                    });
+                    if hit {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Closest triangle of `mesh` crossed by the ray from `origin` along `direction`
+    /// before `max_distance`, measured in lengths of `direction`.
+    pub fn intersect(
+        &self,
+        mesh: &IndexedMesh3D,
+        origin: &Vector3D,
+        direction: &Vector3D,
+        max_distance: f32,
+    ) -> Option<RayHit> {
+        self.intersect_where(mesh, origin, direction, max_distance, |_| true)
+    }
+
+    /// Like `intersect`, but only counting the hits `accept` returns true for,
+    /// so the ray goes through the others.
+    pub fn intersect_where(
+        &self,
+        mesh: &IndexedMesh3D,
+        origin: &Vector3D,
+        direction: &Vector3D,
+        max_distance: f32,
+        accept: impl Fn(&RayHit) -> bool,
+    ) -> Option<RayHit> {
+        let mut closest: Option<RayHit> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let reach = closest.map_or(max_distance, |hit| hit.distance);
+            let node = &self.nodes[index];
+            if !node.bounds.is_hit(origin, direction, reach) {
+                continue;
+            }
+
+            match node.content {
+                BvhContent::Inner { left, right } => stack.extend([left, right]),
+                BvhContent::Leaf { first, count } => {
+                    for triangle in self.triangles[first..first + count].iter() {
+                        let vertices = triangle_vertices(mesh, *triangle);
+                        let distance = match intersect_triangle(origin, direction, &vertices) {
+                            Some(distance) => distance,
+                            None => continue,
+                        };
+                        let reach = closest.map_or(max_distance, |hit| hit.distance);
+                        if distance < 0.0 || distance > reach {
+                            continue;
+                        }
+
+                        let hit = RayHit {
+                            triangle: *triangle,
+                            distance,
+                            point: Vector3D::new(
+                                origin[0] + distance * direction[0],
+                                origin[1] + distance * direction[1],
+                                origin[2] + distance * direction[2],
+                            ),
+                        };
+                        if accept(&hit) {
+                            closest = Some(hit);
+                        }
+                    }
+                }
+            }
+        }
+
+        closest
+    }
+
+    /// Point of the surface of `mesh` closest to `point`.
+    pub fn nearest_point(&self, mesh: &IndexedMesh3D, point: &Vector3D) -> Option<NearestPoint> {
+        let mut nearest: Option<(usize, Vector3D, f32)> = None;
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let best = nearest.map_or(f32::INFINITY, |(_, _, distance)| distance);
+            if node.bounds.distance_squared(point) > best {
+                continue;
+            }
+
+            match node.content {
+                BvhContent::Inner { left, right } => {
+                    // The closer child goes on top, so it is searched first.
+                    let left_distance = self.nodes[left].bounds.distance_squared(point);
+                    let right_distance = self.nodes[right].bounds.distance_squared(point);
+                    if left_distance < right_distance {
+                        stack.extend([right, left]);
+                    } else {
+                        stack.extend([left, right]);
+                    }
+                }
+                BvhContent::Leaf { first, count } => {
+                    for triangle in self.triangles[first..first + count].iter() {
+                        let vertices = triangle_vertices(mesh, *triangle);
+                        let closest = closest_point_on_triangle(point, &vertices);
+                        let offset = closest - *point;
+                        let distance = offset.dot_product(&offset);
+                        if distance < nearest.map_or(f32::INFINITY, |(_, _, best)| best) {
+                            nearest = Some((*triangle, closest, distance));
+                        }
+                    }
+                }
+            }
+        }
+
+        nearest.map(|(triangle, point, distance)| NearestPoint {
+            triangle,
+            distance: distance.sqrt(),
+            point,
+        })
+    }
+
+    /// Indexes of the triangles of `mesh` whose boxes overlap `aabb`, in no
+    /// particular order.
+    pub fn overlapping(&self, mesh: &IndexedMesh3D, aabb: &Aabb) -> Vec<usize> {
+        let mut triangles = Vec::new();
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.overlaps(aabb) {
+                continue;
+            }
+
+            match node.content {
+                BvhContent::Inner { left, right } => stack.extend([left, right]),
+                BvhContent::Leaf { first, count } => triangles.extend(
+                    self.triangles[first..first + count]
+                        .iter()
+                        .filter(|triangle| triangle_bounds(mesh, **triangle).overlaps(aabb)),
+                ),
+            }
+        }
+
+        triangles
+    }
+}
+
+fn triangle_vertices(mesh: &IndexedMesh3D, triangle: usize) -> [Vector3D; 3] {
+    mesh.triangles[triangle]
+        .vertices_indices
+        .map(|index| mesh.vertices[index])
+}
+
+fn triangle_bounds(mesh: &IndexedMesh3D, triangle: usize) -> Aabb {
+    Aabb::from_points(&triangle_vertices(mesh, triangle))
+}
+
+/// Distance along `direction` at which the ray from `origin` crosses the triangle,
+/// from either side (Möller-Trumbore).
+fn intersect_triangle(
+    origin: &Vector3D,
+    direction: &Vector3D,
+    vertices: &[Vector3D; 3],
+) -> Option<f32> {
+    let edge_1 = vertices[1] - vertices[0];
+    let edge_2 = vertices[2] - vertices[0];
+    let p = direction.cross_product(&edge_2);
+    let determinant = edge_1.dot_product(&p);
+    if determinant.abs() <= f32::EPSILON {
+        return None;
+    }
+
+    let to_origin = *origin - vertices[0];
+    let u = to_origin.dot_product(&p) / determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = to_origin.cross_product(&edge_1);
+    let v = direction.dot_product(&q) / determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some(edge_2.dot_product(&q) / determinant)
+}
+
+/// Point of the triangle closest to `point`, found from the region of the
+/// triangle it projects to (Ericson, Real-Time Collision Detection).
+fn closest_point_on_triangle(point: &Vector3D, vertices: &[Vector3D; 3]) -> Vector3D {
+    let [a, b, c] = *vertices;
+    let along = |from: Vector3D, to: Vector3D, t: f32| {
+        Vector3D::new(
+            from[0] + t * (to[0] - from[0]),
+            from[1] + t * (to[1] - from[1]),
+            from[2] + t * (to[2] - from[2]),
+        )
+    };
+    let (ab, ac, ap) = (b - a, c - a, *point - a);
+
+    let (d1, d2) = (ab.dot_product(&ap), ac.dot_product(&ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = *point - b;
+    let (d3, d4) = (ab.dot_product(&bp), ac.dot_product(&bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return along(a, b, d1 / (d1 - d3));
+    }
+
+    let cp = *point - c;
+    let (d5, d6) = (ab.dot_product(&cp), ac.dot_product(&cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return along(a, c, d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return along(b, c, (d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denominator = 1.0 / (va + vb + vc);
+    let (v, w) = (vb * denominator, vc * denominator);
+    Vector3D::new(
+        a[0] + v * ab[0] + w * ac[0],
+        a[1] + v * ab[1] + w * ac[1],
+        a[2] + v * ab[2] + w * ac[2],
+    )
+}
@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+// Distinct, reasonably bright colors for telling objects apart on dark terminals.
+const PALETTE: &[Color] = &[
+    Color::new(230, 159, 0),
+    Color::new(86, 180, 233),
+    Color::new(0, 158, 115),
+    Color::new(240, 228, 66),
+    Color::new(204, 121, 167),
+    Color::new(213, 94, 0),
+    Color::new(0, 114, 178),
+];
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /// Color from channels in [0, 1], as given by MTL files.
+    pub fn from_unit(r: f32, g: f32, b: f32) -> Color {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::new(channel(r), channel(g), channel(b))
+    }
+
+    /// Relative luminance in [0, 1].
+    pub fn luminance(&self) -> f32 {
+        (0.2126 * self.r as f32 + 0.7152 * self.g as f32 + 0.0722 * self.b as f32) / 255.0
+    }
+
+    /// Color number `index` of the default palette, repeating when it runs out.
+    pub fn palette(index: usize) -> Color {
+        PALETTE[index % PALETTE.len()]
+    }
+
+    /// Color of `value` in [0, 1] on a map that goes from blue through light gray
+    /// to red, for values with a meaningful middle.
+    pub fn diverging(value: f32) -> Color {
+        const COLD: [f32; 3] = [59.0, 76.0, 192.0];
+        const MIDDLE: [f32; 3] = [221.0, 221.0, 221.0];
+        const HOT: [f32; 3] = [180.0, 4.0, 38.0];
+
+        let value = value.clamp(0.0, 1.0);
+        let (from, to, t) = if value < 0.5 {
+            (COLD, MIDDLE, 2.0 * value)
+        } else {
+            (MIDDLE, HOT, 2.0 * value - 1.0)
+        };
+        let channel = |index: usize| (from[index] + t * (to[index] - from[index])).round() as u8;
+        Color::new(channel(0), channel(1), channel(2))
+    }
+
+    /// Multiplies every channel by `factor`, clamped to [0, 1].
+    pub fn scale(&self, factor: f32) -> Color {
+        let factor = factor.clamp(0.0, 1.0);
+        Color::new(
+            (self.r as f32 * factor).round() as u8,
+            (self.g as f32 * factor).round() as u8,
+            (self.b as f32 * factor).round() as u8,
+        )
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Parses `#rrggbb` or `rrggbb`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let hex = text.strip_prefix('#').unwrap_or(text);
+        let channel = |range| {
+            hex.get(range)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or(format!("Error: Invalid Color {text}"))
+        };
+
+        if hex.len() != 6 {
+            return Err(format!("Error: Invalid Color {text}"));
+        }
+        Ok(Color::new(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+    }
+}
@@ -0,0 +1,120 @@
+use super::{Aabb, IndexedMesh3D, Vector3D};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+// Distance under which vertices are merged before the curvature is measured, as a
+// share of the diagonal of the bounding box.
+const WELD_TOLERANCE: f32 = 1e-5;
+
+/// Curvature of the surface at a point.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Curvature {
+    /// Average of the principal curvatures. Positive where the surface bulges
+    /// outwards and negative in hollows.
+    pub mean: f32,
+    /// Product of the principal curvatures. Positive on domes and in bowls, and
+    /// negative on saddles.
+    pub gaussian: f32,
+}
+
+impl IndexedMesh3D {
+    /// Measures the curvature at every vertex and stores it in the `curvature` of
+    /// the corners of the triangles.
+    ///
+    /// Files often repeat the vertices shared by their faces, so the vertices
+    /// are welded first and the corners at the same place get the same value.
+    pub fn bake_curvature(&mut self) {
+        let bounds = Aabb::from_points(&self.vertices);
+        let diagonal = (bounds.max - bounds.min).norm();
+        let welded = self.weld(WELD_TOLERANCE * diagonal);
+        let curvatures = welded.get_vertex_curvatures();
+
+        // Welding keeps the triangles in place, only their indices change.
+        for (triangle, welded_triangle) in self.triangles.iter_mut().zip(welded.triangles.iter()) {
+            triangle.curvature = Some(
+                welded_triangle
+                    .vertices_indices
+                    .map(|index| curvatures[index]),
+            );
+        }
+    }
+
+    /// Discrete curvature of every vertex: the angle defect for the Gaussian one
+    /// and the cotangent Laplacian for the mean one, both over a third of the area
+    /// of the faces around the vertex.
+    fn get_vertex_curvatures(&self) -> Vec<Curvature> {
+        let count = self.vertices.len();
+        let mut areas = vec![0.0; count];
+        let mut angles = vec![0.0; count];
+        let mut laplacians = vec![[0.0; 3]; count];
+        let mut normals = vec![[0.0; 3]; count];
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for triangle in self.triangles.iter() {
+            let indices = triangle.vertices_indices;
+            let points = indices.map(|index| self.vertices[index]);
+            let cross = (points[1] - points[0]).cross_product(&(points[2] - points[0]));
+            let area = cross.norm() / 2.0;
+            if area <= f32::EPSILON {
+                continue;
+            }
+
+            for corner in 0..3 {
+                let (next, previous) = ((corner + 1) % 3, (corner + 2) % 3);
+                let (index, next_index, previous_index) =
+                    (indices[corner], indices[next], indices[previous]);
+                let to_next = points[next] - points[corner];
+                let to_previous = points[previous] - points[corner];
+
+                let cosine = to_next.dot_product(&to_previous);
+                let sine = to_next.cross_product(&to_previous).norm();
+                angles[index] += sine.atan2(cosine);
+                areas[index] += area / 3.0;
+
+                // The cotangent of this corner weighs the opposite edge.
+                let cotangent = cosine / sine;
+                let opposite = points[previous] - points[next];
+                for axis in 0..3 {
+                    laplacians[next_index][axis] += cotangent * opposite[axis];
+                    laplacians[previous_index][axis] -= cotangent * opposite[axis];
+                    normals[index][axis] += cross[axis];
+                }
+
+                let key = (
+                    next_index.min(previous_index),
+                    next_index.max(previous_index),
+                );
+                *edges.entry(key).or_insert(0) += 1;
+            }
+        }
+
+        // The angles around a vertex on an open border add up to half a turn.
+        let mut full_angles = vec![2.0 * PI; count];
+        for ((a, b), faces) in edges {
+            if faces == 1 {
+                full_angles[a] = PI;
+                full_angles[b] = PI;
+            }
+        }
+
+        (0..count)
+            .map(|index| {
+                let area = areas[index];
+                if area <= f32::EPSILON {
+                    return Curvature::default();
+                }
+
+                let [x, y, z] = normals[index];
+                let norm = (x * x + y * y + z * z).sqrt().max(f32::EPSILON);
+                let normal = Vector3D::new(x / norm, y / norm, z / norm);
+                let [x, y, z] = laplacians[index];
+                let laplacian = Vector3D::new(x, y, z);
+
+                Curvature {
+                    mean: -laplacian.dot_product(&normal) / (4.0 * area),
+                    gaussian: (full_angles[index] - angles[index]) / area,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,484 @@
+use super::{CalculateNormal, Curvature, IndexedMesh3D, IndexedTriangle3D, Vector3D};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// Boundary edges get an extra plane perpendicular to the face, weighted so open
+// borders are kept in place instead of being eaten away by the collapses.
+const BOUNDARY_WEIGHT: f64 = 1000.0;
+
+// A collapse is rejected when it turns any surviving face by more than ~78 degrees.
+const MINIMUM_NORMAL_COSINE: f32 = 0.2;
+
+/// Stop condition for `IndexedMesh3D::decimate`.
+#[derive(Debug, Clone, Copy)]
+pub enum DecimationTarget {
+    /// Collapse edges until at most this many triangles remain.
+    Triangles(usize),
+    /// Collapse edges while the quadric error of the next collapse stays below this bound.
+    MaximumError(f32),
+}
+
+/// Symmetric 4x4 matrix stored as its upper triangle.
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn from_plane(a: f64, b: f64, c: f64, d: f64, weight: f64) -> Quadric {
+        Quadric([
+            a * a * weight,
+            a * b * weight,
+            a * c * weight,
+            a * d * weight,
+            b * b * weight,
+            b * c * weight,
+            b * d * weight,
+            c * c * weight,
+            c * d * weight,
+            d * d * weight,
+        ])
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        self.0
+            .iter_mut()
+            .zip(other.0.iter())
+            .for_each(|(a, b)| *a += b);
+    }
+
+    fn sum(&self, other: &Quadric) -> Quadric {
+        let mut result = *self;
+        result.add(other);
+        result
+    }
+
+    fn error(&self, point: &Vector3D) -> f64 {
+        let [a, b, c, d, e, f, g, h, i, j] = self.0;
+        let (x, y, z) = (point[0] as f64, point[1] as f64, point[2] as f64);
+
+        a * x * x
+            + 2.0 * b * x * y
+            + 2.0 * c * x * z
+            + 2.0 * d * x
+            + e * y * y
+            + 2.0 * f * y * z
+            + 2.0 * g * y
+            + h * z * z
+            + 2.0 * i * z
+            + j
+    }
+
+    /// Point minimising the error, if the quadric is not singular.
+    fn optimal_point(&self) -> Option<Vector3D> {
+        let [a, b, c, d, e, f, g, h, i, _] = self.0;
+
+        let det = a * (e * h - f * f) - b * (b * h - f * c) + c * (b * f - e * c);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        // Cramer's rule on [[a b c] [b e f] [c f h]] * p = -[d g i]
+        let x = -(d * (e * h - f * f) - b * (g * h - f * i) + c * (g * f - e * i)) / det;
+        let y = -(a * (g * h - i * f) - d * (b * h - f * c) + c * (b * i - g * c)) / det;
+        let z = -(a * (e * i - f * g) - b * (b * i - g * c) + d * (b * f - e * c)) / det;
+
+        Some(Vector3D::new(x as f32, y as f32, z as f32))
+    }
+}
+
+#[derive(Debug)]
+struct Collapse {
+    cost: f64,
+    vertices: [usize; 2],
+    versions: [usize; 2],
+    target: Vector3D,
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    // Reversed so the `BinaryHeap` pops the cheapest collapse first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+struct Decimator {
+    vertices: Vec<Vector3D>,
+    triangles: Vec<[usize; 3]>,
+    removed_triangles: Vec<bool>,
+    vertex_triangles: Vec<Vec<usize>>,
+    quadrics: Vec<Quadric>,
+    versions: Vec<usize>,
+    heap: BinaryHeap<Collapse>,
+    live_triangles: usize,
+    /// Occlusion baked at each vertex, averaged over the collapses.
+    occlusion: Option<Vec<f32>>,
+    /// Curvature baked at each vertex, averaged over the collapses.
+    curvature: Option<Vec<Curvature>>,
+    /// Whether a collapse moved each vertex, which leaves the texture coordinates
+    /// of its corners wrong.
+    moved: Vec<bool>,
+}
+
+impl Decimator {
+    fn new(mesh: &IndexedMesh3D) -> Decimator {
+        let vertices = mesh.vertices.clone();
+        let triangles: Vec<[usize; 3]> = mesh
+            .triangles
+            .iter()
+            .map(|triangle| triangle.vertices_indices)
+            .collect();
+
+        let mut vertex_triangles = vec![Vec::new(); vertices.len()];
+        for (index, triangle) in triangles.iter().enumerate() {
+            for vertex in triangle {
+                vertex_triangles[*vertex].push(index);
+            }
+        }
+
+        let mut decimator = Decimator {
+            quadrics: vec![Quadric::default(); vertices.len()],
+            versions: vec![0; vertices.len()],
+            removed_triangles: vec![false; triangles.len()],
+            live_triangles: triangles.len(),
+            heap: BinaryHeap::new(),
+            occlusion: get_vertex_values(mesh, |triangle| triangle.occlusion),
+            curvature: get_vertex_values(mesh, |triangle| triangle.curvature),
+            moved: vec![false; vertices.len()],
+            vertices,
+            triangles,
+            vertex_triangles,
+        };
+
+        decimator.compute_quadrics();
+        decimator.compute_collapses();
+        decimator
+    }
+
+    fn compute_quadrics(&mut self) {
+        let mut edge_count: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+
+        for (index, triangle) in self.triangles.iter().enumerate() {
+            let [p0, p1, p2] = triangle.map(|vertex| self.vertices[vertex]);
+            let cross = (p1 - p0).cross_product(&(p2 - p0));
+            let double_area = cross.norm();
+            if double_area <= f32::EPSILON {
+                continue;
+            }
+
+            let normal = Vector3D::new(
+                cross[0] / double_area,
+                cross[1] / double_area,
+                cross[2] / double_area,
+            );
+            let d = -normal.dot_product(&p0);
+            let quadric = Quadric::from_plane(
+                normal[0] as f64,
+                normal[1] as f64,
+                normal[2] as f64,
+                d as f64,
+                double_area as f64 / 2.0,
+            );
+
+            for vertex in triangle {
+                self.quadrics[*vertex].add(&quadric);
+            }
+
+            for edge in 0..3 {
+                let key = edge_key(triangle[edge], triangle[(edge + 1) % 3]);
+                edge_count.entry(key).or_insert((0, index)).0 += 1;
+            }
+        }
+
+        for ((v0, v1), (count, triangle_index)) in edge_count {
+            if count != 1 {
+                continue;
+            }
+
+            let [p0, p1, p2] = self.triangles[triangle_index].map(|vertex| self.vertices[vertex]);
+            let face_normal = (p1 - p0).cross_product(&(p2 - p0));
+            let edge = self.vertices[v1] - self.vertices[v0];
+            let constraint = edge.cross_product(&face_normal);
+            let norm = constraint.norm();
+            if norm <= f32::EPSILON {
+                continue;
+            }
+
+            let normal = Vector3D::new(
+                constraint[0] / norm,
+                constraint[1] / norm,
+                constraint[2] / norm,
+            );
+            let d = -normal.dot_product(&self.vertices[v0]);
+            let quadric = Quadric::from_plane(
+                normal[0] as f64,
+                normal[1] as f64,
+                normal[2] as f64,
+                d as f64,
+                BOUNDARY_WEIGHT * (edge.norm() as f64).powi(2),
+            );
+
+            self.quadrics[v0].add(&quadric);
+            self.quadrics[v1].add(&quadric);
+        }
+    }
+
+    fn compute_collapses(&mut self) {
+        let mut edges = HashSet::new();
+        for triangle in self.triangles.iter() {
+            for edge in 0..3 {
+                edges.insert(edge_key(triangle[edge], triangle[(edge + 1) % 3]));
+            }
+        }
+
+        for (v0, v1) in edges {
+            self.push_collapse(v0, v1);
+        }
+    }
+
+    fn push_collapse(&mut self, v0: usize, v1: usize) {
+        let quadric = self.quadrics[v0].sum(&self.quadrics[v1]);
+        let p0 = self.vertices[v0];
+        let p1 = self.vertices[v1];
+        let midpoint = Vector3D::new(
+            (p0[0] + p1[0]) / 2.0,
+            (p0[1] + p1[1]) / 2.0,
+            (p0[2] + p1[2]) / 2.0,
+        );
+
+        let candidates = match quadric.optimal_point() {
+            Some(optimal) => vec![optimal],
+            None => vec![p0, p1, midpoint],
+        };
+
+        let (cost, target) = candidates
+            .into_iter()
+            .map(|point| (quadric.error(&point).max(0.0), point))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap();
+
+        self.heap.push(Collapse {
+            cost,
+            vertices: [v0, v1],
+            versions: [self.versions[v0], self.versions[v1]],
+            target,
+        });
+    }
+
+    fn run(&mut self, target: DecimationTarget) {
+        while let Some(collapse) = self.heap.pop() {
+            match target {
+                DecimationTarget::Triangles(count) if self.live_triangles <= count => break,
+                DecimationTarget::MaximumError(error) if collapse.cost > error as f64 => break,
+                _ => {}
+            }
+
+            let [v0, v1] = collapse.vertices;
+            if collapse.versions != [self.versions[v0], self.versions[v1]] {
+                continue;
+            }
+
+            if self.is_valid_collapse(v0, v1, &collapse.target) {
+                self.collapse(v0, v1, collapse.target);
+            }
+        }
+    }
+
+    fn neighbours(&self, vertex: usize) -> HashSet<usize> {
+        self.vertex_triangles[vertex]
+            .iter()
+            .flat_map(|triangle| self.triangles[*triangle])
+            .filter(|other| *other != vertex)
+            .collect()
+    }
+
+    fn is_valid_collapse(&self, v0: usize, v1: usize, target: &Vector3D) -> bool {
+        // Link condition: an interior edge shares exactly two neighbours, more would
+        // pinch the surface into a non-manifold shape.
+        let shared = self
+            .neighbours(v0)
+            .intersection(&self.neighbours(v1))
+            .count();
+        let shared_faces = self.vertex_triangles[v0]
+            .iter()
+            .filter(|triangle| self.triangles[**triangle].contains(&v1))
+            .count();
+        if shared > shared_faces {
+            return false;
+        }
+
+        for vertex in [v0, v1] {
+            for triangle_index in self.vertex_triangles[vertex].iter() {
+                let triangle = self.triangles[*triangle_index];
+                if triangle.contains(&v0) && triangle.contains(&v1) {
+                    continue;
+                }
+
+                let before = triangle.map(|index| self.vertices[index]);
+                let after = triangle.map(|index| {
+                    if index == vertex {
+                        *target
+                    } else {
+                        self.vertices[index]
+                    }
+                });
+
+                let normal_before = (before[1] - before[0]).cross_product(&(before[2] - before[0]));
+                let normal_after = (after[1] - after[0]).cross_product(&(after[2] - after[0]));
+                let norms = normal_before.norm() * normal_after.norm();
+                if norms <= f32::EPSILON * f32::EPSILON {
+                    return false;
+                }
+
+                if normal_before.dot_product(&normal_after) / norms < MINIMUM_NORMAL_COSINE {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn collapse(&mut self, v0: usize, v1: usize, target: Vector3D) {
+        self.vertices[v0] = target;
+        self.moved[v0] = true;
+        if let Some(occlusion) = &mut self.occlusion {
+            occlusion[v0] = (occlusion[v0] + occlusion[v1]) / 2.0;
+        }
+        if let Some(curvature) = &mut self.curvature {
+            curvature[v0] = Curvature {
+                mean: (curvature[v0].mean + curvature[v1].mean) / 2.0,
+                gaussian: (curvature[v0].gaussian + curvature[v1].gaussian) / 2.0,
+            };
+        }
+        let quadric = self.quadrics[v1];
+        self.quadrics[v0].add(&quadric);
+
+        let moved_triangles = std::mem::take(&mut self.vertex_triangles[v1]);
+        for triangle_index in moved_triangles {
+            let triangle = &mut self.triangles[triangle_index];
+            if triangle.contains(&v0) {
+                self.removed_triangles[triangle_index] = true;
+                self.live_triangles -= 1;
+                continue;
+            }
+
+            triangle.iter_mut().for_each(|vertex| {
+                if *vertex == v1 {
+                    *vertex = v0;
+                }
+            });
+            self.vertex_triangles[v0].push(triangle_index);
+        }
+
+        let removed_triangles = &self.removed_triangles;
+        for vertex in self.neighbours(v0).into_iter().chain([v0]) {
+            self.vertex_triangles[vertex].retain(|triangle| !removed_triangles[*triangle]);
+        }
+
+        self.versions[v0] += 1;
+        self.versions[v1] += 1;
+
+        for neighbour in self.neighbours(v0) {
+            self.push_collapse(v0, neighbour);
+        }
+    }
+
+    /// Keeps the groups of `mesh`, the one the decimator was built from, and the
+    /// colors and texture coordinates of the triangles no collapse moved.
+    fn into_mesh(self, mesh: &IndexedMesh3D) -> IndexedMesh3D {
+        let mut remap = vec![usize::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::with_capacity(self.live_triangles);
+
+        for (index, triangle) in self.triangles.iter().enumerate() {
+            if self.removed_triangles[index] {
+                continue;
+            }
+
+            let vertices_indices = triangle.map(|vertex| {
+                if remap[vertex] == usize::MAX {
+                    remap[vertex] = vertices.len();
+                    vertices.push(self.vertices[vertex]);
+                }
+                remap[vertex]
+            });
+
+            let normal = vertices_indices
+                .map(|vertex| vertices[vertex])
+                .calculate_normal();
+
+            let original = &mesh.triangles[index];
+            triangles.push(IndexedTriangle3D {
+                normal,
+                vertices_indices,
+                uvs: original
+                    .uvs
+                    .filter(|_| !triangle.iter().any(|vertex| self.moved[*vertex])),
+                occlusion: self
+                    .occlusion
+                    .as_ref()
+                    .map(|occlusion| triangle.map(|vertex| occlusion[vertex])),
+                curvature: self
+                    .curvature
+                    .as_ref()
+                    .map(|curvature| triangle.map(|vertex| curvature[vertex])),
+                ..*original
+            });
+        }
+
+        IndexedMesh3D {
+            vertices,
+            triangles,
+            groups: mesh.groups.clone(),
+        }
+    }
+}
+
+// Value baked at each vertex of `mesh` from the corners of its triangles, if
+// every triangle has them. The baking gives the corners of a vertex the same value.
+fn get_vertex_values<T: Copy + Default>(
+    mesh: &IndexedMesh3D,
+    corners: impl Fn(&IndexedTriangle3D) -> Option<[T; 3]>,
+) -> Option<Vec<T>> {
+    let mut values = vec![T::default(); mesh.vertices.len()];
+    for triangle in mesh.triangles.iter() {
+        let corners = corners(triangle)?;
+        for (vertex, value) in triangle.vertices_indices.iter().zip(corners) {
+            values[*vertex] = value;
+        }
+    }
+    Some(values)
+}
+
+fn edge_key(v0: usize, v1: usize) -> (usize, usize) {
+    if v0 < v1 {
+        (v0, v1)
+    } else {
+        (v1, v0)
+    }
+}
+
+impl IndexedMesh3D {
+    /// Simplifies the mesh with Garland-Heckbert quadric edge collapses.
+    ///
+    /// The mesh is expected to share vertices between adjacent triangles, as read
+    /// from STL and OBJ files; unconnected triangles cannot be collapsed.
+    pub fn decimate(&self, target: DecimationTarget) -> IndexedMesh3D {
+        let mut decimator = Decimator::new(self);
+        decimator.run(target);
+        decimator.into_mesh(self)
+    }
+}
@@ -0,0 +1,38 @@
+use super::{Color, Texture};
+use std::sync::Arc;
+
+/// Surface properties read from an MTL file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    pub name: String,
+    /// `Ka`
+    pub ambient: Color,
+    /// `Kd`, the color used when rendering.
+    pub diffuse: Color,
+    /// `Ks`
+    pub specular: Color,
+    /// `Ns`, the specular exponent.
+    pub shininess: f32,
+    /// `d`, 1 for fully opaque.
+    pub opacity: f32,
+    /// `map_Kd`, the image file as written in the MTL file.
+    pub diffuse_map: Option<String>,
+    /// The `diffuse_map` image, which replaces the diffuse color where loaded.
+    pub texture: Option<Arc<Texture>>,
+}
+
+impl Material {
+    /// A material with the defaults of the MTL specification.
+    pub fn new(name: String) -> Material {
+        Material {
+            name,
+            ambient: Color::from_unit(0.2, 0.2, 0.2),
+            diffuse: Color::from_unit(0.8, 0.8, 0.8),
+            specular: Color::from_unit(1.0, 1.0, 1.0),
+            shininess: 0.0,
+            opacity: 1.0,
+            diffuse_map: None,
+            texture: None,
+        }
+    }
+}
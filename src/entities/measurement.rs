@@ -0,0 +1,102 @@
+use super::{CalculateMaximumRadius, IndexedMesh3D, Vector3D};
+use std::collections::HashMap;
+
+// Cosine of the largest angle between the normals of neighbouring triangles of
+// the same face, about one degree.
+const FACE_COSINE: f32 = 0.9998;
+
+// Distance under which vertices are merged to find the neighbours of triangles,
+// as a share of the size of the mesh.
+const WELD_TOLERANCE: f32 = 1e-5;
+
+pub fn distance(a: &Vector3D, b: &Vector3D) -> f32 {
+    (*b - *a).norm()
+}
+
+/// Angle at `vertex` between the segments to `a` and to `b`, in degrees.
+pub fn angle(a: &Vector3D, vertex: &Vector3D, b: &Vector3D) -> Option<f32> {
+    let (to_a, to_b) = (*a - *vertex, *b - *vertex);
+    let norms = to_a.norm() * to_b.norm();
+    if norms <= f32::EPSILON {
+        return None;
+    }
+
+    let cosine = (to_a.dot_product(&to_b) / norms).clamp(-1.0, 1.0);
+    Some(cosine.acos().to_degrees())
+}
+
+/// Radius of the circle through the three points, None when they are aligned.
+pub fn circle_radius(a: &Vector3D, b: &Vector3D, c: &Vector3D) -> Option<f32> {
+    let cross = (*b - *a).cross_product(&(*c - *a)).norm();
+    if cross <= f32::EPSILON {
+        return None;
+    }
+
+    Some(distance(a, b) * distance(b, c) * distance(c, a) / (2.0 * cross))
+}
+
+impl IndexedMesh3D {
+    /// Triangles of the flat face `triangle` belongs to: the ones reached from it
+    /// across shared edges without bending.
+    pub fn get_face(&self, triangle: usize) -> Vec<usize> {
+        let size = self.vertices.calculate_maximum_radius();
+        let welded = self.weld(WELD_TOLERANCE * size);
+
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (index, welded_triangle) in welded.triangles.iter().enumerate() {
+            let [a, b, c] = welded_triangle.vertices_indices;
+            for (start, end) in [(a, b), (b, c), (c, a)] {
+                edges
+                    .entry((start.min(end), start.max(end)))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        // From the vertices, as the normals stored in files are not always right.
+        let normal = |index: usize| {
+            let [a, b, c] = welded.triangles[index]
+                .vertices_indices
+                .map(|vertex| welded.vertices[vertex]);
+            let normal = (b - a).cross_product(&(c - a));
+            let norm = normal.norm().max(f32::EPSILON);
+            Vector3D::new(normal[0] / norm, normal[1] / norm, normal[2] / norm)
+        };
+        let face_normal = normal(triangle);
+
+        let mut face = vec![triangle];
+        let mut in_face = vec![false; welded.triangles.len()];
+        in_face[triangle] = true;
+        let mut next = 0;
+        while next < face.len() {
+            let [a, b, c] = welded.triangles[face[next]].vertices_indices;
+            next += 1;
+
+            for (start, end) in [(a, b), (b, c), (c, a)] {
+                for neighbour in edges[&(start.min(end), start.max(end))].iter() {
+                    if !in_face[*neighbour]
+                        && normal(*neighbour).dot_product(&face_normal) >= FACE_COSINE
+                    {
+                        in_face[*neighbour] = true;
+                        face.push(*neighbour);
+                    }
+                }
+            }
+        }
+
+        face
+    }
+
+    /// Sum of the areas of `triangles`.
+    pub fn get_area(&self, triangles: &[usize]) -> f32 {
+        triangles
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = self.triangles[*triangle]
+                    .vertices_indices
+                    .map(|index| self.vertices[index]);
+                (b - a).cross_product(&(c - a)).norm() / 2.0
+            })
+            .sum()
+    }
+}
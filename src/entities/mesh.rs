@@ -1,10 +1,19 @@
-use super::{CalculateCenter, IndexedTriangle3D, Triangle3D, Vector3D};
-use rayon::prelude::*;
+use super::{CalculateCenter, IndexedTriangle3D, Material, Triangle3D, Vector3D};
+use crate::parallel::*;
+
+/// A named part of a mesh, such as an OBJ object or group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshGroup {
+    pub name: String,
+    pub material: Option<Material>,
+}
 
 #[derive(Debug, Clone)]
 pub struct IndexedMesh3D {
     pub vertices: Vec<Vector3D>,
     pub triangles: Vec<IndexedTriangle3D>,
+    /// Referenced by the `group` of the triangles.
+    pub groups: Vec<MeshGroup>,
 }
 
 pub struct IndexedMesh3DIterator<'a> {
@@ -14,7 +23,15 @@ pub struct IndexedMesh3DIterator<'a> {
 }
 
 impl IndexedMesh3D {
-    pub fn iter(&self) -> IndexedMesh3DIterator {
+    pub fn new(vertices: Vec<Vector3D>, triangles: Vec<IndexedTriangle3D>) -> IndexedMesh3D {
+        IndexedMesh3D {
+            vertices,
+            triangles,
+            groups: Vec::new(),
+        }
+    }
+
+    pub fn iter(&self) -> IndexedMesh3DIterator<'_> {
         IndexedMesh3DIterator {
             vertices: &self.vertices,
             triangles: &self.triangles,
@@ -22,12 +39,63 @@ impl IndexedMesh3D {
         }
     }
 
-    pub fn move_to_center(&mut self) {
+    /// Moves the centroid of the vertices to the origin and returns where it was.
+    pub fn move_to_center(&mut self) -> Vector3D {
         let center = self.vertices.calculate_center();
 
         self.vertices
             .par_iter_mut()
             .for_each(|vertex| vertex.move_against_vector(center));
+
+        center
+    }
+
+    /// Appends the vertices, triangles and groups of `other`.
+    pub fn extend(&mut self, other: &IndexedMesh3D) {
+        let offset = self.vertices.len();
+        let group_offset = self.groups.len();
+        self.vertices.extend(other.vertices.iter());
+        self.groups.extend(other.groups.iter().cloned());
+        self.triangles
+            .extend(other.triangles.iter().map(|triangle| IndexedTriangle3D {
+                vertices_indices: triangle.vertices_indices.map(|index| index + offset),
+                group: triangle.group.map(|group| group + group_offset),
+                ..*triangle
+            }));
+    }
+
+    /// Material of `group`, if it has one.
+    pub fn get_material(&self, group: Option<usize>) -> Option<&Material> {
+        group
+            .and_then(|group| self.groups.get(group))
+            .and_then(|group| group.material.as_ref())
+    }
+
+    pub fn translate(&mut self, vector: Vector3D) {
+        self.vertices.par_iter_mut().for_each(|vertex| {
+            vertex[0] += vector[0];
+            vertex[1] += vector[1];
+            vertex[2] += vector[2];
+        });
+    }
+
+    /// Uniform scaling. Face normals keep their direction for positive factors.
+    pub fn scale(&mut self, factor: f32) {
+        self.vertices.par_iter_mut().for_each(|vertex| {
+            vertex[0] *= factor;
+            vertex[1] *= factor;
+            vertex[2] *= factor;
+        });
+    }
+
+    pub fn rotate(&mut self, angles: (&f32, &f32, &f32)) {
+        self.vertices
+            .par_iter_mut()
+            .for_each(|vertex| vertex.rotate(angles));
+
+        self.triangles.par_iter_mut().for_each(|triangle| {
+            triangle.rotate(angles);
+        });
     }
 }
 
@@ -1,15 +1,37 @@
+pub mod ambient_occlusion;
+pub mod bvh;
+pub mod color;
+pub mod curvature;
+pub mod decimation;
+pub mod material;
+pub mod measurement;
 pub mod mesh;
 pub mod object;
+pub mod repair;
 pub mod scene;
+pub mod section;
+pub mod shadow_map;
+pub mod slicer;
+pub mod texture;
 pub mod triangle_2d;
 pub mod triangle_3d;
 pub mod vector_2d;
 pub mod vector_3d;
 pub mod visible_mesh;
 
+pub use bvh::*;
+pub use color::*;
+pub use curvature::*;
+pub use decimation::*;
+pub use material::*;
+pub use measurement::*;
 pub use mesh::*;
 pub use object::*;
 pub use scene::*;
+pub use section::*;
+pub use shadow_map::*;
+pub use slicer::*;
+pub use texture::*;
 pub use triangle_2d::*;
 pub use triangle_3d::*;
 pub use vector_2d::*;
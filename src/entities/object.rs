@@ -1,21 +1,108 @@
-use super::CalculateMaximumRadius;
-use super::IndexedMesh3D;
-use rayon::prelude::*;
+use super::{Bvh, CalculateMaximumRadius, Color, Vector3D};
+use super::{DecimationTarget, IndexedMesh3D};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Which version of the mesh to render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Detail {
+    #[default]
+    Full,
+    /// The decimated mesh, if one was built, for fast interactive frames.
+    Reduced,
+}
+
+/// Placement of an object, applied around its own center.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform {
+    pub translation: Vector3D,
+    /// Rotation around the X, Y and Z axes, in radians.
+    pub rotation: (f32, f32, f32),
+    pub scale: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform {
+            translation: Vector3D::new(0.0, 0.0, 0.0),
+            rotation: (0.0, 0.0, 0.0),
+            scale: 1.0,
+        }
+    }
+}
+
+impl FromStr for Transform {
+    type Err = String;
+
+    /// Parses `tx,ty,tz`, optionally followed by `,rx,ry,rz` in degrees and
+    /// `,scale`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Error: Invalid Transform {text}");
+        let values = text
+            .split(',')
+            .map(|value| value.trim().parse::<f32>().map_err(|_| error()))
+            .collect::<Result<Vec<f32>, String>>()?;
+
+        let (translation, rotation, scale) = match values[..] {
+            [tx, ty, tz] => ([tx, ty, tz], [0.0; 3], 1.0),
+            [tx, ty, tz, rx, ry, rz] => ([tx, ty, tz], [rx, ry, rz], 1.0),
+            [tx, ty, tz, rx, ry, rz, scale] => ([tx, ty, tz], [rx, ry, rz], scale),
+            _ => return Err(error()),
+        };
+        if scale <= 0.0 {
+            return Err(error());
+        }
+        let [rx, ry, rz] = rotation.map(f32::to_radians);
+        Ok(Transform {
+            translation: Vector3D::new(translation[0], translation[1], translation[2]),
+            rotation: (rx, ry, rz),
+            scale,
+        })
+    }
+}
 
 #[derive(Debug)]
 pub struct Object {
     mesh: IndexedMesh3D,
+    reduced_mesh: Option<IndexedMesh3D>,
+    /// Hierarchy over the triangles of the full mesh, for ray and point queries.
+    bvh: Option<Bvh>,
     maximum_radius: f32,
+    center: Vector3D,
+    original_center: Vector3D,
+    /// Axes of the file as seen by the observer.
+    axes: [Vector3D; 3],
+    /// Size as seen over size in the file.
+    scale: f32,
+    name: String,
+    color: Option<Color>,
+    visible: bool,
+    hidden_groups: HashSet<usize>,
 }
 
 impl Object {
     pub fn new(mut mesh: IndexedMesh3D) -> Object {
-        mesh.move_to_center();
+        let original_center = mesh.move_to_center();
         let maximum_radius = mesh.vertices.calculate_maximum_radius();
 
         Object {
             mesh,
+            reduced_mesh: None,
+            bvh: None,
             maximum_radius,
+            center: Vector3D::new(0.0, 0.0, 0.0),
+            original_center,
+            axes: [
+                Vector3D::new(1.0, 0.0, 0.0),
+                Vector3D::new(0.0, 1.0, 0.0),
+                Vector3D::new(0.0, 0.0, 1.0),
+            ],
+            scale: 1.0,
+            name: String::new(),
+            color: None,
+            visible: true,
+            hidden_groups: HashSet::new(),
         }
     }
 
@@ -23,18 +110,173 @@ impl Object {
         &self.mesh
     }
 
+    pub fn get_mesh_with_detail(&self, detail: Detail) -> &IndexedMesh3D {
+        match (detail, &self.reduced_mesh) {
+            (Detail::Reduced, Some(reduced_mesh)) => reduced_mesh,
+            _ => &self.mesh,
+        }
+    }
+
+    /// The mesh for `detail` without the triangles of the hidden groups.
+    pub fn get_shown_mesh(&self, detail: Detail) -> Cow<'_, IndexedMesh3D> {
+        let mesh = self.get_mesh_with_detail(detail);
+        if self.hidden_groups.is_empty() {
+            return Cow::Borrowed(mesh);
+        }
+
+        let triangles = mesh
+            .triangles
+            .iter()
+            .filter(|triangle| match triangle.group {
+                Some(group) => self.is_group_visible(group),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        Cow::Owned(IndexedMesh3D {
+            vertices: mesh.vertices.clone(),
+            triangles,
+            groups: mesh.groups.clone(),
+        })
+    }
+
+    /// Radius of the bounding sphere around `get_center`.
     pub fn get_maximum_radius(&self) -> &f32 {
         &self.maximum_radius
     }
 
+    pub fn get_center(&self) -> &Vector3D {
+        &self.center
+    }
+
+    /// Center of the mesh in the coordinates of the file it was read from.
+    pub fn get_original_center(&self) -> &Vector3D {
+        &self.original_center
+    }
+
+    /// Axes of the file the object was read from, as seen by the observer.
+    pub fn get_axes(&self) -> [Vector3D; 3] {
+        self.axes
+    }
+
+    /// Size as seen over size in the file the object was read from.
+    pub fn get_scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn get_color(&self) -> Option<Color> {
+        self.color
+    }
+
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.color = color;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Whether the triangles of the mesh group number `group` are drawn.
+    pub fn is_group_visible(&self, group: usize) -> bool {
+        !self.hidden_groups.contains(&group)
+    }
+
+    pub fn set_group_visible(&mut self, group: usize, visible: bool) {
+        if visible {
+            self.hidden_groups.remove(&group);
+        } else {
+            self.hidden_groups.insert(group);
+        }
+    }
+
+    /// Builds the reduced mesh when the full one has more than `maximum_triangles`.
+    pub fn build_reduced_mesh(&mut self, maximum_triangles: usize) {
+        self.reduced_mesh = if self.mesh.triangles.len() > maximum_triangles {
+            Some(
+                self.mesh
+                    .decimate(DecimationTarget::Triangles(maximum_triangles)),
+            )
+        } else {
+            None
+        };
+    }
+
+    /// Builds the hierarchy over the triangles of the full mesh. It follows the
+    /// object when it moves afterwards.
+    pub fn build_bvh(&mut self) {
+        self.bvh = Some(Bvh::new(&self.mesh));
+    }
+
+    pub fn get_bvh(&self) -> Option<&Bvh> {
+        self.bvh.as_ref()
+    }
+
+    /// Rotates the object around the origin of the scene.
     pub fn rotate_mesh(&mut self, angles: (&f32, &f32, &f32)) {
-        self.mesh
-            .vertices
-            .par_iter_mut()
-            .for_each(|vertex| vertex.rotate(angles));
-
-        self.mesh.triangles.par_iter_mut().for_each(|triangle| {
-            triangle.rotate(angles);
-        });
+        self.mesh.rotate(angles);
+
+        if let Some(reduced_mesh) = self.reduced_mesh.as_mut() {
+            reduced_mesh.rotate(angles);
+        }
+
+        self.center.rotate(angles);
+        self.axes.iter_mut().for_each(|axis| axis.rotate(angles));
+        self.refit_bvh();
+    }
+
+    pub fn translate(&mut self, vector: Vector3D) {
+        self.mesh.translate(vector);
+
+        if let Some(reduced_mesh) = self.reduced_mesh.as_mut() {
+            reduced_mesh.translate(vector);
+        }
+
+        self.center = Vector3D::new(
+            self.center[0] + vector[0],
+            self.center[1] + vector[1],
+            self.center[2] + vector[2],
+        );
+        self.refit_bvh();
+    }
+
+    fn refit_bvh(&mut self) {
+        if let Some(bvh) = self.bvh.as_mut() {
+            bvh.refit(&self.mesh);
+        }
+    }
+
+    /// Scales and rotates the object around its center, then moves it.
+    pub fn apply_transform(&mut self, transform: &Transform) {
+        let center = self.center;
+        self.translate(Vector3D::new(-center[0], -center[1], -center[2]));
+
+        self.mesh.scale(transform.scale);
+        if let Some(reduced_mesh) = self.reduced_mesh.as_mut() {
+            reduced_mesh.scale(transform.scale);
+        }
+        self.maximum_radius *= transform.scale;
+        self.scale *= transform.scale;
+
+        let (x, y, z) = transform.rotation;
+        self.rotate_mesh((&x, &y, &z));
+
+        self.translate(Vector3D::new(
+            center[0] + transform.translation[0],
+            center[1] + transform.translation[1],
+            center[2] + transform.translation[2],
+        ));
     }
 }
@@ -0,0 +1,98 @@
+use super::{CalculateNormal, IndexedMesh3D, IndexedTriangle3D, Vector3D};
+use std::collections::{HashMap, HashSet};
+
+impl IndexedMesh3D {
+    /// Merges vertices closer than `tolerance` so adjacent triangles share indices.
+    pub fn weld(&self, tolerance: f32) -> IndexedMesh3D {
+        let cell_size = tolerance.max(f32::EPSILON);
+        let mut cells: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        let mut vertices: Vec<Vector3D> = Vec::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+
+        for vertex in self.vertices.iter() {
+            let cell = [0, 1, 2].map(|axis| (vertex[axis] / cell_size).floor() as i64);
+
+            let existing = neighbour_cells(cell)
+                .filter_map(|neighbour| cells.get(&neighbour))
+                .flatten()
+                .find(|index| (vertices[**index] - *vertex).norm() <= tolerance)
+                .copied();
+
+            let index = existing.unwrap_or_else(|| {
+                cells.entry(cell).or_default().push(vertices.len());
+                vertices.push(*vertex);
+                vertices.len() - 1
+            });
+            remap.push(index);
+        }
+
+        let triangles = self
+            .triangles
+            .iter()
+            .map(|triangle| IndexedTriangle3D {
+                vertices_indices: triangle.vertices_indices.map(|index| remap[index]),
+                ..*triangle
+            })
+            .collect();
+
+        IndexedMesh3D {
+            vertices,
+            triangles,
+            groups: self.groups.clone(),
+        }
+    }
+
+    /// Drops degenerate and duplicated triangles and the vertices nobody uses,
+    /// and recomputes the face normals from the vertex positions.
+    pub fn repair(&self) -> IndexedMesh3D {
+        let mut seen = HashSet::new();
+        let mut remap = vec![usize::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+
+        for triangle in self.triangles.iter() {
+            let [a, b, c] = triangle.vertices_indices;
+            if a == b || b == c || c == a {
+                continue;
+            }
+
+            let positions = triangle.vertices_indices.map(|index| self.vertices[index]);
+            let cross = (positions[1] - positions[0]).cross_product(&(positions[2] - positions[0]));
+            if cross.norm() <= f32::EPSILON {
+                continue;
+            }
+
+            let mut key = triangle.vertices_indices;
+            key.sort_unstable();
+            if !seen.insert(key) {
+                continue;
+            }
+
+            let vertices_indices = triangle.vertices_indices.map(|index| {
+                if remap[index] == usize::MAX {
+                    remap[index] = vertices.len();
+                    vertices.push(self.vertices[index]);
+                }
+                remap[index]
+            });
+
+            triangles.push(IndexedTriangle3D {
+                normal: positions.calculate_normal(),
+                vertices_indices,
+                ..*triangle
+            });
+        }
+
+        IndexedMesh3D {
+            vertices,
+            triangles,
+            groups: self.groups.clone(),
+        }
+    }
+}
+
+fn neighbour_cells(cell: [i64; 3]) -> impl Iterator<Item = [i64; 3]> {
+    (-1..=1).flat_map(move |x| {
+        (-1..=1).flat_map(move |y| (-1..=1).map(move |z| [cell[0] + x, cell[1] + y, cell[2] + z]))
+    })
+}
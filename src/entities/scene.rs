@@ -1,36 +1,415 @@
+use super::CullMode;
+use super::Detail;
 use super::Object;
+use super::Transform;
 use super::Vector3D;
 use super::VisibleIndexedMesh3D;
+use super::{
+    Aabb, Cap, ClippingPlane, IndexedMesh3D, Layer, Plane, RayHit, ShadowMap, Shadows, Vector2D,
+};
+use std::borrow::Cow;
+
+/// How the objects of a scene are placed relative to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// Where the files put them, as parts of an assembly.
+    #[default]
+    Assembly,
+    /// In a row along the X axis.
+    SideBySide,
+    /// All centered at the origin.
+    Overlay,
+}
+
+/// Triangle seen at a point of the screen.
+#[derive(Debug, Clone, Copy)]
+pub struct Pick {
+    /// Index of the object in the scene.
+    pub object: usize,
+    /// Index into the triangles of the full mesh of the object.
+    pub triangle: usize,
+    /// Where the triangle is hit, as seen by the observer.
+    pub point: Vector3D,
+}
 
 pub struct Scene {
-    object: Object,
+    objects: Vec<Object>,
     light: Vector3D,
     observer: Vector3D,
+    cull_mode: CullMode,
+    detail: Detail,
+    axes: [Vector3D; 3],
+    clipping: Option<ClippingPlane>,
+    shadows: Shadows,
 }
 
 impl Scene {
     pub fn new(object: Object, light: Vector3D, observer: Vector3D) -> Scene {
         Scene {
-            object,
+            objects: vec![object],
             light,
             observer,
+            cull_mode: CullMode::default(),
+            detail: Detail::default(),
+            axes: [
+                Vector3D::new(1.0, 0.0, 0.0),
+                Vector3D::new(0.0, 1.0, 0.0),
+                Vector3D::new(0.0, 0.0, 1.0),
+            ],
+            clipping: None,
+            shadows: Shadows::default(),
+        }
+    }
+
+    pub fn add_object(&mut self, object: Object) {
+        self.objects.push(object);
+    }
+
+    pub fn get_objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    pub fn get_objects_mut(&mut self) -> &mut [Object] {
+        &mut self.objects
+    }
+
+    /// Places the objects and centers the whole group at the origin.
+    pub fn arrange(&mut self, layout: Layout) {
+        let gap = 0.1
+            * self
+                .objects
+                .iter()
+                .map(|object| *object.get_maximum_radius())
+                .fold(0.0, f32::max);
+        let mut cursor = 0.0;
+
+        for object in self.objects.iter_mut() {
+            let center = *object.get_center();
+            object.translate(Vector3D::new(-center[0], -center[1], -center[2]));
+
+            let radius = *object.get_maximum_radius();
+            let position = match layout {
+                Layout::Assembly => *object.get_original_center(),
+                Layout::SideBySide => Vector3D::new(cursor + radius, 0.0, 0.0),
+                Layout::Overlay => Vector3D::new(0.0, 0.0, 0.0),
+            };
+            cursor += 2.0 * radius + gap;
+
+            object.translate(position);
+        }
+
+        let count = self.objects.len().max(1) as f32;
+        let mut center = Vector3D::new(0.0, 0.0, 0.0);
+        for object in self.objects.iter() {
+            let object_center = object.get_center();
+            center = Vector3D::new(
+                center[0] + object_center[0] / count,
+                center[1] + object_center[1] / count,
+                center[2] + object_center[2] / count,
+            );
+        }
+
+        for object in self.objects.iter_mut() {
+            object.translate(Vector3D::new(-center[0], -center[1], -center[2]));
+        }
+    }
+
+    /// Applies each transform to the object in the same place, around where
+    /// `arrange` put it.
+    pub fn apply_transforms(&mut self, transforms: &[Transform]) {
+        for (object, transform) in self.objects.iter_mut().zip(transforms) {
+            object.apply_transform(transform);
+        }
+    }
+
+    pub fn get_cull_mode(&self) -> CullMode {
+        self.cull_mode
+    }
+
+    pub fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        self.cull_mode = cull_mode;
+    }
+
+    pub fn get_detail(&self) -> Detail {
+        self.detail
+    }
+
+    pub fn set_detail(&mut self, detail: Detail) {
+        self.detail = detail;
+    }
+
+    /// Builds the reduced meshes, sharing `maximum_triangles` between the objects
+    /// in proportion to their size.
+    pub fn build_level_of_detail(&mut self, maximum_triangles: usize) {
+        let total: usize = self
+            .objects
+            .iter()
+            .map(|object| object.get_mesh().triangles.len())
+            .sum();
+
+        for object in self.objects.iter_mut() {
+            let triangles = object.get_mesh().triangles.len();
+            let share = maximum_triangles as f64 * triangles as f64 / total.max(1) as f64;
+            object.build_reduced_mesh(share.ceil() as usize);
         }
     }
 
+    /// Radius of the sphere around the origin that bounds every object.
+    pub fn get_maximum_radius(&self) -> f32 {
+        self.objects
+            .iter()
+            .map(|object| object.get_center().norm() + object.get_maximum_radius())
+            .fold(0.0, f32::max)
+    }
+
+    /// Axes of the scene as seen by the observer.
+    pub fn get_axes(&self) -> [Vector3D; 3] {
+        self.axes
+    }
+
+    pub fn get_clipping(&self) -> Option<ClippingPlane> {
+        self.clipping
+    }
+
+    pub fn set_clipping(&mut self, clipping: Option<ClippingPlane>) {
+        self.clipping = clipping;
+    }
+
+    pub fn get_shadows(&self) -> Shadows {
+        self.shadows
+    }
+
+    pub fn set_shadows(&mut self, shadows: Shadows) {
+        self.shadows = shadows;
+    }
+
+    /// The clipping plane in the current orientation; geometry above it is discarded.
+    pub fn get_clipping_plane(&self) -> Option<Plane> {
+        self.clipping.map(|clipping| {
+            let sign = if clipping.flipped { -1.0 } else { 1.0 };
+            let axis = self.axes[clipping.axis.index()];
+            Plane::new(
+                Vector3D::new(sign * axis[0], sign * axis[1], sign * axis[2]),
+                sign * clipping.offset,
+            )
+        })
+    }
+
     pub fn rotate_delta_x(&mut self, delta_angle: &f32) {
-        self.object.rotate_mesh((delta_angle, &0.0, &0.0));
+        self.rotate((delta_angle, &0.0, &0.0));
     }
 
     pub fn rotate_delta_y(&mut self, delta_angle: &f32) {
-        self.object.rotate_mesh((&0.0, delta_angle, &0.0));
+        self.rotate((&0.0, delta_angle, &0.0));
     }
 
     pub fn rotate_delta_z(&mut self, delta_angle: &f32) {
-        self.object.rotate_mesh((&0.0, &0.0, delta_angle));
+        self.rotate((&0.0, &0.0, delta_angle));
+    }
+
+    fn rotate(&mut self, angles: (&f32, &f32, &f32)) {
+        self.objects
+            .iter_mut()
+            .for_each(|object| object.rotate_mesh(angles));
+        self.axes.iter_mut().for_each(|axis| axis.rotate(angles));
+    }
+
+    /// Builds the hierarchies used by `pick` for the objects that lack one.
+    pub fn build_bvhs(&mut self) {
+        for object in self.objects.iter_mut() {
+            if object.get_bvh().is_none() {
+                object.build_bvh();
+            }
+        }
+    }
+
+    /// Closest shown triangle under `point` of the screen, among the objects with
+    /// a hierarchy. Faces that are culled, hidden or clipped away are looked
+    /// through.
+    pub fn pick(&self, point: &Vector2D) -> Option<Pick> {
+        let radius = self.get_maximum_radius();
+        // The observer looks along -Z, so the ray starts in front of everything.
+        let origin = Vector3D::new(point[0], point[1], 2.0 * radius + 1.0);
+        self.cast(&origin, &self.observer, 4.0 * radius + 2.0)
+    }
+
+    /// Closest shown triangle hit by a ray within `max_distance` of `origin`,
+    /// among the objects with a hierarchy. Faces culled as seen along the ray,
+    /// hidden or clipped away are passed through.
+    pub fn cast(&self, origin: &Vector3D, direction: &Vector3D, max_distance: f32) -> Option<Pick> {
+        let plane = self.get_clipping_plane();
+
+        self.objects
+            .iter()
+            .enumerate()
+            .filter(|(_, object)| object.is_visible())
+            .filter_map(|(index, object)| {
+                let mesh = object.get_mesh();
+                let accept = |hit: &RayHit| {
+                    let triangle = &mesh.triangles[hit.triangle];
+                    let facing = triangle.normal.dot_product(direction);
+                    let shown = match self.cull_mode {
+                        CullMode::Back => facing < 0.0,
+                        CullMode::Front => facing > 0.0,
+                        CullMode::None => true,
+                    };
+                    shown
+                        && triangle
+                            .group
+                            .is_none_or(|group| object.is_group_visible(group))
+                        && plane.is_none_or(|plane| plane.distance(&hit.point) <= 0.0)
+                };
+                let hit = object.get_bvh()?.intersect_where(
+                    mesh,
+                    origin,
+                    direction,
+                    max_distance,
+                    accept,
+                )?;
+                Some((index, hit))
+            })
+            .min_by(|(_, a), (_, b)| a.distance.total_cmp(&b.distance))
+            .map(|(object, hit)| Pick {
+                object,
+                triangle: hit.triangle,
+                point: hit.point,
+            })
+    }
+
+    pub fn get_light(&self) -> Vector3D {
+        self.light
     }
 
-    pub fn get_visible_mesh(&self) -> VisibleIndexedMesh3D {
-        let mesh = self.object.get_mesh();
-        VisibleIndexedMesh3D::new(mesh, &self.light, &self.observer)
+    /// `point` of the object number `object`, as seen by the observer, in the
+    /// coordinates of the file the object was read from, undoing its transform.
+    pub fn to_file_coordinates(&self, object: usize, point: &Vector3D) -> Vector3D {
+        let object = &self.objects[object];
+        let center = object.get_center();
+        let original_center = object.get_original_center();
+        let offset = *point - *center;
+        let scale = object.get_scale();
+        let [x, y, z] = object
+            .get_axes()
+            .map(|axis| offset.dot_product(&axis) / scale);
+        Vector3D::new(
+            x + original_center[0],
+            y + original_center[1],
+            z + original_center[2],
+        )
+    }
+
+    /// `point` in the coordinates of the file of the object number `object`, as
+    /// seen by the observer. The inverse of `to_file_coordinates`.
+    pub fn from_file_coordinates(&self, object: usize, point: &Vector3D) -> Vector3D {
+        let object = &self.objects[object];
+        let offset = *point - *object.get_original_center();
+        let center = *object.get_center();
+        let axes = object.get_axes();
+        let scale = object.get_scale();
+        let [x, y, z] = [0, 1, 2].map(|coordinate| {
+            center[coordinate]
+                + scale
+                    * (0..3)
+                        .map(|axis| axes[axis][coordinate] * offset[axis])
+                        .sum::<f32>()
+        });
+        Vector3D::new(x, y, z)
+    }
+
+    /// Box around the full mesh of the object number `object`, aligned with the
+    /// axes of its file and in its coordinates.
+    pub fn get_file_bounds(&self, object: usize) -> Aabb {
+        let vertices: Vec<Vector3D> = self.objects[object]
+            .get_mesh()
+            .vertices
+            .iter()
+            .map(|vertex| self.to_file_coordinates(object, vertex))
+            .collect();
+        Aabb::from_points(&vertices)
+    }
+
+    /// Area of `triangles` of the full mesh of the object number `object`, in
+    /// the units of its file.
+    pub fn get_file_area(&self, object: usize, triangles: &[usize]) -> f32 {
+        let object = &self.objects[object];
+        object.get_mesh().get_area(triangles) / object.get_scale().powi(2)
+    }
+
+    /// `direction`, as seen by the observer, in the axes of the file of the
+    /// object number `object`.
+    pub fn to_file_direction(&self, object: usize, direction: &Vector3D) -> Vector3D {
+        let [x, y, z] = self.objects[object]
+            .get_axes()
+            .map(|axis| direction.dot_product(&axis));
+        Vector3D::new(x, y, z)
+    }
+
+    fn get_visible_objects(&self) -> impl Iterator<Item = &Object> {
+        self.objects.iter().filter(|object| object.is_visible())
+    }
+
+    /// The meshes of the visible objects at the current detail, clipped.
+    fn get_shown_meshes(&self) -> Vec<(&Object, Cow<'_, IndexedMesh3D>)> {
+        let plane = self.get_clipping_plane();
+
+        self.get_visible_objects()
+            .map(|object| {
+                let mesh = object.get_shown_mesh(self.detail);
+                let mesh = match plane {
+                    Some(plane) => Cow::Owned(mesh.clip(&plane)),
+                    None => mesh,
+                };
+                (object, mesh)
+            })
+            .collect()
+    }
+
+    /// One visible mesh per visible object.
+    pub fn get_visible_meshes(&self) -> Vec<VisibleIndexedMesh3D> {
+        self.get_shown_meshes()
+            .into_iter()
+            .map(|(object, mesh)| {
+                let mut visible_mesh =
+                    VisibleIndexedMesh3D::new(&mesh, &self.light, &self.observer, self.cull_mode);
+                visible_mesh.set_color(object.get_color());
+                visible_mesh
+            })
+            .collect()
+    }
+
+    /// Depth of the scene seen from the light, if shadows are enabled.
+    pub fn get_shadow_map(&self) -> Option<ShadowMap> {
+        if !self.shadows.enabled {
+            return None;
+        }
+
+        let shown_meshes = self.get_shown_meshes();
+        let meshes: Vec<&IndexedMesh3D> = shown_meshes.iter().map(|(_, mesh)| &**mesh).collect();
+        Some(ShadowMap::new(&meshes, &self.light, &self.shadows))
+    }
+
+    /// Slices the full detail meshes of the visible objects along the Z axis of the scene.
+    pub fn get_layers(&self, layer_height: f32) -> Vec<Layer> {
+        let mut mesh = IndexedMesh3D::new(Vec::new(), Vec::new());
+        self.get_visible_objects()
+            .for_each(|object| mesh.extend(&object.get_shown_mesh(Detail::Full)));
+
+        mesh.slice_along(&self.axes, layer_height)
+    }
+
+    /// Section to fill where the clipping plane cuts the objects, if capping is on.
+    pub fn get_cap(&self) -> Option<Cap> {
+        let plane = self.get_clipping_plane()?;
+        if !self.clipping?.cap {
+            return None;
+        }
+
+        Some(Cap {
+            plane,
+            contours: self
+                .get_visible_objects()
+                .flat_map(|object| object.get_shown_mesh(self.detail).section(&plane))
+                .collect(),
+        })
     }
 }
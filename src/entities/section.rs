@@ -0,0 +1,302 @@
+use super::{Curvature, IndexedMesh3D, IndexedTriangle3D, Vector2D, Vector3D};
+use std::collections::HashMap;
+
+/// Points `p` with `normal · p = offset`. `normal` is expected to be unitary.
+#[derive(Debug, Clone, Copy)]
+pub struct Plane {
+    pub normal: Vector3D,
+    pub offset: f32,
+}
+
+impl Plane {
+    pub fn new(normal: Vector3D, offset: f32) -> Plane {
+        Plane { normal, offset }
+    }
+
+    /// Signed distance, positive on the side the normal points to.
+    pub fn distance(&self, point: &Vector3D) -> f32 {
+        self.normal.dot_product(point) - self.offset
+    }
+
+    fn is_above(&self, point: &Vector3D) -> bool {
+        self.distance(point) > 0.0
+    }
+
+    /// Fraction of the way from `a` to `b` where the segment crosses the plane.
+    fn crossing(&self, a: &Vector3D, b: &Vector3D) -> f32 {
+        let da = self.distance(a);
+        let db = self.distance(b);
+        da / (da - db)
+    }
+
+    fn intersection(&self, a: &Vector3D, b: &Vector3D) -> Vector3D {
+        let t = self.crossing(a, b);
+
+        Vector3D::new(
+            a[0] + t * (b[0] - a[0]),
+            a[1] + t * (b[1] - a[1]),
+            a[2] + t * (b[2] - a[2]),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub fn index(&self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+}
+
+/// Axis aligned clipping plane of a scene, in the axes of the object.
+#[derive(Debug, Clone, Copy)]
+pub struct ClippingPlane {
+    pub axis: Axis,
+    /// Position along the axis, from the center of the object.
+    pub offset: f32,
+    /// Keep the positive side of the axis instead of the negative one.
+    pub flipped: bool,
+    /// Fill the section where the object was cut.
+    pub cap: bool,
+}
+
+impl ClippingPlane {
+    pub fn new(axis: Axis) -> ClippingPlane {
+        ClippingPlane {
+            axis,
+            offset: 0.0,
+            flipped: false,
+            cap: true,
+        }
+    }
+}
+
+/// Polyline where a mesh crosses a plane. Closed unless the mesh has holes.
+#[derive(Debug, Clone)]
+pub struct Contour {
+    pub points: Vec<Vector3D>,
+    pub closed: bool,
+}
+
+/// Section of a mesh to be filled where it was clipped.
+#[derive(Debug, Clone)]
+pub struct Cap {
+    pub plane: Plane,
+    pub contours: Vec<Contour>,
+}
+
+fn edge_key(v0: usize, v1: usize) -> (usize, usize) {
+    if v0 < v1 {
+        (v0, v1)
+    } else {
+        (v1, v0)
+    }
+}
+
+/// Values of the `corners` of a polygon, when all of them have one.
+fn get_corners<T: Copy>(values: &[Option<T>], corners: [usize; 3]) -> Option<[T; 3]> {
+    let [a, b, c] = corners.map(|corner| values[corner]);
+    Some([a?, b?, c?])
+}
+
+impl IndexedMesh3D {
+    /// Discards the geometry above `plane`. Triangles crossing it are cut, and the
+    /// new vertices are shared between the triangles at both sides of an edge.
+    pub fn clip(&self, plane: &Plane) -> IndexedMesh3D {
+        let mut vertices = self.vertices.clone();
+        let mut triangles = Vec::with_capacity(self.triangles.len());
+        let mut edge_vertices: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for triangle in self.triangles.iter() {
+            let indices = triangle.vertices_indices;
+            let above = indices.map(|index| plane.is_above(&self.vertices[index]));
+
+            if above.iter().all(|above| !above) {
+                triangles.push(*triangle);
+                continue;
+            }
+
+            if above.iter().all(|above| *above) {
+                continue;
+            }
+
+            // Sutherland-Hodgman over the three edges, keeping the winding. The
+            // texture coordinates, the occlusion and the curvature of the new corners
+            // are interpolated along the edges.
+            let mut polygon = Vec::with_capacity(4);
+            let mut polygon_uvs = Vec::with_capacity(4);
+            let mut polygon_occlusion = Vec::with_capacity(4);
+            let mut polygon_curvature = Vec::with_capacity(4);
+            for edge in 0..3 {
+                let current = indices[edge];
+                let next = indices[(edge + 1) % 3];
+
+                if !above[edge] {
+                    polygon.push(current);
+                    polygon_uvs.push(triangle.uvs.map(|uvs| uvs[edge]));
+                    polygon_occlusion.push(triangle.occlusion.map(|occlusion| occlusion[edge]));
+                    polygon_curvature.push(triangle.curvature.map(|curvature| curvature[edge]));
+                }
+
+                if above[edge] != above[(edge + 1) % 3] {
+                    let index =
+                        *edge_vertices
+                            .entry(edge_key(current, next))
+                            .or_insert_with(|| {
+                                vertices.push(
+                                    plane.intersection(
+                                        &self.vertices[current],
+                                        &self.vertices[next],
+                                    ),
+                                );
+                                vertices.len() - 1
+                            });
+                    polygon.push(index);
+
+                    let t = plane.crossing(&self.vertices[current], &self.vertices[next]);
+                    polygon_uvs.push(triangle.uvs.map(|uvs| {
+                        let (a, b) = (uvs[edge], uvs[(edge + 1) % 3]);
+                        Vector2D::new(a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1]))
+                    }));
+                    polygon_occlusion.push(triangle.occlusion.map(|occlusion| {
+                        let (a, b) = (occlusion[edge], occlusion[(edge + 1) % 3]);
+                        a + t * (b - a)
+                    }));
+                    polygon_curvature.push(triangle.curvature.map(|curvature| {
+                        let (a, b) = (curvature[edge], curvature[(edge + 1) % 3]);
+                        Curvature {
+                            mean: a.mean + t * (b.mean - a.mean),
+                            gaussian: a.gaussian + t * (b.gaussian - a.gaussian),
+                        }
+                    }));
+                }
+            }
+
+            for index in 1..polygon.len() - 1 {
+                let corners = [0, index, index + 1];
+                triangles.push(IndexedTriangle3D {
+                    vertices_indices: corners.map(|corner| polygon[corner]),
+                    uvs: get_corners(&polygon_uvs, corners),
+                    occlusion: get_corners(&polygon_occlusion, corners),
+                    curvature: get_corners(&polygon_curvature, corners),
+                    ..*triangle
+                });
+            }
+        }
+
+        IndexedMesh3D {
+            vertices,
+            triangles,
+            groups: self.groups.clone(),
+        }
+    }
+
+    /// Intersects the mesh with `plane` and chains the segments into contours.
+    pub fn section(&self, plane: &Plane) -> Vec<Contour> {
+        let mut section = Section::default();
+
+        for triangle in self.triangles.iter() {
+            section.add_triangle(&self.vertices, triangle.vertices_indices, plane);
+        }
+
+        section.into_contours()
+    }
+}
+
+/// Segments where triangles cross a plane, keyed by the mesh edges they cut so
+/// neighbouring segments can be chained without comparing floats.
+#[derive(Default)]
+pub(super) struct Section {
+    points: HashMap<(usize, usize), Vector3D>,
+    links: HashMap<(usize, usize), Vec<(usize, usize)>>,
+}
+
+impl Section {
+    pub(super) fn add_triangle(
+        &mut self,
+        vertices: &[Vector3D],
+        indices: [usize; 3],
+        plane: &Plane,
+    ) {
+        let above = indices.map(|index| plane.is_above(&vertices[index]));
+
+        let crossings: Vec<(usize, usize)> = (0..3)
+            .filter(|edge| above[*edge] != above[(edge + 1) % 3])
+            .map(|edge| edge_key(indices[edge], indices[(edge + 1) % 3]))
+            .collect();
+
+        if crossings.len() != 2 {
+            return;
+        }
+
+        for key in crossings.iter() {
+            self.points
+                .entry(*key)
+                .or_insert_with(|| plane.intersection(&vertices[key.0], &vertices[key.1]));
+        }
+
+        self.links
+            .entry(crossings[0])
+            .or_default()
+            .push(crossings[1]);
+        self.links
+            .entry(crossings[1])
+            .or_default()
+            .push(crossings[0]);
+    }
+
+    pub(super) fn into_contours(mut self) -> Vec<Contour> {
+        let mut contours = Vec::new();
+
+        // Open contours first, starting from their loose ends.
+        let mut starts: Vec<(usize, usize)> = self
+            .links
+            .iter()
+            .filter(|(_, next)| next.len() == 1)
+            .map(|(key, _)| *key)
+            .collect();
+        starts.sort_unstable();
+        let mut remaining: Vec<(usize, usize)> = self.links.keys().copied().collect();
+        remaining.sort_unstable();
+        starts.extend(remaining);
+
+        for start in starts {
+            if self.links.get(&start).is_none_or(|next| next.is_empty()) {
+                continue;
+            }
+
+            let mut chain = vec![start];
+            let mut current = start;
+            while let Some(next) = self.links.get_mut(&current).and_then(|next| next.pop()) {
+                if let Some(back) = self.links.get_mut(&next) {
+                    if let Some(position) = back.iter().position(|key| *key == current) {
+                        back.swap_remove(position);
+                    }
+                }
+                chain.push(next);
+                current = next;
+            }
+
+            let closed = chain.len() > 2 && chain.first() == chain.last();
+            if closed {
+                chain.pop();
+            }
+
+            contours.push(Contour {
+                points: chain.iter().map(|key| self.points[key]).collect(),
+                closed,
+            });
+        }
+
+        contours
+    }
+}
@@ -0,0 +1,200 @@
+use super::{IndexedMesh3D, Vector3D};
+
+// Steepest slope of a surface, seen from the light, trusted when its depth is
+// followed to the neighbouring texels.
+const MAXIMUM_SLOPE: f32 = 4.0;
+
+/// Settings of the shadows cast by the light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadows {
+    pub enabled: bool,
+    /// Depth, in texels of the map, that a point may lie behind the closest
+    /// surface and still be lit. Keeps faces from shadowing themselves.
+    pub bias: f32,
+    /// Texels around a point that are tested too, so shadow borders are soft.
+    /// Zero tests the point alone.
+    pub softness: usize,
+    /// Texels along each side of the map.
+    pub resolution: usize,
+}
+
+impl Default for Shadows {
+    fn default() -> Self {
+        Shadows {
+            enabled: false,
+            bias: 1.5,
+            softness: 1,
+            resolution: 256,
+        }
+    }
+}
+
+/// Depth of the surfaces closest to a directional light, seen along it.
+#[derive(Debug, Clone)]
+pub struct ShadowMap {
+    /// Two axes across the light and the direction to the light.
+    axes: [Vector3D; 3],
+    origin: [f32; 2],
+    texel_size: f32,
+    resolution: usize,
+    /// Larger is closer to the light, and -infinity where nothing is seen.
+    depths: Vec<f32>,
+    bias: f32,
+    softness: usize,
+}
+
+impl ShadowMap {
+    /// Renders `meshes` as seen from `light`, a direction that goes from the light
+    /// to the scene.
+    pub fn new(meshes: &[&IndexedMesh3D], light: &Vector3D, shadows: &Shadows) -> ShadowMap {
+        let axes = light_axes(light);
+        let resolution = shadows.resolution.max(1);
+
+        let project = |vertex: &Vector3D| axes.map(|axis| vertex.dot_product(&axis));
+        let (mut min, mut max) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+        for vertex in meshes.iter().flat_map(|mesh| mesh.vertices.iter()) {
+            let [u, v, _] = project(vertex);
+            min = [min[0].min(u), min[1].min(v)];
+            max = [max[0].max(u), max[1].max(v)];
+        }
+        let extent = (max[0] - min[0]).max(max[1] - min[1]);
+        // The far side of the scene falls inside the last texel, not past it.
+        let texel_size = if extent > 0.0 {
+            extent / (resolution as f32 - 1.0).max(1.0)
+        } else {
+            1.0
+        };
+
+        let mut shadow_map = ShadowMap {
+            axes,
+            origin: min,
+            texel_size,
+            resolution,
+            depths: vec![f32::NEG_INFINITY; resolution * resolution],
+            bias: shadows.bias,
+            softness: shadows.softness,
+        };
+
+        for mesh in meshes.iter() {
+            for triangle in mesh.iter() {
+                let points = triangle.vertices.map(|vertex| {
+                    let [u, v, depth] = project(&vertex);
+                    let [x, y] = shadow_map.to_texel(u, v);
+                    [x, y, depth]
+                });
+                shadow_map.set_triangle(&points);
+            }
+        }
+
+        shadow_map
+    }
+
+    /// Share of the light that reaches `point`, on a surface with `normal`, in [0, 1].
+    ///
+    /// The neighbouring texels are compared with the depth the surface has under
+    /// them, so sloped surfaces do not shadow themselves.
+    pub fn light_at(&self, point: &Vector3D, normal: &Vector3D) -> f32 {
+        let [u, v, depth] = self.axes.map(|axis| point.dot_product(&axis));
+        let [x, y] = self.to_texel(u, v);
+        let (col, row) = (x.floor() as isize, y.floor() as isize);
+        let softness = self.softness as isize;
+
+        // Change of the depth of the surface per texel along each axis.
+        let [normal_u, normal_v, normal_w] = self.axes.map(|axis| normal.dot_product(&axis));
+        let slope = |component: f32| {
+            if normal_w.abs() > f32::EPSILON {
+                (-component / normal_w).clamp(-MAXIMUM_SLOPE, MAXIMUM_SLOPE) * self.texel_size
+            } else {
+                0.0
+            }
+        };
+        let (slope_u, slope_v) = (slope(normal_u), slope(normal_v));
+        let depth = depth + self.bias * self.texel_size;
+
+        let mut lit = 0;
+        let mut samples = 0;
+        for sample_row in row - softness..=row + softness {
+            for sample_col in col - softness..=col + softness {
+                samples += 1;
+                let sample_depth = depth
+                    + slope_u * (sample_col as f32 + 0.5 - x)
+                    + slope_v * (sample_row as f32 + 0.5 - y);
+                match self.get_depth(sample_col, sample_row) {
+                    Some(closest) if sample_depth < closest => {}
+                    _ => lit += 1,
+                }
+            }
+        }
+
+        lit as f32 / samples as f32
+    }
+
+    fn to_texel(&self, u: f32, v: f32) -> [f32; 2] {
+        [
+            (u - self.origin[0]) / self.texel_size,
+            (v - self.origin[1]) / self.texel_size,
+        ]
+    }
+
+    fn get_depth(&self, col: isize, row: isize) -> Option<f32> {
+        if col < 0 || row < 0 || col >= self.resolution as isize || row >= self.resolution as isize
+        {
+            return None;
+        }
+        Some(self.depths[row as usize * self.resolution + col as usize])
+    }
+
+    /// Keeps the closest depth of the triangle on the texels whose centers it covers.
+    fn set_triangle(&mut self, points: &[[f32; 3]; 3]) {
+        let [p1, p2, p3] = points;
+        let area = (p2[0] - p1[0]) * (p3[1] - p1[1]) - (p3[0] - p1[0]) * (p2[1] - p1[1]);
+        if area.abs() <= f32::EPSILON {
+            return;
+        }
+
+        let last = self.resolution as f32 - 1.0;
+        let min = |axis: usize| {
+            p1[axis]
+                .min(p2[axis])
+                .min(p3[axis])
+                .floor()
+                .clamp(0.0, last)
+        };
+        let max = |axis: usize| p1[axis].max(p2[axis]).max(p3[axis]).ceil().clamp(0.0, last);
+
+        for row in min(1) as usize..=max(1) as usize {
+            for col in min(0) as usize..=max(0) as usize {
+                let (x, y) = (col as f32 + 0.5, row as f32 + 0.5);
+                let w1 = ((p3[0] - p2[0]) * (y - p2[1]) - (p3[1] - p2[1]) * (x - p2[0])) / area;
+                let w2 = ((p1[0] - p3[0]) * (y - p3[1]) - (p1[1] - p3[1]) * (x - p3[0])) / area;
+                let w3 = 1.0 - w1 - w2;
+                if w1 < 0.0 || w2 < 0.0 || w3 < 0.0 {
+                    continue;
+                }
+
+                let depth = w1 * p1[2] + w2 * p2[2] + w3 * p3[2];
+                let texel = &mut self.depths[row * self.resolution + col];
+                *texel = texel.max(depth);
+            }
+        }
+    }
+}
+
+/// Orthonormal axes with the third one pointing to the light.
+fn light_axes(light: &Vector3D) -> [Vector3D; 3] {
+    let normalize = |vector: Vector3D| {
+        let norm = vector.norm();
+        Vector3D::new(vector[0] / norm, vector[1] / norm, vector[2] / norm)
+    };
+
+    let to_light = normalize(Vector3D::new(-light[0], -light[1], -light[2]));
+    let helper = if to_light[0].abs() < 0.9 {
+        Vector3D::new(1.0, 0.0, 0.0)
+    } else {
+        Vector3D::new(0.0, 1.0, 0.0)
+    };
+    let u = normalize(helper.cross_product(&to_light));
+    let v = to_light.cross_product(&u);
+
+    [u, v, to_light]
+}
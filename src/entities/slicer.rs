@@ -0,0 +1,97 @@
+use super::section::Section;
+use super::{IndexedMesh3D, Plane, Vector2D, Vector3D};
+
+/// Contour of a layer, in the coordinates of the slicing plane.
+#[derive(Debug, Clone)]
+pub struct LayerContour {
+    pub points: Vec<Vector2D>,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Layer {
+    /// Position of the slicing plane along the up axis.
+    pub height: f32,
+    pub contours: Vec<LayerContour>,
+}
+
+impl IndexedMesh3D {
+    /// Slices the mesh with planes perpendicular to Z, one every `layer_height`,
+    /// each at the middle of its layer as 3D printing slicers do.
+    pub fn slice(&self, layer_height: f32) -> Vec<Layer> {
+        self.slice_along(
+            &[
+                Vector3D::new(1.0, 0.0, 0.0),
+                Vector3D::new(0.0, 1.0, 0.0),
+                Vector3D::new(0.0, 0.0, 1.0),
+            ],
+            layer_height,
+        )
+    }
+
+    /// Like `slice`, with `axes[2]` as the up axis and the contours given in the
+    /// coordinates of `axes[0]` and `axes[1]`. The axes must be orthonormal.
+    pub fn slice_along(&self, axes: &[Vector3D; 3], layer_height: f32) -> Vec<Layer> {
+        if self.vertices.is_empty() || layer_height <= 0.0 {
+            return Vec::new();
+        }
+
+        let up = axes[2];
+        let heights: Vec<f32> = self
+            .vertices
+            .iter()
+            .map(|vertex| vertex.dot_product(&up))
+            .collect();
+        let bottom = heights.iter().cloned().fold(f32::INFINITY, f32::min);
+        let top = heights.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let count = (((top - bottom) / layer_height).ceil() as usize).max(1);
+
+        let planes: Vec<Plane> = (0..count)
+            .map(|layer| Plane::new(up, bottom + (layer as f32 + 0.5) * layer_height))
+            .collect();
+        let mut sections: Vec<Section> = (0..count).map(|_| Section::default()).collect();
+
+        for triangle in self.triangles.iter() {
+            let indices = triangle.vertices_indices;
+            let [h0, h1, h2] = indices.map(|index| heights[index]);
+            let low = h0.min(h1).min(h2);
+            let high = h0.max(h1).max(h2);
+
+            let first = ((low - bottom) / layer_height - 0.5).ceil().max(0.0) as usize;
+            let last = ((high - bottom) / layer_height - 0.5).floor();
+            if last < 0.0 {
+                continue;
+            }
+            let last = (last as usize).min(count - 1);
+
+            for layer in first..=last {
+                sections[layer].add_triangle(&self.vertices, indices, &planes[layer]);
+            }
+        }
+
+        sections
+            .into_iter()
+            .zip(planes)
+            .map(|(section, plane)| Layer {
+                height: plane.offset,
+                contours: section
+                    .into_contours()
+                    .into_iter()
+                    .map(|contour| LayerContour {
+                        points: contour
+                            .points
+                            .iter()
+                            .map(|point| {
+                                Vector2D::new(
+                                    point.dot_product(&axes[0]),
+                                    point.dot_product(&axes[1]),
+                                )
+                            })
+                            .collect(),
+                        closed: contour.closed,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,34 @@
+use super::{Color, Vector2D};
+
+/// RGB image mapped onto triangles through their UV coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Texture {
+    pub width: usize,
+    pub height: usize,
+    /// Row by row, from the top one.
+    pub pixels: Vec<Color>,
+}
+
+impl Texture {
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> Texture {
+        Texture {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Nearest pixel to `uv`, repeating the image outside [0, 1]. V grows upwards,
+    /// as in OBJ files.
+    pub fn sample(&self, uv: &Vector2D) -> Color {
+        if self.pixels.is_empty() {
+            return Color::new(0, 0, 0);
+        }
+
+        let u = uv[0] - uv[0].floor();
+        let v = uv[1] - uv[1].floor();
+        let col = ((u * self.width as f32) as usize).min(self.width - 1);
+        let row = (((1.0 - v) * self.height as f32) as usize).min(self.height - 1);
+        self.pixels[row * self.width + col]
+    }
+}
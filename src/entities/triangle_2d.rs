@@ -1,10 +1,23 @@
-use super::{TriangleArea, Vector2D};
+use super::{Color, Curvature, Texture, TriangleArea, Vector2D, Vector3D};
+use std::sync::Arc;
 
 pub struct VisibleTriangle2D {
     pub vertices: [Vector2D; 3],
     pub shadow_value: f32,
     pub mean_z: f32,
+    /// Depth of each vertex, larger is closer.
+    pub depths: [f32; 3],
+    /// Normal of the side that is drawn.
+    pub normal: Vector3D,
     pub area: Option<f32>,
+    pub back_face: bool,
+    pub color: Option<Color>,
+    pub uvs: Option<[Vector2D; 3]>,
+    pub texture: Option<Arc<Texture>>,
+    /// Share of the ambient light blocked at each vertex, once baked.
+    pub occlusion: Option<[f32; 3]>,
+    /// Curvature of the surface at each vertex, once baked.
+    pub curvature: Option<[Curvature; 3]>,
 }
 
 impl VisibleTriangle2D {
@@ -42,6 +55,66 @@ impl VisibleTriangle2D {
         self.vertices.calculate_area()
     }
 
+    /// Weights of the vertices that give `point` as their weighted sum.
+    pub fn barycentric_coordinates(&self, point: &Vector2D) -> [f32; 3] {
+        let [p1, p2, p3] = self.vertices;
+        let area = (p2 - p1).cross_product(&(p3 - p1));
+        if area.abs() <= f32::EPSILON {
+            return [1.0 / 3.0; 3];
+        }
+
+        let w1 = (p3 - p2).cross_product(&(*point - p2)) / area;
+        let w2 = (p1 - p3).cross_product(&(*point - p3)) / area;
+        [w1, w2, 1.0 - w1 - w2]
+    }
+
+    /// Depth of the face at `point`.
+    pub fn depth_at(&self, point: &Vector2D) -> f32 {
+        let weights = self.barycentric_coordinates(point);
+        weights[0] * self.depths[0] + weights[1] * self.depths[1] + weights[2] * self.depths[2]
+    }
+
+    /// Share of the ambient light blocked at `point`, zero when it was not baked.
+    pub fn occlusion_at(&self, point: &Vector2D) -> f32 {
+        let occlusion = match self.occlusion {
+            Some(occlusion) => occlusion,
+            None => return 0.0,
+        };
+        let weights = self.barycentric_coordinates(point);
+        weights[0] * occlusion[0] + weights[1] * occlusion[1] + weights[2] * occlusion[2]
+    }
+
+    /// Curvature of the surface at `point`, if it was baked.
+    pub fn curvature_at(&self, point: &Vector2D) -> Option<Curvature> {
+        let curvature = self.curvature?;
+        let weights = self.barycentric_coordinates(point);
+        Some(Curvature {
+            mean: (0..3)
+                .map(|index| weights[index] * curvature[index].mean)
+                .sum(),
+            gaussian: (0..3)
+                .map(|index| weights[index] * curvature[index].gaussian)
+                .sum(),
+        })
+    }
+
+    /// Texture color at `point`, for textured triangles.
+    ///
+    /// The projection is orthographic, so depth does not change along the screen
+    /// and interpolating the UVs linearly in screen space is already perspective
+    /// correct.
+    pub fn sample_texture(&self, point: &Vector2D) -> Option<Color> {
+        let texture = self.texture.as_ref()?;
+        let uvs = self.uvs?;
+        let weights = self.barycentric_coordinates(point);
+
+        let uv = Vector2D::new(
+            weights[0] * uvs[0][0] + weights[1] * uvs[1][0] + weights[2] * uvs[2][0],
+            weights[0] * uvs[0][1] + weights[1] * uvs[1][1] + weights[2] * uvs[2][1],
+        );
+        Some(texture.sample(&uv))
+    }
+
     pub fn contains_point(&self, point: &Vector2D) -> bool {
         let [p1, p2, p3] = self.vertices;
         let area_1 = [p1, p2, *point].calculate_area();
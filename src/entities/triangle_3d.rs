@@ -1,4 +1,5 @@
-use super::Vector3D;
+use super::{Color, Curvature, Texture, Vector2D, Vector3D};
+use std::sync::Arc;
 
 pub struct Triangle3D {
     pub normal: Vector3D,
@@ -11,6 +12,10 @@ impl Triangle3D {
         self.normal.dot_product(observer) < 0.0
     }
 
+    pub fn is_back_facing(&self, observer: &Vector3D) -> bool {
+        self.normal.dot_product(observer) > 0.0
+    }
+
     pub fn shadow_value(&self, light: &Vector3D) -> f32 {
         let light_norm = light.norm();
         let normal_norm = self.normal.norm();
@@ -27,13 +32,35 @@ impl Triangle3D {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct IndexedTriangle3D {
     pub normal: Vector3D,
     pub vertices_indices: [usize; 3],
+    /// Index into the `groups` of the mesh.
+    pub group: Option<usize>,
+    /// Texture coordinates of each corner.
+    pub uvs: Option<[Vector2D; 3]>,
+    /// Color of the facet itself, as stored in some binary STL files.
+    pub color: Option<Color>,
+    /// Share of the ambient light blocked at each corner, once baked.
+    pub occlusion: Option<[f32; 3]>,
+    /// Curvature of the surface at each corner, once baked.
+    pub curvature: Option<[Curvature; 3]>,
 }
 
 impl IndexedTriangle3D {
+    pub fn new(normal: Vector3D, vertices_indices: [usize; 3]) -> IndexedTriangle3D {
+        IndexedTriangle3D {
+            normal,
+            vertices_indices,
+            group: None,
+            uvs: None,
+            color: None,
+            occlusion: None,
+            curvature: None,
+        }
+    }
+
     pub fn rotate(&mut self, angles: (&f32, &f32, &f32)) {
         self.normal.rotate(angles);
     }
@@ -45,4 +72,10 @@ pub struct VisibleIndexedTriangle3D {
     pub normal: Vector3D,
     pub shadow_value: f32,
     pub mean_z: f32,
+    pub back_face: bool,
+    pub color: Option<Color>,
+    pub uvs: Option<[Vector2D; 3]>,
+    pub texture: Option<Arc<Texture>>,
+    pub occlusion: Option<[f32; 3]>,
+    pub curvature: Option<[Curvature; 3]>,
 }
@@ -9,6 +9,11 @@ impl Vector2D {
         Vector2D([x, y])
     }
 
+    pub fn norm(&self) -> f32 {
+        let Vector2D([x, y]) = self;
+        (x * x + y * y).sqrt()
+    }
+
     pub fn cross_product(&self, other: &Vector2D) -> f32 {
         let Vector2D([x1, y1]) = self;
         let Vector2D([x2, y2]) = other;
@@ -1,4 +1,4 @@
-use rayon::prelude::*;
+use crate::parallel::*;
 use std::ops::{Index, IndexMut, Sub};
 use std::sync::{Arc, Mutex};
 
@@ -154,16 +154,10 @@ pub trait CalculateMaximumRadius {
 
 impl CalculateMaximumRadius for [Vector3D] {
     fn calculate_maximum_radius(&self) -> f32 {
-        self.par_iter().map(|vertex| vertex.norm()).reduce(
-            || 0.0,
-            |a, b| {
-                if a > b {
-                    a
-                } else {
-                    b
-                }
-            },
-        )
+        self.par_iter()
+            .map(|vertex| vertex.norm())
+            .max_by(|a, b| a.total_cmp(b))
+            .unwrap_or(0.0)
     }
 }
 
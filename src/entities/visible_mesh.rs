@@ -1,8 +1,30 @@
-use super::{IndexedMesh3D, Triangle3D, Vector3D, VisibleIndexedTriangle3D, VisibleTriangle2D};
-use rayon::prelude::*;
+use super::{Color, IndexedMesh3D, Vector3D, VisibleIndexedTriangle3D, VisibleTriangle2D};
+use crate::parallel::*;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+/// Selects which faces survive the visibility test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CullMode {
+    /// Discard faces pointing away from the observer.
+    #[default]
+    Back,
+    /// Discard faces pointing towards the observer.
+    Front,
+    /// Keep both sides; back faces are flagged so they can be drawn apart.
+    None,
+}
+
+impl CullMode {
+    pub fn next(&self) -> CullMode {
+        match self {
+            CullMode::Back => CullMode::Front,
+            CullMode::Front => CullMode::None,
+            CullMode::None => CullMode::Back,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct VisibleIndexedMesh3D {
     pub vertices: HashMap<usize, Vector3D>,
@@ -20,43 +42,67 @@ impl VisibleIndexedMesh3D {
         mesh: &IndexedMesh3D,
         light: &Vector3D,
         observer: &Vector3D,
+        cull_mode: CullMode,
     ) -> VisibleIndexedMesh3D {
         let visible_mesh = Arc::new(Mutex::new(VisibleIndexedMesh3D {
             vertices: HashMap::new(),
             triangles: Vec::new(),
         }));
 
-        mesh.iter().par_bridge().for_each(|triangle| {
-            if triangle.is_visible(observer) {
-                let shadow_value = triangle.shadow_value(light);
-                let mean_z = triangle.mean_z();
+        mesh.iter()
+            .zip(mesh.triangles.iter())
+            .par_bridge()
+            .for_each(|(triangle, indexed_triangle)| {
+                let back_face = match cull_mode {
+                    CullMode::Back if triangle.is_visible(observer) => false,
+                    CullMode::Front if triangle.is_back_facing(observer) => true,
+                    CullMode::None if triangle.is_visible(observer) => false,
+                    CullMode::None if triangle.is_back_facing(observer) => true,
+                    _ => return,
+                };
+
+                // Back faces are lit as seen from behind, i.e. with the normal flipped.
+                let shadow_value = if back_face {
+                    -triangle.shadow_value(light)
+                } else {
+                    triangle.shadow_value(light)
+                };
+                let material = mesh.get_material(indexed_triangle.group);
+
+                let visible_triangle = VisibleIndexedTriangle3D {
+                    vertices_indices: triangle.vertices_indices,
+                    normal: triangle.normal,
+                    shadow_value,
+                    mean_z: triangle.mean_z(),
+                    back_face,
+                    color: indexed_triangle
+                        .color
+                        .or(material.map(|material| material.diffuse)),
+                    uvs: indexed_triangle.uvs,
+                    texture: material.and_then(|material| material.texture.clone()),
+                    occlusion: indexed_triangle.occlusion,
+                    curvature: indexed_triangle.curvature,
+                };
 
                 visible_mesh
                     .lock()
                     .unwrap()
-                    .set_triangle(triangle, shadow_value, mean_z);
-            }
-        });
+                    .set_triangle(&triangle.vertices, visible_triangle);
+            });
 
         let result = visible_mesh.lock().unwrap().clone();
         result
     }
 
-    fn set_triangle(&mut self, triangle: Triangle3D, shadow_value: f32, mean_z: f32) {
-        let vertices_indices = triangle.vertices_indices;
-        let vertices = triangle.vertices;
-
+    fn set_triangle(
+        &mut self,
+        vertices: &[Vector3D; 3],
+        visible_triangle: VisibleIndexedTriangle3D,
+    ) {
         for (index, vertex) in vertices.iter().enumerate() {
-            self.set_vertex(&vertices_indices[index], vertex);
+            self.set_vertex(&visible_triangle.vertices_indices[index], vertex);
         }
 
-        let visible_triangle = VisibleIndexedTriangle3D {
-            vertices_indices,
-            normal: triangle.normal,
-            shadow_value,
-            mean_z,
-        };
-
         self.triangles.push(visible_triangle);
     }
 
@@ -70,7 +116,14 @@ impl VisibleIndexedMesh3D {
         self.vertices.contains_key(index)
     }
 
-    pub fn iter(&self) -> VisibleIndexedMesh3DIterator {
+    /// Gives `color` to the triangles that do not have one of their own.
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.triangles.iter_mut().for_each(|triangle| {
+            triangle.color = triangle.color.or(color);
+        });
+    }
+
+    pub fn iter(&self) -> VisibleIndexedMesh3DIterator<'_> {
         VisibleIndexedMesh3DIterator {
             vertices: &self.vertices,
             triangles: &self.triangles,
@@ -85,17 +138,29 @@ impl Iterator for VisibleIndexedMesh3DIterator<'_> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < self.triangles.len() {
             let visible_indexed_triangle = &self.triangles[self.index];
-            let vertices = [
-                self.vertices[&visible_indexed_triangle.vertices_indices[0]].into(),
-                self.vertices[&visible_indexed_triangle.vertices_indices[1]].into(),
-                self.vertices[&visible_indexed_triangle.vertices_indices[2]].into(),
-            ];
+            let vertices_3d = visible_indexed_triangle
+                .vertices_indices
+                .map(|index| self.vertices[&index]);
+            let vertices = vertices_3d.map(|vertex| vertex.into());
+            let normal = visible_indexed_triangle.normal;
 
             let visible_triangle = VisibleTriangle2D {
                 vertices,
                 shadow_value: visible_indexed_triangle.shadow_value,
                 mean_z: visible_indexed_triangle.mean_z,
+                depths: vertices_3d.map(|vertex| vertex[2]),
+                normal: if visible_indexed_triangle.back_face {
+                    Vector3D::new(-normal[0], -normal[1], -normal[2])
+                } else {
+                    normal
+                },
                 area: None,
+                back_face: visible_indexed_triangle.back_face,
+                color: visible_indexed_triangle.color,
+                uvs: visible_indexed_triangle.uvs,
+                texture: visible_indexed_triangle.texture.clone(),
+                occlusion: visible_indexed_triangle.occlusion,
+                curvature: visible_indexed_triangle.curvature,
             };
 
             self.index += 1;
@@ -1,15 +1,471 @@
-use clap::Parser;
+use super::{get_config_path, parse_contrast, parse_gamma, read_config, Conversion, Render};
+use crate::{
+    Color, CullMode, Dithering, Layout, Ramp, RayTracing, Shading, Shadows, Toon, Transform,
+    Vector3D, Visualization,
+};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
 
 /// A simple 3D files viewer on console
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    /// Path to the 3D file. It can be OBJ or STL.
-    #[arg()]
-    path: String,
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Paths to the 3D files. They can be OBJ, STL or PLY.
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+
+    /// How to place several files: as modeled, in a row, or on top of each other.
+    #[arg(long, value_enum, default_value_t = LayoutArg::Assembly)]
+    pub layout: LayoutArg,
+
+    /// Placement of a file, moved by `tx,ty,tz` and optionally rotated by
+    /// `rx,ry,rz` degrees and scaled around its center. Given once per file, in
+    /// the order of the paths.
+    #[arg(
+        long = "transform",
+        allow_hyphen_values = true,
+        value_name = "TRANSFORM"
+    )]
+    pub transforms: Vec<Transform>,
+
+    /// Draw with 24-bit ANSI colors, one per file.
+    #[arg(long)]
+    pub color: bool,
+
+    /// Which faces to discard. With `none` the inner side is drawn with its own glyphs.
+    #[arg(long, value_enum, default_value_t = Cull::Back)]
+    pub cull: Cull,
+
+    /// Layer height of the layer view. Defaults to a fiftieth of the object size.
+    #[arg(long)]
+    pub layer_height: Option<f32>,
+
+    /// Start with the shadows cast by the light turned on. They can be toggled with `s`.
+    #[arg(long)]
+    pub shadows: bool,
+
+    /// Depth, in texels of the shadow map, a point may lie behind the surface
+    /// closest to the light and still be lit.
+    #[arg(long, default_value_t = 1.5, value_name = "TEXELS")]
+    pub shadow_bias: f32,
+
+    /// Texels around each point also tested against the shadow map, to soften
+    /// the borders of the shadows.
+    #[arg(long, default_value_t = 1, value_name = "TEXELS")]
+    pub shadow_softness: usize,
+
+    /// Bake ambient occlusion at load time, casting this many rays from every vertex.
+    #[arg(long, value_name = "RAYS")]
+    pub ao: Option<usize>,
+
+    #[command(flatten)]
+    pub shading: ShadingArgs,
+}
+
+impl Args {
+    pub fn get_shadows(&self) -> Shadows {
+        Shadows {
+            enabled: self.shadows,
+            bias: self.shadow_bias,
+            softness: self.shadow_softness,
+            ..Shadows::default()
+        }
+    }
+}
+
+/// How the faces are drawn, for the viewer and `render`.
+#[derive(ClapArgs, Debug)]
+pub struct ShadingArgs {
+    /// Config file with defaults for `--ramp`, `--dither`, `--gamma` and
+    /// `--contrast`. Defaults to `~/.config/sx3d/config` when it exists.
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<String>,
+
+    /// Glyphs from the darkest to the brightest: `standard`, `classic`, `blocks`,
+    /// `digits`, or the characters themselves. Defaults to `standard`.
+    #[arg(long)]
+    pub ramp: Option<String>,
+
+    /// How shades between two glyphs are spread over the cells. Defaults to `none`.
+    #[arg(long, value_enum)]
+    pub dither: Option<Dither>,
+
+    /// Gamma of the shades, above 0. Above 1 brightens the mid tones. Defaults to 1.
+    #[arg(long, value_parser = parse_gamma)]
+    pub gamma: Option<f32>,
+
+    /// Contrast of the shades around the middle gray, from 0 on. Defaults to 1.
+    #[arg(long, value_parser = parse_contrast)]
+    pub contrast: Option<f32>,
+
+    /// Draw silhouettes and creases with `/ \ | _ -` along their direction.
+    #[arg(long)]
+    pub edges: bool,
+
+    /// Cel shading: split the light into this many flat bands and outline the model.
+    #[arg(long, value_name = "BANDS")]
+    pub toon: Option<usize>,
+
+    /// Glyph of the toon outlines.
+    #[arg(long, default_value_t = '#', value_name = "CHAR")]
+    pub outline_char: char,
+
+    /// Color of the toon outlines with `--color`, as `#rrggbb`.
+    #[arg(long, value_name = "COLOR")]
+    pub outline_color: Option<Color>,
+
+    /// Darken creases and corners with ambient occlusion estimated from the depth
+    /// of every frame.
+    #[arg(long)]
+    pub ssao: bool,
+
+    /// What the faces show instead of the light, to inspect the surface. Colored
+    /// with `--color`.
+    #[arg(long, value_enum, default_value_t = View::Shaded)]
+    pub view: View,
+}
+
+impl ShadingArgs {
+    /// Reads the config file given with `--config`, or the one at the default
+    /// path if there is one, and keeps its values for the flags not given.
+    pub fn apply_config(&mut self) -> Result<(), String> {
+        let path = match &self.config {
+            Some(path) => path.into(),
+            None => match get_config_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(()),
+            },
+        };
+        let file = std::fs::File::open(&path)
+            .map_err(|error| format!("Error: {error} Opening Config {}", path.to_string_lossy()))?;
+        let config = read_config(file)?;
+
+        self.ramp = self.ramp.take().or(config.ramp);
+        self.dither = self.dither.or(config.dithering.map(Dither::from));
+        self.gamma = self.gamma.or(config.gamma);
+        self.contrast = self.contrast.or(config.contrast);
+        Ok(())
+    }
+
+    pub fn get_shading(&self) -> Shading {
+        let ramp = self.ramp.as_deref().unwrap_or("standard");
+        let defaults = Shading::default();
+        Shading {
+            ramp: match Ramp::from_name(ramp) {
+                Some(ramp) => ramp.chars(),
+                None => ramp.chars().collect(),
+            },
+            dithering: self.dither.map_or(defaults.dithering, Dithering::from),
+            gamma: self.gamma.unwrap_or(defaults.gamma),
+            contrast: self.contrast.unwrap_or(defaults.contrast),
+            edges: self.edges,
+            toon: self.toon.map(|bands| Toon {
+                bands,
+                outline: self.outline_char,
+                outline_color: self.outline_color,
+            }),
+            ssao: self.ssao,
+            visualization: self.view.into(),
+            ..defaults
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Convert a 3D file to STL, OBJ or PLY, chosen by the output extension.
+    Convert(ConvertArgs),
+    /// Slice a 3D file along Z and write the contour of every layer as SVG.
+    Slice(SliceArgs),
+    /// Render 3D files once and print the frame or write it to a file.
+    Render(RenderArgs),
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct RenderArgs {
+    /// Paths to the 3D files. They can be OBJ, STL or PLY.
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+
+    /// File to write the frame to instead of printing it. Names ending in .ppm
+    /// or .svg get an image of the frame.
+    #[arg(long, short)]
+    pub output: Option<String>,
+
+    /// Columns and rows of the frame, an odd number.
+    #[arg(long, default_value_t = 79, value_name = "CELLS")]
+    pub size: usize,
+
+    /// How to place several files: as modeled, in a row, or on top of each other.
+    #[arg(long, value_enum, default_value_t = LayoutArg::Assembly)]
+    pub layout: LayoutArg,
+
+    /// Placement of a file, moved by `tx,ty,tz` and optionally rotated by
+    /// `rx,ry,rz` degrees and scaled around its center. Given once per file, in
+    /// the order of the paths.
+    #[arg(
+        long = "transform",
+        allow_hyphen_values = true,
+        value_name = "TRANSFORM"
+    )]
+    pub transforms: Vec<Transform>,
+
+    /// Draw with 24-bit ANSI colors, one per file.
+    #[arg(long)]
+    pub color: bool,
+
+    /// Write braille dots, 2 by 4 cells per character, instead of a glyph per cell.
+    #[arg(long)]
+    pub braille: bool,
+
+    /// Which faces to discard. With `none` the inner side is drawn with its own glyphs.
+    #[arg(long, value_enum, default_value_t = Cull::Back)]
+    pub cull: Cull,
+
+    /// Rotation of the view around the X axis, in degrees.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub rotate_x: f32,
+
+    /// Rotation of the view around the Y axis, in degrees.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub rotate_y: f32,
+
+    /// Rotation of the view around the Z axis, in degrees.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub rotate_z: f32,
+
+    /// Cast rays through every cell instead of rasterizing the triangles. Slower,
+    /// with hard shadows, reflections and ambient occlusion.
+    #[arg(long)]
+    pub ray_trace: bool,
+
+    /// Rays per side of every cell with `--ray-trace`, which casts the square of it.
+    #[arg(long, default_value_t = 2)]
+    pub samples: usize,
+
+    /// Cast the shadows of the light.
+    #[arg(long)]
+    pub shadows: bool,
+
+    /// Share of the light the faces reflect like mirrors with `--ray-trace`.
+    #[arg(long, default_value_t = 0.0, value_name = "SHARE")]
+    pub reflectivity: f32,
+
+    /// Times a ray is reflected at most with `--ray-trace`.
+    #[arg(long, default_value_t = 2)]
+    pub bounces: usize,
+
+    /// Ambient occlusion: rays cast from every hit with `--ray-trace`, or from
+    /// every vertex at load time otherwise.
+    #[arg(long, value_name = "RAYS")]
+    pub ao: Option<usize>,
+
+    #[command(flatten)]
+    pub shading: ShadingArgs,
+}
+
+impl From<RenderArgs> for Render {
+    fn from(args: RenderArgs) -> Self {
+        Render {
+            shading: args.shading.get_shading(),
+            paths: args.paths,
+            output: args.output,
+            size: args.size,
+            layout: args.layout.into(),
+            transforms: args.transforms,
+            colored: args.color,
+            braille: args.braille,
+            cull_mode: args.cull.into(),
+            rotation: (
+                args.rotate_x.to_radians(),
+                args.rotate_y.to_radians(),
+                args.rotate_z.to_radians(),
+            ),
+            shadows: args.shadows,
+            ambient_occlusion: args.ao,
+            ray_tracing: args.ray_trace.then_some(RayTracing {
+                samples: args.samples,
+                shadows: args.shadows,
+                reflectivity: args.reflectivity,
+                bounces: args.bounces,
+                occlusion_rays: args.ao.unwrap_or(0),
+            }),
+        }
+    }
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct SliceArgs {
+    /// Path to the 3D file to read. It can be OBJ, STL or PLY.
+    pub input: String,
+
+    /// Directory to write `layer_0001.svg`, `layer_0002.svg`...
+    pub output: String,
+
+    /// Distance between slicing planes, in model units.
+    #[arg(long, default_value_t = 0.2)]
+    pub layer_height: f32,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ConvertArgs {
+    /// Path to the 3D file to read. It can be OBJ, STL or PLY.
+    pub input: String,
+
+    /// Path to the file to write. It can be STL, OBJ or PLY.
+    pub output: String,
+
+    /// Write ASCII STL or PLY instead of binary.
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Move the centroid of the vertices to the origin.
+    #[arg(long)]
+    pub center: bool,
+
+    /// Rotation around the X axis, in degrees.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub rotate_x: f32,
+
+    /// Rotation around the Y axis, in degrees.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub rotate_y: f32,
+
+    /// Rotation around the Z axis, in degrees.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub rotate_z: f32,
+
+    /// Uniform scale factor.
+    #[arg(long)]
+    pub scale: Option<f32>,
+
+    /// Translation applied after rotating and scaling.
+    #[arg(long, num_args = 3, value_names = ["X", "Y", "Z"], allow_negative_numbers = true)]
+    pub translate: Option<Vec<f32>>,
+
+    /// Merge vertices closer than this distance.
+    #[arg(long, value_name = "TOLERANCE")]
+    pub weld: Option<f32>,
+
+    /// Remove degenerate and duplicated triangles.
+    #[arg(long)]
+    pub repair: bool,
+
+    /// Simplify the mesh down to this number of triangles.
+    #[arg(long, value_name = "TRIANGLES")]
+    pub decimate: Option<usize>,
+}
+
+impl From<ConvertArgs> for Conversion {
+    fn from(args: ConvertArgs) -> Self {
+        Conversion {
+            input: args.input,
+            output: args.output,
+            ascii: args.ascii,
+            center: args.center,
+            rotation: (
+                args.rotate_x.to_radians(),
+                args.rotate_y.to_radians(),
+                args.rotate_z.to_radians(),
+            ),
+            scale: args.scale,
+            translation: args.translate.map(|t| Vector3D::new(t[0], t[1], t[2])),
+            weld: args.weld,
+            repair: args.repair,
+            decimate: args.decimate,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Cull {
+    Back,
+    Front,
+    None,
+}
+
+impl From<Cull> for CullMode {
+    fn from(cull: Cull) -> Self {
+        match cull {
+            Cull::Back => CullMode::Back,
+            Cull::Front => CullMode::Front,
+            Cull::None => CullMode::None,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Dither {
+    None,
+    Bayer,
+    FloydSteinberg,
+}
+
+impl From<Dither> for Dithering {
+    fn from(dither: Dither) -> Self {
+        match dither {
+            Dither::None => Dithering::None,
+            Dither::Bayer => Dithering::Bayer,
+            Dither::FloydSteinberg => Dithering::FloydSteinberg,
+        }
+    }
+}
+
+impl From<Dithering> for Dither {
+    fn from(dithering: Dithering) -> Self {
+        match dithering {
+            Dithering::None => Dither::None,
+            Dithering::Bayer => Dither::Bayer,
+            Dithering::FloydSteinberg => Dither::FloydSteinberg,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum View {
+    Shaded,
+    Depth,
+    Normal,
+    MeanCurvature,
+    GaussianCurvature,
+}
+
+impl From<View> for Visualization {
+    fn from(view: View) -> Self {
+        match view {
+            View::Shaded => Visualization::Shaded,
+            View::Depth => Visualization::Depth,
+            View::Normal => Visualization::Normal,
+            View::MeanCurvature => Visualization::MeanCurvature,
+            View::GaussianCurvature => Visualization::GaussianCurvature,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum LayoutArg {
+    Assembly,
+    SideBySide,
+    Overlay,
+}
+
+impl From<LayoutArg> for Layout {
+    fn from(layout: LayoutArg) -> Self {
+        match layout {
+            LayoutArg::Assembly => Layout::Assembly,
+            LayoutArg::SideBySide => Layout::SideBySide,
+            LayoutArg::Overlay => Layout::Overlay,
+        }
+    }
+}
+
+pub fn get_args() -> Args {
+    Args::parse()
 }
 
 pub fn get_file_path() -> String {
-    let args = Args::parse();
-    args.path
+    get_args().paths.remove(0)
 }
@@ -1,16 +1,39 @@
 use super::read_mesh;
 use super::Controller;
-use crate::canvas::SquaredCanvas;
+use super::{Frontend, Input, TerminalGuard};
+use crate::canvas::{AsciiTarget, Frame, FrameDiff, Rasterizer, Shading, SquaredCanvas};
 use crate::entities::object::Object;
-use crate::entities::scene::Scene;
-use crate::Vector3D;
-use std::io::{stdin, stdout, Write};
-use termion::event::{Event, Key};
-use termion::input::TermRead;
-use termion::raw::{IntoRawMode, RawTerminal};
-use termion::terminal_size;
+use crate::entities::scene::{Layout, Scene};
+use crate::{Color, CullMode, Shadows, Transform, Vector3D};
+use std::time::Duration;
 
-pub struct Console {}
+const HELP_TEXT: &str = "To start press Enter key.\r\nTo rotate the object around XY axis use arrow keys.\r\nTo cycle back/front/no face culling, press 'c'.\r\nTo toggle the shadows, press 's'.\r\nTo toggle the picking cursor press 'x', and move it with the arrow keys or click a triangle.\r\nTo measure press 'm', then click or press space on up to three points, press 'a' for the area of a face, 'b' for the bounding boxes and backspace to clear.\r\nTo cycle the clipping plane between off/X/Y/Z press 'p', move it with '+' and '-', flip it with 'f' and toggle the cap with 'k'.\r\nTo toggle the layer view press 'l', and step through the layers with '[' and ']'.\r\nTo show or hide one of the first nine objects, press its number.\r\nTo select the next group press 'g', hide it with 'h' and isolate it with 'i'.\r\nTo quit, press 'q'.";
+
+// Time without input after which the full detail mesh replaces the reduced one.
+const IDLE_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// What the terminal shows, to write only what changes.
+struct Screen {
+    diff: FrameDiff,
+    /// Columns and lines of the terminal.
+    size: (usize, usize),
+    /// Frame shown last, before fitting it in the terminal, to show it again
+    /// when the terminal is resized.
+    frame: Frame,
+}
+
+pub struct Console {
+    cull_mode: CullMode,
+    layer_height: Option<f32>,
+    layout: Layout,
+    /// Placement of each file, in the order they are given.
+    transforms: Vec<Transform>,
+    colored: bool,
+    shading: Shading,
+    shadows: Shadows,
+    /// Rays cast from every vertex to bake the ambient occlusion, if it is baked.
+    ambient_occlusion: Option<usize>,
+}
 
 impl Default for Console {
     fn default() -> Self {
@@ -20,91 +43,212 @@ impl Default for Console {
 
 impl Console {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            cull_mode: CullMode::default(),
+            layer_height: None,
+            layout: Layout::default(),
+            transforms: Vec::new(),
+            colored: false,
+            shading: Shading::default(),
+            shadows: Shadows::default(),
+            ambient_occlusion: None,
+        }
+    }
+
+    pub fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        self.cull_mode = cull_mode;
+    }
+
+    pub fn set_layer_height(&mut self, layer_height: Option<f32>) {
+        self.layer_height = layer_height;
+    }
+
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+    }
+
+    pub fn set_transforms(&mut self, transforms: Vec<Transform>) {
+        self.transforms = transforms;
+    }
+
+    pub fn set_colored(&mut self, colored: bool) {
+        self.colored = colored;
+    }
+
+    pub fn set_shading(&mut self, shading: Shading) {
+        self.shading = shading;
+    }
+
+    pub fn set_shadows(&mut self, shadows: Shadows) {
+        self.shadows = shadows;
     }
 
-    pub fn start(&mut self, path: String) {
-        let indexed_mesh = read_mesh(path).unwrap();
-        let object = Object::new(indexed_mesh.clone());
-        let size = terminal_size().unwrap();
+    pub fn set_ambient_occlusion(&mut self, rays: Option<usize>) {
+        self.ambient_occlusion = rays;
+    }
+
+    /// Runs the viewer on `frontend` until 'q' is pressed or the input closes.
+    pub fn start(&mut self, paths: Vec<String>, frontend: &mut dyn Frontend) -> Result<(), String> {
+        if paths.is_empty() {
+            return Err("Error: No Files To Show".to_string());
+        }
+        if self.transforms.len() > paths.len() {
+            return Err("Error: More Transforms Than Files".to_string());
+        }
+        let mut objects = Vec::new();
+        for (index, path) in paths.into_iter().enumerate() {
+            let mut indexed_mesh = read_mesh(path.clone())?;
+            if let Some(rays) = self.ambient_occlusion {
+                indexed_mesh.bake_ambient_occlusion(rays);
+            }
+            if self.shading.visualization.uses_curvature() {
+                indexed_mesh.bake_curvature();
+            }
+            let mut object = Object::new(indexed_mesh);
+            object.set_name(path);
+            object.set_color(Some(Color::palette(index)));
+            objects.push(object);
+        }
+        let mut objects = objects.into_iter();
+        let size = frontend.get_size()?;
 
         let (mut i, j) = size;
-        i = (i + 2) / 3;
+        i = i.div_ceil(3);
         let mut cols: usize;
 
         if i >= j {
-            cols = j as usize;
+            cols = j;
         } else {
-            cols = i as usize;
+            cols = i;
         }
 
-        if cols % 2 == 0 {
+        if cols.is_multiple_of(2) {
             cols -= 1;
         } else {
             cols -= 2;
         }
 
-        let maximum_diameter = 2.0 * object.get_maximum_radius();
-
-        let scene = Scene::new(
-            object,
+        let mut scene = Scene::new(
+            objects.next().unwrap(),
             Vector3D::new(-1.0, -1.0, -1.0),
             Vector3D::new(0.0, 0.0, -1.0),
         );
-        let matrix = SquaredCanvas::new(cols, maximum_diameter);
+        objects.for_each(|object| scene.add_object(object));
+        scene.arrange(self.layout);
+        scene.apply_transforms(&self.transforms);
+        scene.set_cull_mode(self.cull_mode);
+        scene.set_shadows(self.shadows);
+
+        let maximum_diameter = 2.0 * scene.get_maximum_radius();
+        let mut matrix = SquaredCanvas::new(cols, maximum_diameter);
+        matrix.set_shading(self.shading.clone());
 
         let mut controller = Controller::new(scene, matrix);
+        controller.set_renderer(Box::new(Rasterizer::new(self.shading.visualization)));
+        controller.set_target(Box::new(AsciiTarget::new(self.colored, "\r\n")));
+        let layer_height = self.layer_height.unwrap_or(maximum_diameter / 50.0);
+        controller.set_layer_height(layer_height);
+        controller.set_terminal_width(size.0);
 
-        let stdin = stdin();
-        let mut stdout = stdout().into_raw_mode().unwrap();
-
-        Self::print_frame(
-                "To start press Enter key.\r\nTo rotate the object around XY axis use arrow keys.\r\nTo quit, press 'q'.".to_string(),
-                &mut stdout,
-            );
-        stdout.flush().unwrap();
-
-        for c in stdin.events() {
-            let evt = c.unwrap();
-            match evt {
-                Event::Key(Key::Char('q')) => {
-                    Self::print_frame("Goodbye!\r\n".to_string(), &mut stdout);
-                    break;
-                }
-                Event::Key(Key::Char('\n')) => {
-                    let frame = controller.enter_key();
-                    Self::print_frame(frame, &mut stdout);
-                }
-                Event::Key(Key::Up) => {
-                    let frame = controller.up_key();
-                    Self::print_frame(frame, &mut stdout);
-                }
-                Event::Key(Key::Down) => {
-                    let frame = controller.down_key();
-                    Self::print_frame(frame, &mut stdout);
+        let mut terminal = TerminalGuard::new(frontend)?;
+        let mut screen = Screen {
+            diff: FrameDiff::new(),
+            size,
+            frame: Frame::new(0, 0),
+        };
+        Self::run(&mut controller, &mut screen, &mut *terminal)?;
+        drop(terminal);
+
+        frontend.write("Goodbye!\n")?;
+        frontend.flush()
+    }
+
+    fn run(
+        controller: &mut Controller,
+        screen: &mut Screen,
+        frontend: &mut dyn Frontend,
+    ) -> Result<(), String> {
+        Self::print_frame(Frame::from_text(HELP_TEXT), screen, frontend)?;
+        frontend.flush()?;
+
+        loop {
+            let input = match frontend.read_input(IDLE_TIMEOUT) {
+                Ok(Some(input)) => input,
+                Ok(None) => {
+                    if let Some(frame) = controller.idle() {
+                        Self::print_frame(frame, screen, frontend)?;
+                        frontend.flush()?;
+                    }
+                    continue;
                 }
-                Event::Key(Key::Left) => {
-                    let frame = controller.left_key();
-                    Self::print_frame(frame, &mut stdout);
+                // The input is closed.
+                Err(_) => break,
+            };
+
+            let frame = match input {
+                Input::Char('q') => break,
+                Input::Char('\n') => controller.enter_key(),
+                Input::Up => controller.up_key(),
+                Input::Down => controller.down_key(),
+                Input::Left => controller.left_key(),
+                Input::Right => controller.right_key(),
+                Input::Char('x') => controller.cursor_key(),
+                Input::Click(column, line) => controller.click(column, line),
+                Input::Char('m') => controller.measure_key(),
+                Input::Char(' ') => controller.measure_point_key(),
+                Input::Char('a') => controller.measure_face_key(),
+                Input::Char('b') => controller.box_key(),
+                Input::Backspace => controller.clear_measure_key(),
+                Input::Char('c') => controller.cull_key(),
+                Input::Char('s') => controller.shadow_key(),
+                Input::Char('p') => controller.clipping_key(),
+                Input::Char('+') => controller.clipping_forward_key(),
+                Input::Char('-') => controller.clipping_backward_key(),
+                Input::Char('f') => controller.flip_clipping_key(),
+                Input::Char('k') => controller.cap_key(),
+                Input::Char(digit @ '1'..='9') => {
+                    let index = digit as usize - '1' as usize;
+                    controller.object_key(index)
                 }
-                Event::Key(Key::Right) => {
-                    let frame = controller.right_key();
-                    Self::print_frame(frame, &mut stdout);
+                Input::Char('g') => controller.group_key(),
+                Input::Char('h') => controller.hide_group_key(),
+                Input::Char('i') => controller.isolate_group_key(),
+                Input::Char('l') => controller.layer_key(),
+                Input::Char('[') => controller.previous_layer_key(),
+                Input::Char(']') => controller.next_layer_key(),
+                // What the terminal kept of the screen is unknown, so the frame
+                // is written whole again.
+                Input::Resize(columns, lines) => {
+                    screen.size = (columns, lines);
+                    screen.diff.reset();
+                    controller.set_terminal_width(columns);
+                    screen.frame.clone()
                 }
-                _ => {}
-            }
-            stdout.flush().unwrap();
+                // Pasted text is not taken for key presses.
+                _ => continue,
+            };
+            Self::print_frame(frame, screen, frontend)?;
+            frontend.flush()?;
         }
+
+        Ok(())
     }
 
-    fn print_frame(frame: String, stdout: &mut RawTerminal<std::io::Stdout>) {
-        write!(
-            stdout,
-            "{}{}{}",
-            termion::clear::All,
-            termion::cursor::Goto(1, 1),
-            frame
-        )
-        .unwrap();
+    /// Writes what changed since the previous frame, in a synchronized update
+    /// so that the terminal shows the frame at once.
+    fn print_frame(
+        frame: Frame,
+        screen: &mut Screen,
+        frontend: &mut dyn Frontend,
+    ) -> Result<(), String> {
+        screen.frame = frame;
+        let (cols, rows) = screen.size;
+        let update = screen.diff.update(&screen.frame.fit(cols, rows));
+        if update.is_empty() {
+            return Ok(());
+        }
+        frontend.set_synchronized_update(true)?;
+        frontend.write(&update)?;
+        frontend.set_synchronized_update(false)
     }
 }
@@ -1,45 +1,690 @@
-use crate::{Scene, SquaredCanvas};
+use crate::{angle, circle_radius, distance};
+use crate::{
+    Aabb, AsciiTarget, Axis, Canvas, ClippingPlane, Detail, Frame, Layer, Pick, Rasterizer,
+    Renderer, Scene, SquaredCanvas, TextTarget, Vector2D, Vector3D, BOX_CHAR, CURSOR_CHAR,
+    HIGHLIGHT_CHAR, HIGHLIGHT_COLOR, MEASURE_CHAR, MEASURE_FACE_CHAR,
+};
 use std::f32::consts::PI;
 
 const DEFAULT_DELTA: f32 = PI / 10.0;
 
-pub struct Controller {
+// Fraction of the object radius the clipping plane moves per key press.
+const CLIPPING_STEP: f32 = 1.0 / 20.0;
+
+// Triangles kept in the reduced mesh per canvas cell. More detail than this cannot
+// be told apart on the console while the object is moving.
+const TRIANGLES_PER_CELL: usize = 2;
+
+// Columns left between the frame and the measurements beside it, and the fewest
+// columns the measurements need to be put there instead of under the frame.
+const PANEL_GAP: usize = 2;
+const PANEL_MINIMUM_WIDTH: usize = 24;
+
+pub struct Controller<C: Canvas = SquaredCanvas> {
     pub scene: Scene,
-    pub matrix: SquaredCanvas,
+    pub matrix: C,
+    /// Draws the scene on `matrix`.
+    renderer: Box<dyn Renderer>,
+    /// Turns the frames of `matrix` into text.
+    target: Box<dyn TextTarget>,
+    layer_height: f32,
+    layers: Option<Vec<Layer>>,
+    layer: usize,
+    /// Selected mesh group, as the index of its object and its index in the mesh.
+    group: Option<(usize, usize)>,
+    /// Cell of the picking cursor, counted from the center of the canvas.
+    cursor: Option<(isize, isize)>,
+    /// Whether clicks and the space key add measured points.
+    measuring: bool,
+    /// Measured points, as the index of their object and their coordinates in its file.
+    measured_points: Vec<(usize, Vector3D)>,
+    /// Measured face, as the index of its object and its triangles.
+    measured_face: Option<(usize, Vec<usize>)>,
+    /// Bounding box of every object in the coordinates of its file, while shown.
+    boxes: Option<Vec<Aabb>>,
+    /// Columns of the terminal, to put the measurements beside the frame.
+    terminal_width: usize,
 }
 
-impl Controller {
-    pub fn new(scene: Scene, matrix: SquaredCanvas) -> Controller {
-        Controller { scene, matrix }
+impl<C: Canvas> Controller<C> {
+    pub fn new(mut scene: Scene, matrix: C) -> Controller<C> {
+        let cols = matrix.get_cols();
+        scene.build_level_of_detail(cols * cols * TRIANGLES_PER_CELL);
+        let layer_height = matrix.get_maximum_diameter() / 50.0;
+        Controller {
+            scene,
+            matrix,
+            renderer: Box::new(Rasterizer::default()),
+            target: Box::new(AsciiTarget::new(false, "\r\n")),
+            layer_height,
+            layers: None,
+            layer: 0,
+            group: None,
+            cursor: None,
+            measuring: false,
+            measured_points: Vec::new(),
+            measured_face: None,
+            boxes: None,
+            terminal_width: 0,
+        }
+    }
+
+    pub fn set_renderer(&mut self, renderer: Box<dyn Renderer>) {
+        self.renderer = renderer;
+    }
+
+    /// Sets how the frames of the canvas are written as text.
+    pub fn set_target(&mut self, target: Box<dyn TextTarget>) {
+        self.target = target;
+    }
+
+    pub fn set_terminal_width(&mut self, terminal_width: usize) {
+        self.terminal_width = terminal_width;
+    }
+
+    pub fn set_layer_height(&mut self, layer_height: f32) {
+        self.layer_height = layer_height;
+        self.layers = None;
+    }
+
+    fn get_frame(&mut self) -> Frame {
+        let mut frame = match &self.layers {
+            Some(layers) => self.get_layer_frame(layers),
+            None => {
+                self.renderer.render(&self.scene, &self.matrix);
+                self.set_measurements();
+                let pick = self.set_cursor();
+                let mut frame = self.attach_panel(self.draw_frame(), self.get_measure_lines());
+                if self.cursor.is_some() {
+                    push_lines(&mut frame, &self.get_pick_status(pick));
+                }
+                frame
+            }
+        };
+
+        if let Some(status) = self.get_group_status() {
+            push_lines(&mut frame, &[status]);
+        }
+        frame
+    }
+
+    /// Draws the picking cursor and the triangle under it, if there is one.
+    fn set_cursor(&self) -> Option<Pick> {
+        let (col, row) = self.cursor?;
+        let pick = self.pick_at_cursor();
+
+        if let Some(pick) = pick {
+            let mesh = self.scene.get_objects()[pick.object].get_mesh();
+            let vertices = mesh.triangles[pick.triangle]
+                .vertices_indices
+                .map(|index| Vector2D::new(mesh.vertices[index][0], mesh.vertices[index][1]));
+            self.matrix.set_triangle_mark(&vertices, HIGHLIGHT_CHAR);
+        }
+        self.matrix.set_mark(col, row, CURSOR_CHAR);
+        pick
+    }
+
+    /// Lines describing the picked triangle, in the coordinates of its file.
+    fn get_pick_status(&self, pick: Option<Pick>) -> Vec<String> {
+        let pick = match pick {
+            Some(pick) => pick,
+            None => return vec!["No triangle under the cursor".to_string()],
+        };
+        let object = &self.scene.get_objects()[pick.object];
+        let mesh = object.get_mesh();
+        let triangle = &mesh.triangles[pick.triangle];
+
+        let group = triangle
+            .group
+            .and_then(|group| mesh.groups.get(group))
+            .map(|group| format!(", group {}", group.name))
+            .unwrap_or_default();
+        let vertices: Vec<String> = triangle
+            .vertices_indices
+            .iter()
+            .map(|index| {
+                let vertex = self
+                    .scene
+                    .to_file_coordinates(pick.object, &mesh.vertices[*index]);
+                format!("{index} {}", format_vector(&vertex))
+            })
+            .collect();
+        let normal = self.scene.to_file_direction(pick.object, &triangle.normal);
+
+        vec![
+            format!("Triangle {} of {}{group}", pick.triangle, object.get_name()),
+            format!("Vertices {}", vertices.join(", ")),
+            format!("Normal {}", format_vector(&normal)),
+        ]
+    }
+
+    /// Places of the measured points as seen by the observer.
+    fn get_measured_positions(&self) -> Vec<Vector3D> {
+        self.measured_points
+            .iter()
+            .map(|(object, point)| self.scene.from_file_coordinates(*object, point))
+            .collect()
+    }
+
+    /// Draws the bounding boxes, the measured face and the measured points.
+    fn set_measurements(&self) {
+        let objects = self.scene.get_objects();
+
+        for (index, bounds) in self.boxes.iter().flatten().enumerate() {
+            if !objects[index].is_visible() {
+                continue;
+            }
+            let corners: Vec<Vector3D> = (0..8)
+                .map(|corner| {
+                    let pick = |bit: usize, axis: usize| {
+                        if corner & bit == 0 {
+                            bounds.min[axis]
+                        } else {
+                            bounds.max[axis]
+                        }
+                    };
+                    let point = Vector3D::new(pick(1, 0), pick(2, 1), pick(4, 2));
+                    self.scene.from_file_coordinates(index, &point)
+                })
+                .collect();
+            // Corners one bit apart share an edge.
+            for corner in 0..8 {
+                for bit in [1, 2, 4] {
+                    if corner & bit == 0 {
+                        self.matrix.set_depth_line(
+                            &corners[corner],
+                            &corners[corner | bit],
+                            BOX_CHAR,
+                            Some(HIGHLIGHT_COLOR),
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some((object, triangles)) = &self.measured_face {
+            let mesh = objects[*object].get_mesh();
+            for triangle in triangles.iter() {
+                let vertices = mesh.triangles[*triangle]
+                    .vertices_indices
+                    .map(|index| Vector2D::new(mesh.vertices[index][0], mesh.vertices[index][1]));
+                self.matrix.set_triangle_mark(&vertices, MEASURE_FACE_CHAR);
+            }
+        }
+
+        let points: Vec<Vector2D> = self
+            .get_measured_positions()
+            .iter()
+            .map(|point| Vector2D::new(point[0], point[1]))
+            .collect();
+        for segment in points.windows(2) {
+            self.matrix
+                .set_line_mark(&segment[0], &segment[1], MEASURE_CHAR);
+        }
+        for (index, point) in points.iter().enumerate() {
+            let label = char::from_digit(index as u32 + 1, 10).unwrap_or('?');
+            self.matrix.set_point_mark(point, label);
+        }
     }
 
-    fn get_frame(&mut self) -> String {
-        let visible_mesh = self.scene.get_visible_mesh();
-        self.matrix.set_mesh(&visible_mesh);
-        self.matrix.get_frame()
+    /// Lines with the measured points, the distances, angle and radius between
+    /// them, the area of the measured face and the size of the bounding boxes.
+    fn get_measure_lines(&self) -> Vec<String> {
+        let objects = self.scene.get_objects();
+        let mut lines = Vec::new();
+
+        if self.measuring && self.measured_points.is_empty() && self.measured_face.is_none() {
+            lines.push("Click or press space to measure a point, 'a' for a face".to_string());
+        }
+
+        for (index, (object, point)) in self.measured_points.iter().enumerate() {
+            lines.push(format!(
+                "Point {} {} on {}",
+                index + 1,
+                format_vector(point),
+                objects[*object].get_name()
+            ));
+        }
+
+        let positions = self.get_measured_positions();
+        for (first, second) in [(0, 1), (1, 2), (0, 2)] {
+            if let (Some(a), Some(b)) = (positions.get(first), positions.get(second)) {
+                lines.push(format!(
+                    "Distance {}-{}: {:.3}",
+                    first + 1,
+                    second + 1,
+                    distance(a, b)
+                ));
+            }
+        }
+        if let [a, b, c] = positions[..] {
+            match (angle(&a, &b, &c), circle_radius(&a, &b, &c)) {
+                (Some(angle), Some(radius)) => {
+                    lines.push(format!("Angle at 2: {angle:.2} degrees"));
+                    lines.push(format!("Radius through 1, 2 and 3: {radius:.3}"));
+                }
+                _ => lines.push("Points 1, 2 and 3 are aligned".to_string()),
+            }
+        }
+
+        if let Some((object, triangles)) = &self.measured_face {
+            lines.push(format!(
+                "Face of {} triangles on {}: area {:.3}",
+                triangles.len(),
+                objects[*object].get_name(),
+                self.scene.get_file_area(*object, triangles)
+            ));
+        }
+
+        for (object, bounds) in objects.iter().zip(self.boxes.iter().flatten()) {
+            if object.is_visible() {
+                let size = bounds.max - bounds.min;
+                lines.push(format!(
+                    "Box of {}: {:.3} x {:.3} x {:.3}",
+                    object.get_name(),
+                    size[0],
+                    size[1],
+                    size[2]
+                ));
+            }
+        }
+
+        lines
+    }
+
+    /// Puts `lines` beside the rows of `frame` if the terminal is wide enough for
+    /// them, or under it otherwise.
+    fn attach_panel(&self, frame: Frame, lines: Vec<String>) -> Frame {
+        let mut frame = frame;
+        let panel_col = frame.cols + PANEL_GAP;
+        let panel_width = self.terminal_width.saturating_sub(panel_col);
+        if panel_width < PANEL_MINIMUM_WIDTH {
+            push_lines(&mut frame, &lines);
+            return frame;
+        }
+
+        for (row, line) in lines.iter().enumerate() {
+            let line: String = line.chars().take(panel_width).collect();
+            frame.write_text(panel_col, row, &line);
+        }
+        frame
+    }
+
+    fn draw_frame(&self) -> Frame {
+        self.target.draw_cells(&self.matrix.get_frame())
     }
 
-    pub fn enter_key(&mut self) -> String {
+    fn get_frame_with_detail(&mut self, detail: Detail) -> Frame {
+        self.scene.set_detail(detail);
         self.get_frame()
     }
 
-    pub fn up_key(&mut self) -> String {
+    pub fn enter_key(&mut self) -> Frame {
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn up_key(&mut self) -> Frame {
+        if self.cursor.is_some() {
+            return self.move_cursor(0, 1);
+        }
         self.scene.rotate_delta_x(&-DEFAULT_DELTA);
-        self.get_frame()
+        self.get_frame_with_detail(Detail::Reduced)
     }
 
-    pub fn down_key(&mut self) -> String {
+    pub fn down_key(&mut self) -> Frame {
+        if self.cursor.is_some() {
+            return self.move_cursor(0, -1);
+        }
         self.scene.rotate_delta_x(&DEFAULT_DELTA);
-        self.get_frame()
+        self.get_frame_with_detail(Detail::Reduced)
     }
 
-    pub fn left_key(&mut self) -> String {
+    pub fn left_key(&mut self) -> Frame {
+        if self.cursor.is_some() {
+            return self.move_cursor(-1, 0);
+        }
         self.scene.rotate_delta_y(&-DEFAULT_DELTA);
-        self.get_frame()
+        self.get_frame_with_detail(Detail::Reduced)
     }
 
-    pub fn right_key(&mut self) -> String {
+    pub fn right_key(&mut self) -> Frame {
+        if self.cursor.is_some() {
+            return self.move_cursor(1, 0);
+        }
         self.scene.rotate_delta_y(&DEFAULT_DELTA);
-        self.get_frame()
+        self.get_frame_with_detail(Detail::Reduced)
+    }
+
+    pub fn cull_key(&mut self) -> Frame {
+        let cull_mode = self.scene.get_cull_mode().next();
+        self.scene.set_cull_mode(cull_mode);
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn shadow_key(&mut self) -> Frame {
+        let mut shadows = self.scene.get_shadows();
+        shadows.enabled = !shadows.enabled;
+        self.scene.set_shadows(shadows);
+        self.get_frame_with_detail(Detail::Full)
     }
+
+    /// Cycles the clipping plane between off and the X, Y and Z axes of the object.
+    pub fn clipping_key(&mut self) -> Frame {
+        let clipping = match self.scene.get_clipping() {
+            None => Some(ClippingPlane::new(Axis::X)),
+            Some(clipping) => match clipping.axis {
+                Axis::X => Some(ClippingPlane {
+                    axis: Axis::Y,
+                    ..clipping
+                }),
+                Axis::Y => Some(ClippingPlane {
+                    axis: Axis::Z,
+                    ..clipping
+                }),
+                Axis::Z => None,
+            },
+        };
+        self.scene.set_clipping(clipping);
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn clipping_forward_key(&mut self) -> Frame {
+        self.move_clipping(CLIPPING_STEP)
+    }
+
+    pub fn clipping_backward_key(&mut self) -> Frame {
+        self.move_clipping(-CLIPPING_STEP)
+    }
+
+    fn move_clipping(&mut self, step: f32) -> Frame {
+        let radius = self.scene.get_maximum_radius();
+        if let Some(mut clipping) = self.scene.get_clipping() {
+            clipping.offset = (clipping.offset + step * radius).clamp(-radius, radius);
+            self.scene.set_clipping(Some(clipping));
+        }
+        self.get_frame_with_detail(Detail::Reduced)
+    }
+
+    /// Keeps the other side of the clipping plane.
+    pub fn flip_clipping_key(&mut self) -> Frame {
+        if let Some(mut clipping) = self.scene.get_clipping() {
+            clipping.flipped = !clipping.flipped;
+            self.scene.set_clipping(Some(clipping));
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn cap_key(&mut self) -> Frame {
+        if let Some(mut clipping) = self.scene.get_clipping() {
+            clipping.cap = !clipping.cap;
+            self.scene.set_clipping(Some(clipping));
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    fn get_layer_frame(&self, layers: &[Layer]) -> Frame {
+        let mut frame = match layers.get(self.layer) {
+            Some(layer) => {
+                self.matrix.set_layer(layer);
+                self.draw_frame()
+            }
+            None => {
+                self.matrix.clear();
+                self.draw_frame()
+            }
+        };
+
+        let height = (self.layer as f32 + 0.5) * self.layer_height;
+        let status = format!(
+            "Layer {}/{} at {height:.3} from the bottom",
+            (self.layer + 1).min(layers.len()),
+            layers.len()
+        );
+        push_lines(&mut frame, &[status]);
+        frame
+    }
+
+    /// Shows or hides the object number `index`, counting from zero.
+    pub fn object_key(&mut self, index: usize) -> Frame {
+        if let Some(object) = self.scene.get_objects_mut().get_mut(index) {
+            object.set_visible(!object.is_visible());
+        }
+        self.update_layers();
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    fn update_layers(&mut self) {
+        if self.layers.is_some() {
+            self.layers = Some(self.scene.get_layers(self.layer_height));
+        }
+    }
+
+    /// Every mesh group of the scene, as the index of its object and its index in the mesh.
+    fn get_groups(&self) -> Vec<(usize, usize)> {
+        self.scene
+            .get_objects()
+            .iter()
+            .enumerate()
+            .flat_map(|(index, object)| {
+                (0..object.get_mesh().groups.len()).map(move |group| (index, group))
+            })
+            .collect()
+    }
+
+    fn get_group_status(&self) -> Option<String> {
+        let (object_index, group_index) = self.group?;
+        let groups = self.get_groups();
+        let position = groups
+            .iter()
+            .position(|group| *group == (object_index, group_index))?;
+        let object = &self.scene.get_objects()[object_index];
+        let group = &object.get_mesh().groups[group_index];
+
+        Some(format!(
+            "Group {}/{}: {}{}",
+            position + 1,
+            groups.len(),
+            group.name,
+            if object.is_group_visible(group_index) {
+                ""
+            } else {
+                " (hidden)"
+            }
+        ))
+    }
+
+    /// Selects the next mesh group, going back to none after the last one.
+    pub fn group_key(&mut self) -> Frame {
+        let groups = self.get_groups();
+        self.group = match self.group {
+            None => groups.first().copied(),
+            Some(selected) => groups
+                .iter()
+                .skip_while(|group| **group != selected)
+                .nth(1)
+                .copied(),
+        };
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Shows or hides the selected group.
+    pub fn hide_group_key(&mut self) -> Frame {
+        if let Some((object_index, group_index)) = self.group {
+            let object = &mut self.scene.get_objects_mut()[object_index];
+            object.set_group_visible(group_index, !object.is_group_visible(group_index));
+        }
+        self.update_layers();
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Hides every group but the selected one, or shows them all again if it
+    /// was already isolated.
+    pub fn isolate_group_key(&mut self) -> Frame {
+        if let Some(selected) = self.group {
+            let groups = self.get_groups();
+            let objects = self.scene.get_objects_mut();
+            let isolated = groups.iter().all(|(object, group)| {
+                objects[*object].is_group_visible(*group) == ((*object, *group) == selected)
+            });
+
+            for (object, group) in groups {
+                let visible = isolated || (object, group) == selected;
+                objects[object].set_group_visible(group, visible);
+            }
+        }
+        self.update_layers();
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Switches between the shaded object and the layer view.
+    pub fn layer_key(&mut self) -> Frame {
+        self.layers = match self.layers {
+            Some(_) => None,
+            None => {
+                let layers = self.scene.get_layers(self.layer_height);
+                self.layer = self.layer.min(layers.len().saturating_sub(1));
+                Some(layers)
+            }
+        };
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn next_layer_key(&mut self) -> Frame {
+        if let Some(layers) = &self.layers {
+            self.layer = (self.layer + 1).min(layers.len().saturating_sub(1));
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn previous_layer_key(&mut self) -> Frame {
+        self.layer = self.layer.saturating_sub(1);
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Shows or hides the picking cursor. While it is shown the arrow keys move
+    /// it instead of rotating the scene.
+    pub fn cursor_key(&mut self) -> Frame {
+        self.cursor = match self.cursor {
+            Some(_) => None,
+            None => {
+                self.scene.build_bvhs();
+                Some((0, 0))
+            }
+        };
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Puts the picking cursor on the cell at a position of the printed frame,
+    /// counted from zero, and measures the point there in measurement mode.
+    pub fn click(&mut self, column: usize, line: usize) -> Frame {
+        let cols = self.matrix.get_cols();
+        let cell = self
+            .target
+            .get_cell_at(column, line, cols, cols)
+            .map(|(col, row)| {
+                // The first row of the frame is the top one.
+                let bound = (cols as isize - 1) / 2;
+                (col as isize - bound, bound - row as isize)
+            });
+        if let Some(cell) = cell {
+            self.scene.build_bvhs();
+            self.cursor = Some(cell);
+            if self.measuring {
+                return self.measure_point_key();
+            }
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Switches the measurement mode on, with the picking cursor, or off,
+    /// forgetting the measured points and face.
+    pub fn measure_key(&mut self) -> Frame {
+        self.measuring = !self.measuring;
+        if self.measuring {
+            self.scene.build_bvhs();
+            self.cursor.get_or_insert((0, 0));
+        } else {
+            self.measured_points.clear();
+            self.measured_face = None;
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Measures the point under the picking cursor. After three points, the
+    /// next one starts a new measurement.
+    pub fn measure_point_key(&mut self) -> Frame {
+        if self.measuring {
+            if let Some(pick) = self.pick_at_cursor() {
+                if self.measured_points.len() == 3 {
+                    self.measured_points.clear();
+                }
+                let point = self.scene.to_file_coordinates(pick.object, &pick.point);
+                self.measured_points.push((pick.object, point));
+            }
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Measures the flat face under the picking cursor.
+    pub fn measure_face_key(&mut self) -> Frame {
+        if let Some(pick) = self.pick_at_cursor() {
+            let mesh = self.scene.get_objects()[pick.object].get_mesh();
+            self.measured_face = Some((pick.object, mesh.get_face(pick.triangle)));
+        }
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    pub fn clear_measure_key(&mut self) -> Frame {
+        self.measured_points.clear();
+        self.measured_face = None;
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Shows or hides the bounding box of every object, in the axes of its file.
+    pub fn box_key(&mut self) -> Frame {
+        self.boxes = match self.boxes {
+            Some(_) => None,
+            None => Some(
+                (0..self.scene.get_objects().len())
+                    .map(|object| self.scene.get_file_bounds(object))
+                    .collect(),
+            ),
+        };
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    fn pick_at_cursor(&self) -> Option<Pick> {
+        let (col, row) = self.cursor?;
+        self.scene
+            .pick(&self.matrix.indexes_to_coordinates(col, row))
+    }
+
+    fn move_cursor(&mut self, step_col: isize, step_row: isize) -> Frame {
+        let range = self.matrix.get_cell_range();
+        let clamp = |index: isize| index.clamp(*range.start(), *range.end());
+        self.cursor = self
+            .cursor
+            .map(|(col, row)| (clamp(col + step_col), clamp(row + step_row)));
+        self.get_frame_with_detail(Detail::Full)
+    }
+
+    /// Called when no key has been pressed for a while. Returns the full detail
+    /// frame if the last one was drawn with the reduced mesh.
+    pub fn idle(&mut self) -> Option<Frame> {
+        match self.scene.get_detail() {
+            Detail::Reduced => Some(self.get_frame_with_detail(Detail::Full)),
+            Detail::Full => None,
+        }
+    }
+}
+
+// Writes `lines` under the rows of `frame`.
+fn push_lines(frame: &mut Frame, lines: &[String]) {
+    for line in lines {
+        frame.write_text(0, frame.rows, line);
+    }
+}
+
+fn format_vector(vector: &Vector3D) -> String {
+    format!("({:.3}, {:.3}, {:.3})", vector[0], vector[1], vector[2])
 }
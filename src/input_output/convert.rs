@@ -0,0 +1,71 @@
+use super::{get_output_format, read_mesh, write_layers_svg, write_mesh};
+use crate::{DecimationTarget, Vector3D};
+
+/// Everything `convert` can do to a mesh between reading and writing it.
+/// Steps are applied in field order.
+#[derive(Debug, Clone, Default)]
+pub struct Conversion {
+    pub input: String,
+    pub output: String,
+    /// Write the text variant of STL or PLY.
+    pub ascii: bool,
+    /// Move the vertex centroid to the origin.
+    pub center: bool,
+    /// Rotation around the X, Y and Z axes, in radians.
+    pub rotation: (f32, f32, f32),
+    pub scale: Option<f32>,
+    pub translation: Option<Vector3D>,
+    /// Merge vertices closer than this distance.
+    pub weld: Option<f32>,
+    pub repair: bool,
+    /// Maximum number of triangles to keep.
+    pub decimate: Option<usize>,
+}
+
+pub fn convert(conversion: &Conversion) -> Result<(), String> {
+    let format = get_output_format(conversion.output.clone(), conversion.ascii)?;
+    let mut mesh = read_mesh(conversion.input.clone())?;
+
+    if conversion.center {
+        mesh.move_to_center();
+    }
+
+    let (x, y, z) = conversion.rotation;
+    mesh.rotate((&x, &y, &z));
+
+    if let Some(scale) = conversion.scale {
+        mesh.scale(scale);
+    }
+
+    if let Some(translation) = conversion.translation {
+        mesh.translate(translation);
+    }
+
+    if let Some(tolerance) = conversion.weld {
+        mesh = mesh.weld(tolerance);
+    }
+
+    if conversion.repair {
+        mesh = mesh.repair();
+    }
+
+    if let Some(triangles) = conversion.decimate {
+        mesh = mesh.decimate(DecimationTarget::Triangles(triangles));
+    }
+
+    write_mesh(conversion.output.clone(), &mesh, format)
+}
+
+/// Slices a 3D file along Z and writes one SVG per layer into `directory`.
+/// Returns the number of layers written.
+pub fn slice_to_svg(input: String, directory: String, layer_height: f32) -> Result<usize, String> {
+    if layer_height <= 0.0 {
+        return Err(format!("Error: Invalid Layer Height: {layer_height}"));
+    }
+
+    let mesh = read_mesh(input)?;
+    let layers = mesh.slice(layer_height);
+    write_layers_svg(directory, &layers)?;
+
+    Ok(layers.len())
+}
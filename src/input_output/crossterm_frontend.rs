@@ -0,0 +1,154 @@
+use super::frontend::{terminal_error, Frontend, Input};
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::terminal::{
+    self, BeginSynchronizedUpdate, EndSynchronizedUpdate, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::io::{stdout, Stdout, Write};
+use std::time::{Duration, Instant};
+
+/// Frontend on crossterm.
+pub struct CrosstermFrontend {
+    stdout: Stdout,
+    raw_mode: bool,
+}
+
+impl Default for CrosstermFrontend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrosstermFrontend {
+    pub fn new() -> Self {
+        Self {
+            stdout: stdout(),
+            raw_mode: false,
+        }
+    }
+}
+
+impl Frontend for CrosstermFrontend {
+    fn get_size(&self) -> Result<(usize, usize), String> {
+        let (columns, lines) = terminal::size().map_err(terminal_error)?;
+        Ok((columns as usize, lines as usize))
+    }
+
+    fn set_raw_mode(&mut self, enabled: bool) -> Result<(), String> {
+        match (enabled, self.raw_mode) {
+            (true, false) => {
+                terminal::enable_raw_mode().map_err(terminal_error)?;
+                execute!(self.stdout, EnableMouseCapture, EnableBracketedPaste)
+                    .map_err(terminal_error)?;
+            }
+            (false, true) => {
+                execute!(self.stdout, DisableBracketedPaste, DisableMouseCapture)
+                    .map_err(terminal_error)?;
+                terminal::disable_raw_mode().map_err(terminal_error)?;
+            }
+            _ => return Ok(()),
+        }
+        self.raw_mode = enabled;
+        Ok(())
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) -> Result<(), String> {
+        match enabled {
+            true => queue!(self.stdout, EnterAlternateScreen),
+            false => queue!(self.stdout, LeaveAlternateScreen),
+        }
+        .map_err(terminal_error)
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) -> Result<(), String> {
+        match visible {
+            true => queue!(self.stdout, Show),
+            false => queue!(self.stdout, Hide),
+        }
+        .map_err(terminal_error)
+    }
+
+    fn set_synchronized_update(&mut self, enabled: bool) -> Result<(), String> {
+        match enabled {
+            true => queue!(self.stdout, BeginSynchronizedUpdate),
+            false => queue!(self.stdout, EndSynchronizedUpdate),
+        }
+        .map_err(terminal_error)
+    }
+
+    fn write(&mut self, text: &str) -> Result<(), String> {
+        self.stdout
+            .write_all(text.as_bytes())
+            .map_err(terminal_error)
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.stdout.flush().map_err(terminal_error)
+    }
+
+    fn read_input(&mut self, timeout: Duration) -> Result<Option<Input>, String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !event::poll(remaining).map_err(terminal_error)? {
+                return Ok(None);
+            }
+            // Events without an input, such as key releases, are skipped while
+            // there is time left.
+            if let Some(input) = get_input(event::read().map_err(terminal_error)?) {
+                return Ok(Some(input));
+            }
+        }
+    }
+
+    fn get_panic_restore(&self) -> fn() {
+        restore
+    }
+}
+
+fn restore() {
+    let _ = execute!(
+        stdout(),
+        EndSynchronizedUpdate,
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        Show,
+        LeaveAlternateScreen
+    );
+    let _ = terminal::disable_raw_mode();
+}
+
+fn get_input(event: Event) -> Option<Input> {
+    match event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press | KeyEventKind::Repeat,
+            ..
+        }) => match code {
+            KeyCode::Char(_) if modifiers.contains(KeyModifiers::CONTROL) => None,
+            KeyCode::Char(char) => Some(Input::Char(char)),
+            KeyCode::Enter => Some(Input::Char('\n')),
+            KeyCode::Up => Some(Input::Up),
+            KeyCode::Down => Some(Input::Down),
+            KeyCode::Left => Some(Input::Left),
+            KeyCode::Right => Some(Input::Right),
+            KeyCode::Backspace => Some(Input::Backspace),
+            _ => None,
+        },
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) => Some(Input::Click(column as usize, row as usize)),
+        Event::Paste(text) => Some(Input::Paste(text)),
+        Event::Resize(columns, lines) => Some(Input::Resize(columns as usize, lines as usize)),
+        _ => None,
+    }
+}
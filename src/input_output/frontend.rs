@@ -0,0 +1,147 @@
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, PanicHookInfo};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Key press, click, paste or resize read from the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// A character key. Enter is '\n'.
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Backspace,
+    /// Press of the left button on a column and line of the terminal, counted
+    /// from zero.
+    Click(usize, usize),
+    /// Text pasted while bracketed paste is on, in one piece so that it is not
+    /// taken for key presses.
+    Paste(String),
+    /// The terminal is now this many columns and lines.
+    Resize(usize, usize),
+}
+
+/// Terminal the viewer runs in, behind the library that drives it.
+pub trait Frontend {
+    /// Columns and lines of the terminal.
+    fn get_size(&self) -> Result<(usize, usize), String>;
+
+    /// Raw mode, with mouse reporting and bracketed paste, or the mode the
+    /// terminal was in before.
+    fn set_raw_mode(&mut self, enabled: bool) -> Result<(), String>;
+
+    /// Screen buffer without scrollback, which the main one is restored from
+    /// when leaving it.
+    fn set_alternate_screen(&mut self, enabled: bool) -> Result<(), String>;
+
+    fn set_cursor_visible(&mut self, visible: bool) -> Result<(), String>;
+
+    /// Starts or ends a synchronized update (DEC mode 2026), during which the
+    /// terminal holds what is written to show it at once. Terminals without
+    /// it ignore the sequences.
+    fn set_synchronized_update(&mut self, enabled: bool) -> Result<(), String>;
+
+    fn write(&mut self, text: &str) -> Result<(), String>;
+
+    fn flush(&mut self) -> Result<(), String>;
+
+    /// Next input, or None if there is none within `timeout`. An error once
+    /// the input is closed.
+    fn read_input(&mut self, timeout: Duration) -> Result<Option<Input>, String>;
+
+    /// Function putting the terminal back as it was as far as it can without
+    /// the frontend, for panic hooks, which cannot reach it. Does nothing by
+    /// default.
+    fn get_panic_restore(&self) -> fn() {
+        || {}
+    }
+}
+
+type PanicHook = dyn Fn(&PanicHookInfo) + Sync + Send;
+
+/// Terminal in raw mode on the alternate screen with the cursor hidden, as
+/// long as the guard lives. Dropping it restores the terminal, also when
+/// unwinding from a panic, and meanwhile a panic hook restores what it can
+/// before the panic is reported, so that the message is not lost with the
+/// alternate screen. The hook found when the guard was made is put back when it
+/// is dropped, unless another one was set in the meantime.
+pub struct TerminalGuard<'a> {
+    frontend: &'a mut dyn Frontend,
+    previous_hook: Arc<PanicHook>,
+    /// Address of the hook set by the guard, to tell whether it is still set.
+    hook: usize,
+}
+
+impl<'a> TerminalGuard<'a> {
+    pub fn new(frontend: &'a mut dyn Frontend) -> Result<TerminalGuard<'a>, String> {
+        let previous_hook: Arc<PanicHook> = Arc::from(panic::take_hook());
+        let hook = previous_hook.clone();
+        let restore = frontend.get_panic_restore();
+        let hook: Box<PanicHook> = Box::new(move |info| {
+            restore();
+            hook(info);
+        });
+        let address = get_address(&hook);
+        panic::set_hook(hook);
+
+        let guard = TerminalGuard {
+            frontend,
+            previous_hook,
+            hook: address,
+        };
+        guard.frontend.set_raw_mode(true)?;
+        guard.frontend.set_alternate_screen(true)?;
+        guard.frontend.set_cursor_visible(false)?;
+        guard.frontend.flush()?;
+        Ok(guard)
+    }
+}
+
+impl<'a> Deref for TerminalGuard<'a> {
+    type Target = dyn Frontend + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        self.frontend
+    }
+}
+
+impl DerefMut for TerminalGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.frontend
+    }
+}
+
+impl Drop for TerminalGuard<'_> {
+    fn drop(&mut self) {
+        // Errors cannot be reported from here, and each step is worth trying.
+        let _ = self.frontend.set_synchronized_update(false);
+        let _ = self.frontend.set_cursor_visible(true);
+        let _ = self.frontend.set_alternate_screen(false);
+        let _ = self.frontend.set_raw_mode(false);
+        let _ = self.frontend.flush();
+
+        // The hook cannot be changed while panicking.
+        if !thread::panicking() {
+            let hook = panic::take_hook();
+            if get_address(&hook) == self.hook {
+                let previous_hook = self.previous_hook.clone();
+                panic::set_hook(Box::new(move |info| previous_hook(info)));
+            } else {
+                panic::set_hook(hook);
+            }
+        }
+    }
+}
+
+// Where a boxed hook lives, which moving the box does not change.
+fn get_address(hook: &PanicHook) -> usize {
+    hook as *const PanicHook as *const () as usize
+}
+
+#[cfg(any(feature = "termion-frontend", feature = "crossterm-frontend"))]
+pub(super) fn terminal_error(error: std::io::Error) -> String {
+    format!("Error: {error} Using The Terminal")
+}
@@ -1,8 +1,39 @@
 mod console;
 pub use console::*;
+#[cfg(feature = "crossterm-frontend")]
+mod crossterm_frontend;
+#[cfg(feature = "crossterm-frontend")]
+pub use crossterm_frontend::*;
+mod frontend;
+pub use frontend::*;
+#[cfg(feature = "termion-frontend")]
+mod termion_frontend;
+#[cfg(feature = "termion-frontend")]
+pub use termion_frontend::*;
+mod read_config;
 mod read_mesh;
+mod read_mtl;
+mod read_ply;
+mod read_texture;
+pub use read_config::*;
 pub use read_mesh::*;
+pub use read_mtl::*;
+pub use read_texture::*;
+mod write_mesh;
+pub use write_mesh::*;
+mod write_svg;
+pub use write_svg::*;
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "cli")]
 pub use cli::*;
 pub mod controller;
 pub use controller::*;
+pub mod convert;
+pub use convert::*;
+pub mod render;
+pub use render::*;
+#[cfg(feature = "ratatui")]
+pub mod widget;
+#[cfg(feature = "ratatui")]
+pub use widget::*;
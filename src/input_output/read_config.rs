@@ -0,0 +1,74 @@
+use std::env::var_os;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use crate::Dithering;
+
+/// Defaults for the shading flags, read from a config file. The flags given on
+/// the command line take precedence.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// A preset name or the glyphs themselves, as taken by `--ramp`.
+    pub ramp: Option<String>,
+    pub dithering: Option<Dithering>,
+    pub gamma: Option<f32>,
+    pub contrast: Option<f32>,
+}
+
+/// Reads a config file of `key = value` lines, with `ramp`, `dither`, `gamma`
+/// and `contrast` as keys. Lines starting with `#` are comments, and a value in
+/// double quotes keeps the spaces at its ends, as in `ramp = " .oO@"`.
+pub fn read_config<R: Read>(read: R) -> Result<Config, String> {
+    let mut config = Config::default();
+
+    for line in BufReader::new(read).lines() {
+        let line = line.map_err(|error| format!("Error: {error} Reading Config"))?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("Error: Invalid Config Line: {line}");
+        let (key, value) = trimmed.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+
+        match key.trim() {
+            "ramp" if !value.is_empty() => config.ramp = Some(value.to_string()),
+            "dither" => config.dithering = Some(Dithering::from_name(value).ok_or_else(invalid)?),
+            "gamma" => config.gamma = Some(parse_gamma(value).map_err(|_| invalid())?),
+            "contrast" => config.contrast = Some(parse_contrast(value).map_err(|_| invalid())?),
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(config)
+}
+
+/// Gamma written in `text`, which has to be a number above 0.
+pub fn parse_gamma(text: &str) -> Result<f32, String> {
+    match text.trim().parse::<f32>() {
+        Ok(gamma) if gamma.is_finite() && gamma > 0.0 => Ok(gamma),
+        _ => Err(format!("Error: Invalid Gamma {text}")),
+    }
+}
+
+/// Contrast written in `text`, which has to be a number from 0 on.
+pub fn parse_contrast(text: &str) -> Result<f32, String> {
+    match text.trim().parse::<f32>() {
+        Ok(contrast) if contrast.is_finite() && contrast >= 0.0 => Ok(contrast),
+        _ => Err(format!("Error: Invalid Contrast {text}")),
+    }
+}
+
+/// Where the config file is looked for when none is given:
+/// `$XDG_CONFIG_HOME/sx3d/config`, or `~/.config/sx3d/config`.
+pub fn get_config_path() -> Option<PathBuf> {
+    let directory = match var_os("XDG_CONFIG_HOME") {
+        Some(directory) if !directory.is_empty() => PathBuf::from(directory),
+        _ => PathBuf::from(var_os("HOME")?).join(".config"),
+    };
+    Some(directory.join("sx3d").join("config"))
+}
@@ -1,43 +1,104 @@
 use std::fs::{File, OpenOptions};
+#[cfg(any(feature = "stl", feature = "obj"))]
 use std::io::Read;
+#[cfg(feature = "stl")]
+use std::io::{Cursor, Seek};
+#[cfg(feature = "obj")]
+use std::{collections::HashMap, path::Path, sync::Arc};
+#[cfg(feature = "stl")]
 use stl_io::{read_stl, IndexedMesh};
+#[cfg(feature = "obj")]
 use wavefront_obj::obj::parse;
 
-use crate::{CalculateNormal, IndexedMesh3D, IndexedTriangle3D, Vector3D};
+#[cfg(feature = "obj")]
+use super::read_mtl::read_mtl;
+use super::read_ply::read_ply;
+#[cfg(feature = "obj")]
+use super::read_texture::read_texture;
+#[cfg(feature = "stl")]
+use crate::Color;
+use crate::IndexedMesh3D;
+#[cfg(any(feature = "stl", feature = "obj"))]
+use crate::{CalculateNormal, IndexedTriangle3D, Vector3D};
+#[cfg(feature = "obj")]
+use crate::{Material, MeshGroup, Vector2D};
 
 pub enum FileType {
     Stl,
     Obj,
+    Ply,
 }
 
 pub fn get_file_type(path: String) -> Result<FileType, String> {
-    let extension = path.split('.').last().unwrap();
+    let extension = path.split('.').next_back().unwrap();
     match extension {
         "stl" => Ok(FileType::Stl),
         "obj" => Ok(FileType::Obj),
+        "ply" => Ok(FileType::Ply),
         _ => Err(format!("Error: Unsupported File Type: {extension}")),
     }
 }
 
 pub fn read_mesh(path: String) -> Result<IndexedMesh3D, String> {
     let file_type = get_file_type(path.clone())?;
-    let mut file = read_file(path)?;
+    let file = read_file(path.clone())?;
 
     match file_type {
-        FileType::Stl => {
-            let stl_mesh = read_stl_mesh_from_file(&mut file)?;
-            let mesh = convert_stl_to_indexed_mesh_3d(stl_mesh);
-            Ok(mesh)
-        }
-        FileType::Obj => {
-            let mut obj_data = String::new();
-            file.read_to_string(&mut obj_data).unwrap();
-            let obj_set = parse(obj_data).unwrap();
-            let mesh = convert_obj_set_to_indexed_mesh_3d(obj_set);
+        FileType::Stl => read_stl_file(file, &path),
+        FileType::Obj => read_obj_file(file, &path),
+        FileType::Ply => read_ply(file),
+    }
+}
 
-            Ok(mesh)
+#[cfg(feature = "stl")]
+fn read_stl_file(mut file: File, path: &str) -> Result<IndexedMesh3D, String> {
+    let mut stl_data = Vec::new();
+    file.read_to_end(&mut stl_data)
+        .map_err(|error| format!("Error: {error} Reading File: {path}"))?;
+    let stl_mesh = read_stl_mesh_from_file(&mut Cursor::new(&stl_data))?;
+    let mut mesh = convert_stl_to_indexed_mesh_3d(stl_mesh);
+
+    let colors = read_stl_facet_colors(&stl_data);
+    if colors.len() == mesh.triangles.len() {
+        for (triangle, color) in mesh.triangles.iter_mut().zip(colors) {
+            triangle.color = color;
         }
     }
+    Ok(mesh)
+}
+
+#[cfg(not(feature = "stl"))]
+fn read_stl_file(_file: File, path: &str) -> Result<IndexedMesh3D, String> {
+    Err(format!(
+        "Error: Reading STL Files Needs The stl Feature: {path}"
+    ))
+}
+
+#[cfg(feature = "obj")]
+fn read_obj_file(mut file: File, path: &str) -> Result<IndexedMesh3D, String> {
+    let mut obj_data = String::new();
+    file.read_to_string(&mut obj_data)
+        .map_err(|error| format!("Error: {error} Reading File: {path}"))?;
+    let obj_set = parse(obj_data).map_err(|error| {
+        format!(
+            "Error: {} Reading OBJ Line {}",
+            error.message, error.line_number
+        )
+    })?;
+    let materials = match &obj_set.material_library {
+        Some(library) => read_material_library(path, library)?,
+        None => Vec::new(),
+    };
+    let mesh = convert_obj_set_to_indexed_mesh_3d(obj_set, &materials);
+
+    Ok(mesh)
+}
+
+#[cfg(not(feature = "obj"))]
+fn read_obj_file(_file: File, path: &str) -> Result<IndexedMesh3D, String> {
+    Err(format!(
+        "Error: Reading OBJ Files Needs The obj Feature: {path}"
+    ))
 }
 
 fn read_file(path: String) -> Result<File, String> {
@@ -48,7 +109,8 @@ fn read_file(path: String) -> Result<File, String> {
     }
 }
 
-fn read_stl_mesh_from_file(file: &mut File) -> Result<IndexedMesh, String> {
+#[cfg(feature = "stl")]
+fn read_stl_mesh_from_file<R: Read + Seek>(file: &mut R) -> Result<IndexedMesh, String> {
     let mesh_result = read_stl(file);
     match mesh_result {
         Ok(mesh) => Ok(mesh),
@@ -56,6 +118,51 @@ fn read_stl_mesh_from_file(file: &mut File) -> Result<IndexedMesh, String> {
     }
 }
 
+#[cfg(feature = "stl")]
+/// Facet colors of a binary STL file, in the 15 bits of the attribute bytes, or
+/// nothing for ASCII files.
+///
+/// Materialise files have `COLOR=` in the header, followed by the default color
+/// as RGBA bytes, and give a facet its own color, red in the lowest bits, when
+/// bit 15 is clear. VisCAM and SolidView files have blue in the lowest bits
+/// and set bit 15 on the facets that have a color.
+fn read_stl_facet_colors(data: &[u8]) -> Vec<Option<Color>> {
+    if data.len() < 84 {
+        return Vec::new();
+    }
+    let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as usize;
+    if data.len() != 84 + 50 * count {
+        return Vec::new();
+    }
+
+    let header = &data[..80];
+    let default_color = header
+        .windows(6)
+        .position(|window| window == b"COLOR=")
+        .map(|position| header.get(position + 6..position + 9));
+
+    data[84..]
+        .chunks_exact(50)
+        .map(|facet| {
+            let attribute = u16::from_le_bytes([facet[48], facet[49]]);
+            let channel = |shift: u16| {
+                let value = ((attribute >> shift) & 0x1f) as u8;
+                (value << 3) | (value >> 2)
+            };
+            let own_color = attribute & 0x8000 == 0;
+
+            match default_color {
+                Some(_) if own_color => Some(Color::new(channel(0), channel(5), channel(10))),
+                Some(Some(rgb)) => Some(Color::new(rgb[0], rgb[1], rgb[2])),
+                Some(None) => None,
+                None if own_color => None,
+                None => Some(Color::new(channel(10), channel(5), channel(0))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "stl")]
 fn convert_stl_to_indexed_mesh_3d(stl_mesh: stl_io::IndexedMesh) -> IndexedMesh3D {
     let vertices: Vec<Vector3D> = stl_mesh
         .vertices
@@ -73,37 +180,63 @@ fn convert_stl_to_indexed_mesh_3d(stl_mesh: stl_io::IndexedMesh) -> IndexedMesh3
                 vertices[f.vertices[2]],
             ];
             let normal = vertices.calculate_normal();
-            IndexedTriangle3D {
-                normal,
-                vertices_indices: [f.vertices[0], f.vertices[1], f.vertices[2]],
-            }
+            IndexedTriangle3D::new(normal, [f.vertices[0], f.vertices[1], f.vertices[2]])
         })
         .collect();
 
-    IndexedMesh3D {
-        vertices,
-        triangles,
-    }
+    IndexedMesh3D::new(vertices, triangles)
 }
 
-fn convert_obj_set_to_indexed_mesh_3d(obj_set: wavefront_obj::obj::ObjSet) -> IndexedMesh3D {
-    let mut mesh: IndexedMesh3D = IndexedMesh3D {
-        vertices: Vec::new(),
-        triangles: Vec::new(),
+#[cfg(feature = "obj")]
+/// Material library named by an OBJ file, and the textures it names, looked up
+/// next to it. Missing files are not an error, since OBJ files are often shared
+/// without theirs, and neither are textures in formats that cannot be read,
+/// which leave the diffuse color in their place.
+fn read_material_library(obj_path: &str, library: &str) -> Result<Vec<Material>, String> {
+    let path = Path::new(obj_path).with_file_name(library);
+    let mut materials = match File::open(&path) {
+        Ok(file) => read_mtl(file)?,
+        Err(_) => return Ok(Vec::new()),
     };
 
+    for material in materials.iter_mut() {
+        let texture_path = match &material.diffuse_map {
+            Some(diffuse_map) => path.with_file_name(diffuse_map),
+            None => continue,
+        };
+        if let Ok(texture) = read_texture(texture_path.to_string_lossy().to_string()) {
+            material.texture = Some(Arc::new(texture));
+        }
+    }
+
+    Ok(materials)
+}
+
+#[cfg(feature = "obj")]
+fn convert_obj_set_to_indexed_mesh_3d(
+    obj_set: wavefront_obj::obj::ObjSet,
+    materials: &[Material],
+) -> IndexedMesh3D {
+    let mut mesh = IndexedMesh3D::new(Vec::new(), Vec::new());
+    let mut groups: HashMap<(String, Option<String>), usize> = HashMap::new();
+
     for object in obj_set.objects {
         if !object.geometry.is_empty() {
-            convert_obj_to_indexed_mesh_3d(object, &mut mesh);
+            convert_obj_to_indexed_mesh_3d(object, materials, &mut groups, &mut mesh);
         }
-
-        continue;
     }
 
     mesh
 }
 
-fn convert_obj_to_indexed_mesh_3d(obj: wavefront_obj::obj::Object, mesh: &mut IndexedMesh3D) {
+#[cfg(feature = "obj")]
+/// Adds the triangles of `obj` to `mesh`, in one group per OBJ group and material.
+fn convert_obj_to_indexed_mesh_3d(
+    obj: wavefront_obj::obj::Object,
+    materials: &[Material],
+    groups: &mut HashMap<(String, Option<String>), usize>,
+    mesh: &mut IndexedMesh3D,
+) {
     let last_index = mesh.vertices.len();
 
     let new_vertices: Vec<Vector3D> = obj
@@ -111,15 +244,40 @@ fn convert_obj_to_indexed_mesh_3d(obj: wavefront_obj::obj::Object, mesh: &mut In
         .iter()
         .map(|v| Vector3D::new(v.x as f32, v.y as f32, v.z as f32))
         .collect();
+    let uvs: Vec<Vector2D> = obj
+        .tex_vertices
+        .iter()
+        .map(|uv| Vector2D::new(uv.u as f32, uv.v as f32))
+        .collect();
 
     for geometry in obj.geometry {
         for shape in geometry.shapes {
             if let wavefront_obj::obj::Primitive::Triangle(a, b, c) = shape.primitive {
+                let name = get_group_name(&obj.name, &shape.groups);
+                let key = (name.clone(), geometry.material_name.clone());
+                let group = *groups.entry(key).or_insert_with(|| {
+                    mesh.groups.push(MeshGroup {
+                        name,
+                        material: geometry.material_name.as_ref().and_then(|material_name| {
+                            materials
+                                .iter()
+                                .find(|material| material.name == *material_name)
+                                .cloned()
+                        }),
+                    });
+                    mesh.groups.len() - 1
+                });
+
                 let vertices = [new_vertices[a.0], new_vertices[b.0], new_vertices[c.0]];
                 let normal = vertices.calculate_normal();
-                let triangle = IndexedTriangle3D {
+                let mut triangle = IndexedTriangle3D::new(
                     normal,
-                    vertices_indices: [a.0 + last_index, b.0 + last_index, c.0 + last_index],
+                    [a.0 + last_index, b.0 + last_index, c.0 + last_index],
+                );
+                triangle.group = Some(group);
+                triangle.uvs = match (a.1, b.1, c.1) {
+                    (Some(a), Some(b), Some(c)) => Some([uvs[a], uvs[b], uvs[c]]),
+                    _ => None,
                 };
                 mesh.triangles.push(triangle);
             }
@@ -129,7 +287,17 @@ fn convert_obj_to_indexed_mesh_3d(obj: wavefront_obj::obj::Object, mesh: &mut In
     mesh.vertices.extend(new_vertices);
 }
 
-#[cfg(test)]
+#[cfg(feature = "obj")]
+fn get_group_name(object_name: &str, group_names: &[String]) -> String {
+    match (object_name.is_empty(), group_names.is_empty()) {
+        (true, true) => "default".to_string(),
+        (false, true) => object_name.to_string(),
+        (true, false) => group_names.join(" "),
+        (false, false) => format!("{object_name}/{}", group_names.join(" ")),
+    }
+}
+
+#[cfg(all(test, feature = "stl", feature = "obj"))]
 mod read_tests {
     use super::*;
 
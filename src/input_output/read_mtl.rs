@@ -0,0 +1,66 @@
+use std::io::{BufRead, BufReader, Read};
+
+use crate::{Color, Material};
+
+/// Reads the materials of an MTL file. Only the colors, `Ns`, `d` and the file
+/// name of `map_Kd` are kept; unknown statements are skipped, and so are values
+/// given in other forms, such as `spectral` and `xyz` colors. A color with one
+/// value is a gray.
+pub fn read_mtl<R: Read>(read: R) -> Result<Vec<Material>, String> {
+    let mut materials: Vec<Material> = Vec::new();
+
+    for line in BufReader::new(read).lines() {
+        let line = line.map_err(|error| format!("Error: {error} Reading MTL"))?;
+        let mut words = line.split_whitespace();
+        let keyword = match words.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let values = words
+            .map(|word| word.parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>();
+
+        if keyword == "newmtl" {
+            let name = line.trim_start()["newmtl".len()..].trim().to_string();
+            materials.push(Material::new(name));
+            continue;
+        }
+
+        // The options of a map come before its file name.
+        if keyword == "map_Kd" {
+            let material = materials
+                .last_mut()
+                .ok_or_else(|| format!("Error: MTL {keyword} Before newmtl"))?;
+            material.diffuse_map = line.split_whitespace().last().map(str::to_string);
+            continue;
+        }
+
+        let material = match (keyword, materials.last_mut()) {
+            ("Ka" | "Kd" | "Ks" | "Ns" | "d" | "Tr", Some(material)) => material,
+            ("Ka" | "Kd" | "Ks" | "Ns" | "d" | "Tr", None) => {
+                return Err(format!("Error: MTL {keyword} Before newmtl"))
+            }
+            _ => continue,
+        };
+        let Ok(values) = values else {
+            continue;
+        };
+
+        let color = match values.as_slice() {
+            [r, g, b] => Some(Color::from_unit(*r, *g, *b)),
+            [gray] => Some(Color::from_unit(*gray, *gray, *gray)),
+            _ => None,
+        };
+        match (keyword, values.as_slice(), color) {
+            ("Ka", _, Some(color)) => material.ambient = color,
+            ("Kd", _, Some(color)) => material.diffuse = color,
+            ("Ks", _, Some(color)) => material.specular = color,
+            ("Ns", [shininess], _) => material.shininess = *shininess,
+            ("d", [opacity], _) => material.opacity = *opacity,
+            ("Tr", [transparency], _) => material.opacity = 1.0 - *transparency,
+            _ => {}
+        }
+    }
+
+    Ok(materials)
+}
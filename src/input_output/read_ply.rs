@@ -0,0 +1,253 @@
+use std::io::{BufRead, BufReader, Read};
+
+use crate::{CalculateNormal, IndexedMesh3D, IndexedTriangle3D, Vector3D};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Clone, Copy)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Result<Scalar, String> {
+        match name {
+            "char" | "int8" => Ok(Scalar::I8),
+            "uchar" | "uint8" => Ok(Scalar::U8),
+            "short" | "int16" => Ok(Scalar::I16),
+            "ushort" | "uint16" => Ok(Scalar::U16),
+            "int" | "int32" => Ok(Scalar::I32),
+            "uint" | "uint32" => Ok(Scalar::U32),
+            "float" | "float32" => Ok(Scalar::F32),
+            "double" | "float64" => Ok(Scalar::F64),
+            _ => Err(format!("Error: Unsupported PLY Type: {name}")),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+}
+
+enum Property {
+    Scalar(String, Scalar),
+    List(String, Scalar, Scalar),
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// Reads ASCII and binary PLY files. Only vertex positions and faces are used;
+/// polygons are split into triangle fans.
+pub fn read_ply<R: Read>(read: R) -> Result<IndexedMesh3D, String> {
+    let mut reader = BufReader::new(read);
+    let (format, elements) = read_header(&mut reader)?;
+    let mut values = Values::new(reader, format);
+
+    let mut vertices = Vec::new();
+    let mut faces: Vec<Vec<usize>> = Vec::new();
+
+    for element in elements.iter() {
+        for _ in 0..element.count {
+            let mut position = [0.0f32; 3];
+            let mut face = Vec::new();
+
+            for property in element.properties.iter() {
+                match property {
+                    Property::Scalar(name, scalar) => {
+                        let value = values.next(*scalar)?;
+                        match name.as_str() {
+                            "x" => position[0] = value as f32,
+                            "y" => position[1] = value as f32,
+                            "z" => position[2] = value as f32,
+                            _ => {}
+                        }
+                    }
+                    Property::List(name, count_scalar, item_scalar) => {
+                        let count = values.next(*count_scalar)? as usize;
+                        let is_face = name == "vertex_indices" || name == "vertex_index";
+                        for _ in 0..count {
+                            let value = values.next(*item_scalar)?;
+                            if is_face {
+                                face.push(value as usize);
+                            }
+                        }
+                    }
+                }
+            }
+
+            match element.name.as_str() {
+                "vertex" => vertices.push(Vector3D::new(position[0], position[1], position[2])),
+                "face" => faces.push(face),
+                _ => {}
+            }
+        }
+    }
+
+    let mut triangles = Vec::with_capacity(faces.len());
+    for face in faces {
+        if face.iter().any(|index| *index >= vertices.len()) {
+            return Err("Error: PLY Face Index Out Of Range".to_string());
+        }
+
+        for index in 1..face.len().saturating_sub(1) {
+            let vertices_indices = [face[0], face[index], face[index + 1]];
+            let normal = vertices_indices
+                .map(|vertex| vertices[vertex])
+                .calculate_normal();
+            triangles.push(IndexedTriangle3D::new(normal, vertices_indices));
+        }
+    }
+
+    Ok(IndexedMesh3D::new(vertices, triangles))
+}
+
+fn read_header<R: BufRead>(reader: &mut R) -> Result<(Format, Vec<Element>), String> {
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    let mut line = String::new();
+    let mut first = true;
+
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => return Err("Error: PLY Header Without end_header".to_string()),
+            Ok(_) => {}
+            Err(error) => return Err(format!("Error: {error} Reading PLY Header")),
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if first {
+            if words != ["ply"] {
+                return Err("Error: Not A PLY File".to_string());
+            }
+            first = false;
+            continue;
+        }
+
+        match words.as_slice() {
+            ["format", "ascii", _] => format = Some(Format::Ascii),
+            ["format", "binary_little_endian", _] => format = Some(Format::BinaryLittleEndian),
+            ["format", "binary_big_endian", _] => format = Some(Format::BinaryBigEndian),
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| format!("Error: Invalid PLY Element Count: {count}"))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, item_type, name] => {
+                let property = Property::List(
+                    name.to_string(),
+                    Scalar::parse(count_type)?,
+                    Scalar::parse(item_type)?,
+                );
+                match elements.last_mut() {
+                    Some(element) => element.properties.push(property),
+                    None => return Err("Error: PLY Property Before Element".to_string()),
+                }
+            }
+            ["property", scalar_type, name] => {
+                let property = Property::Scalar(name.to_string(), Scalar::parse(scalar_type)?);
+                match elements.last_mut() {
+                    Some(element) => element.properties.push(property),
+                    None => return Err("Error: PLY Property Before Element".to_string()),
+                }
+            }
+            ["end_header"] => break,
+            _ => {}
+        }
+    }
+
+    match format {
+        Some(format) => Ok((format, elements)),
+        None => Err("Error: PLY Header Without Format".to_string()),
+    }
+}
+
+struct Values<R: BufRead> {
+    reader: R,
+    format: Format,
+    words: std::vec::IntoIter<String>,
+}
+
+impl<R: BufRead> Values<R> {
+    fn new(reader: R, format: Format) -> Values<R> {
+        Values {
+            reader,
+            format,
+            words: Vec::new().into_iter(),
+        }
+    }
+
+    fn next(&mut self, scalar: Scalar) -> Result<f64, String> {
+        if self.format == Format::Ascii {
+            return self.next_word();
+        }
+
+        let mut bytes = [0u8; 8];
+        let bytes = &mut bytes[..scalar.size()];
+        if let Err(error) = self.reader.read_exact(bytes) {
+            return Err(format!("Error: {error} Reading PLY Data"));
+        }
+        if self.format == Format::BinaryBigEndian {
+            bytes.reverse();
+        }
+
+        Ok(match scalar {
+            Scalar::I8 => bytes[0] as i8 as f64,
+            Scalar::U8 => bytes[0] as f64,
+            Scalar::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            Scalar::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            Scalar::I32 => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            Scalar::U32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            Scalar::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            Scalar::F64 => f64::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+            ]),
+        })
+    }
+
+    fn next_word(&mut self) -> Result<f64, String> {
+        loop {
+            if let Some(word) = self.words.next() {
+                return word
+                    .parse()
+                    .map_err(|_| format!("Error: Invalid PLY Value: {word}"));
+            }
+
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return Err("Error: Unexpected End Of PLY Data".to_string()),
+                Ok(_) => {
+                    self.words = line
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect::<Vec<String>>()
+                        .into_iter();
+                }
+                Err(error) => return Err(format!("Error: {error} Reading PLY Data")),
+            }
+        }
+    }
+}
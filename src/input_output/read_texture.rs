@@ -0,0 +1,209 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+#[cfg(feature = "png")]
+use png::{ColorType, Decoder, Transformations};
+
+use crate::{Color, Texture};
+
+/// Reads a PPM, PNG or TGA image, by the extension of `path`.
+pub fn read_texture(path: String) -> Result<Texture, String> {
+    let extension = path.split('.').next_back().unwrap().to_lowercase();
+    let file = File::open(&path).map_err(|error| format!("Error: {error} Reading File: {path}"))?;
+    let mut read = BufReader::new(file);
+
+    match extension.as_str() {
+        "ppm" => read_ppm(&mut read),
+        "png" => read_png(read),
+        "tga" => read_tga(&mut read),
+        _ => Err(format!("Error: Unsupported Texture Type: {extension}")),
+    }
+}
+
+fn read_ppm<R: Read>(read: &mut R) -> Result<Texture, String> {
+    let mut data = Vec::new();
+    read.read_to_end(&mut data)
+        .map_err(|error| format!("Error: {error} Reading PPM"))?;
+
+    // Header: magic number, width, height and maximum value, with # comments.
+    let mut position = 0;
+    let mut fields = Vec::with_capacity(4);
+    while fields.len() < 4 {
+        while position < data.len() && data[position].is_ascii_whitespace() {
+            position += 1;
+        }
+        if data.get(position) == Some(&b'#') {
+            while position < data.len() && data[position] != b'\n' {
+                position += 1;
+            }
+            continue;
+        }
+        let start = position;
+        while position < data.len() && !data[position].is_ascii_whitespace() {
+            position += 1;
+        }
+        if start == position {
+            return Err("Error: Truncated PPM Header".to_string());
+        }
+        fields.push(String::from_utf8_lossy(&data[start..position]).to_string());
+    }
+
+    let number = |field: &str| {
+        field
+            .parse::<usize>()
+            .map_err(|_| format!("Error: Invalid PPM Header Field: {field}"))
+    };
+    let width = number(&fields[1])?;
+    let height = number(&fields[2])?;
+    let maximum = number(&fields[3])?.max(1);
+    // The format caps samples at two bytes.
+    if maximum > 65535 {
+        return Err(format!("Error: Invalid PPM Header Field: {}", fields[3]));
+    }
+    let pixel_count = width
+        .checked_mul(height)
+        .filter(|count| count.checked_mul(3).is_some())
+        .ok_or_else(|| format!("Error: Invalid PPM Size: {width}x{height}"))?;
+    let channel = |value: usize| (value.min(maximum) * 255 / maximum) as u8;
+    let samples = data.get(position + 1..).unwrap_or_default();
+
+    let values: Vec<usize> = match fields[0].as_str() {
+        "P3" => String::from_utf8_lossy(&data[position..])
+            .split_whitespace()
+            .map(number)
+            .collect::<Result<_, _>>()?,
+        // A single whitespace character separates the header from the samples.
+        "P6" if maximum < 256 => samples.iter().map(|b| *b as usize).collect(),
+        "P6" => samples
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]) as usize)
+            .collect(),
+        magic => return Err(format!("Error: Unsupported PPM Type: {magic}")),
+    };
+
+    if values.len() < 3 * pixel_count {
+        return Err("Error: Truncated PPM Data".to_string());
+    }
+
+    let pixels = values
+        .chunks_exact(3)
+        .take(pixel_count)
+        .map(|rgb| Color::new(channel(rgb[0]), channel(rgb[1]), channel(rgb[2])))
+        .collect();
+    Ok(Texture::new(width, height, pixels))
+}
+
+#[cfg(feature = "png")]
+fn read_png<R: Read>(read: R) -> Result<Texture, String> {
+    let mut decoder = Decoder::new(read);
+    decoder.set_transformations(Transformations::EXPAND | Transformations::STRIP_16);
+    let mut reader = decoder
+        .read_info()
+        .map_err(|error| format!("Error: {error} Reading PNG"))?;
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut data)
+        .map_err(|error| format!("Error: {error} Reading PNG"))?;
+
+    let channels = match info.color_type {
+        ColorType::Grayscale => 1,
+        ColorType::GrayscaleAlpha => 2,
+        ColorType::Rgb => 3,
+        ColorType::Rgba => 4,
+        ColorType::Indexed => return Err("Error: Unexpanded PNG Palette".to_string()),
+    };
+
+    let pixels = data[..info.buffer_size()]
+        .chunks_exact(info.line_size)
+        .flat_map(|line| line.chunks_exact(channels).take(info.width as usize))
+        .map(|pixel| match channels {
+            1 | 2 => Color::new(pixel[0], pixel[0], pixel[0]),
+            _ => Color::new(pixel[0], pixel[1], pixel[2]),
+        })
+        .collect();
+    Ok(Texture::new(
+        info.width as usize,
+        info.height as usize,
+        pixels,
+    ))
+}
+
+#[cfg(not(feature = "png"))]
+fn read_png<R: Read>(_read: R) -> Result<Texture, String> {
+    Err("Error: Reading PNG Textures Needs The png Feature".to_string())
+}
+
+/// Uncompressed and run length encoded true color and grayscale TGA files.
+fn read_tga<R: Read>(read: &mut R) -> Result<Texture, String> {
+    let mut data = Vec::new();
+    read.read_to_end(&mut data)
+        .map_err(|error| format!("Error: {error} Reading TGA"))?;
+    if data.len() < 18 {
+        return Err("Error: Truncated TGA Header".to_string());
+    }
+
+    let id_length = data[0] as usize;
+    let color_map_type = data[1];
+    let image_type = data[2];
+    let width = u16::from_le_bytes([data[12], data[13]]) as usize;
+    let height = u16::from_le_bytes([data[14], data[15]]) as usize;
+    let bytes_per_pixel = data[16] as usize / 8;
+    let top_down = data[17] & 0x20 != 0;
+
+    if color_map_type != 0 {
+        return Err("Error: Unsupported Color Mapped TGA".to_string());
+    }
+    let run_length_encoded = match image_type {
+        2 | 3 => false,
+        10 | 11 => true,
+        _ => return Err(format!("Error: Unsupported TGA Type: {image_type}")),
+    };
+    if !matches!(bytes_per_pixel, 1 | 3 | 4) {
+        return Err(format!("Error: Unsupported TGA Depth: {}", data[16]));
+    }
+
+    let to_color = |pixel: &[u8]| match pixel.len() {
+        1 => Color::new(pixel[0], pixel[0], pixel[0]),
+        _ => Color::new(pixel[2], pixel[1], pixel[0]),
+    };
+    let count = width * height;
+    let mut pixels = Vec::with_capacity(count);
+    let mut bytes = data.get(18 + id_length..).unwrap_or_default();
+    let truncated = || "Error: Truncated TGA Data".to_string();
+
+    while pixels.len() < count {
+        if !run_length_encoded {
+            let pixel = bytes.get(..bytes_per_pixel).ok_or_else(truncated)?;
+            pixels.push(to_color(pixel));
+            bytes = &bytes[bytes_per_pixel..];
+            continue;
+        }
+
+        let header = *bytes.first().ok_or_else(truncated)?;
+        let length = (header & 0x7f) as usize + 1;
+        bytes = &bytes[1..];
+        if header & 0x80 != 0 {
+            let pixel = bytes.get(..bytes_per_pixel).ok_or_else(truncated)?;
+            pixels.extend(std::iter::repeat_n(to_color(pixel), length));
+            bytes = &bytes[bytes_per_pixel..];
+        } else {
+            let run = bytes
+                .get(..length * bytes_per_pixel)
+                .ok_or_else(truncated)?;
+            pixels.extend(run.chunks_exact(bytes_per_pixel).map(to_color));
+            bytes = &bytes[length * bytes_per_pixel..];
+        }
+    }
+    pixels.truncate(count);
+
+    // Rows are stored from the bottom unless the descriptor says otherwise.
+    if !top_down {
+        pixels = pixels
+            .chunks_exact(width.max(1))
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+    }
+    Ok(Texture::new(width, height, pixels))
+}
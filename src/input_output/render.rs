@@ -0,0 +1,142 @@
+use super::read_mesh;
+use crate::{
+    AsciiTarget, BrailleTarget, Canvas, Color, CullMode, Frame, Layout, Object, PpmTarget,
+    Rasterizer, RayTracer, RayTracing, RenderTarget, Renderer, Scene, Shading, Shadows,
+    SquaredCanvas, SvgTarget, Transform, Vector3D, Visualization,
+};
+use std::fs::write;
+use std::path::Path;
+
+/// A single frame of some 3D files, drawn for printing or saving rather than
+/// for the interactive viewer.
+#[derive(Debug, Clone)]
+pub struct Render {
+    pub paths: Vec<String>,
+    /// File to write the frame to. It is printed when there is none. Files
+    /// ending in .ppm or .svg get an image, others text.
+    pub output: Option<String>,
+    /// Columns and rows of the canvas, an odd number.
+    pub size: usize,
+    pub layout: Layout,
+    /// Placement of each file, in the order of `paths`.
+    pub transforms: Vec<Transform>,
+    pub colored: bool,
+    /// Write the text in braille dots, 2 by 4 cells per character.
+    pub braille: bool,
+    pub cull_mode: CullMode,
+    /// Rotation of the view around the X, Y and Z axes, in radians.
+    pub rotation: (f32, f32, f32),
+    pub shading: Shading,
+    /// Shadows of the light, from a shadow map when rasterizing.
+    pub shadows: bool,
+    /// Rays cast from every vertex to bake the ambient occlusion when rasterizing.
+    pub ambient_occlusion: Option<usize>,
+    /// Cast rays through the cells with these settings instead of rasterizing.
+    pub ray_tracing: Option<RayTracing>,
+}
+
+/// Draws the frame described by `render`.
+pub fn get_render_frame(render: &Render) -> Result<Frame, String> {
+    if render.size.is_multiple_of(2) {
+        return Err(format!("Error: Invalid Size: {}", render.size));
+    }
+    if render.ray_tracing.is_some() && render.shading.visualization != Visualization::Shaded {
+        return Err("Error: Only The Shaded View Can Be Ray Traced".to_string());
+    }
+    if render.transforms.len() > render.paths.len() {
+        return Err("Error: More Transforms Than Files".to_string());
+    }
+
+    let mut objects = Vec::new();
+    for (index, path) in render.paths.iter().enumerate() {
+        let mut indexed_mesh = read_mesh(path.clone())?;
+        if render.ray_tracing.is_none() {
+            if let Some(rays) = render.ambient_occlusion {
+                indexed_mesh.bake_ambient_occlusion(rays);
+            }
+        }
+        if render.shading.visualization.uses_curvature() {
+            indexed_mesh.bake_curvature();
+        }
+        let mut object = Object::new(indexed_mesh);
+        object.set_name(path.clone());
+        object.set_color(Some(Color::palette(index)));
+        objects.push(object);
+    }
+
+    let mut objects = objects.into_iter();
+    let first = match objects.next() {
+        Some(object) => object,
+        None => return Err("Error: No Files To Render".to_string()),
+    };
+    let mut scene = Scene::new(
+        first,
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    );
+    objects.for_each(|object| scene.add_object(object));
+    scene.arrange(render.layout);
+    scene.apply_transforms(&render.transforms);
+    scene.set_cull_mode(render.cull_mode);
+
+    let (x, y, z) = render.rotation;
+    scene.rotate_delta_x(&x);
+    scene.rotate_delta_y(&y);
+    scene.rotate_delta_z(&z);
+
+    let mut canvas = SquaredCanvas::new(render.size, 2.0 * scene.get_maximum_radius());
+    canvas.set_shading(render.shading.clone());
+
+    let renderer: Box<dyn Renderer> = match render.ray_tracing {
+        Some(ray_tracing) => {
+            scene.build_bvhs();
+            Box::new(RayTracer::new(ray_tracing))
+        }
+        None => {
+            scene.set_shadows(Shadows {
+                enabled: render.shadows,
+                ..Shadows::default()
+            });
+            Box::new(Rasterizer::new(render.shading.visualization))
+        }
+    };
+    renderer.render(&scene, &canvas);
+
+    Ok(canvas.get_frame())
+}
+
+/// Draws the frame described by `render` as text, with `\n` line endings.
+pub fn render_frame(render: &Render) -> Result<String, String> {
+    let frame = get_render_frame(render)?;
+    let text = match render.braille {
+        true => BrailleTarget::new(render.colored, "\n").draw(&frame),
+        false => AsciiTarget::new(render.colored, "\n").draw(&frame),
+    };
+    Ok(text)
+}
+
+/// Draws the frame described by `render` and writes it to its output, or prints it.
+pub fn render(render: &Render) -> Result<(), String> {
+    let extension = render
+        .output
+        .as_ref()
+        .and_then(|output| Path::new(output).extension())
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    let frame = match extension.as_deref() {
+        Some("ppm") => PpmTarget::default().draw(&get_render_frame(render)?),
+        Some("svg") => SvgTarget::default()
+            .draw(&get_render_frame(render)?)
+            .into_bytes(),
+        _ => render_frame(render)?.into_bytes(),
+    };
+
+    match &render.output {
+        Some(output) => {
+            if let Err(error) = write(output, frame) {
+                return Err(format!("Error: {error} Writing File: {output}"));
+            }
+        }
+        None => print!("{}", String::from_utf8_lossy(&frame)),
+    }
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use super::frontend::{terminal_error, Frontend, Input};
+use std::cell::Cell;
+use std::io::{self, stdin, stdout, Read, Stdout, Write};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+use termion::event::{Event, Key, MouseButton, MouseEvent};
+use termion::input::{MouseTerminal, TermRead};
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::terminal_size;
+
+// Sequences around pasted text, and the ones turning bracketed paste on and off.
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+const ENABLE_BRACKETED_PASTE: &str = "\x1b[?2004h";
+const DISABLE_BRACKETED_PASTE: &str = "\x1b[?2004l";
+
+// Turning off the mouse reporting of `MouseTerminal`.
+const DISABLE_MOUSE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+// Synchronized output, which termion has no sequences for.
+const BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026h";
+const END_SYNCHRONIZED_UPDATE: &str = "\x1b[?2026l";
+
+/// Frontend on termion. The input is read on a thread of its own, started with
+/// the first read. termion reports no resizes, so every read compares the size
+/// of the terminal with the one read last.
+pub struct TermionFrontend {
+    /// Raw mode and mouse reporting while it is set.
+    terminal: Option<MouseTerminal<RawTerminal<Stdout>>>,
+    inputs: Option<Receiver<Input>>,
+    /// Size of the terminal read last.
+    size: Cell<Option<(usize, usize)>>,
+}
+
+impl Default for TermionFrontend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TermionFrontend {
+    pub fn new() -> Self {
+        Self {
+            terminal: None,
+            inputs: None,
+            size: Cell::new(None),
+        }
+    }
+
+    fn get_inputs(&mut self) -> &Receiver<Input> {
+        self.inputs.get_or_insert_with(|| {
+            let (sender, receiver) = channel();
+            thread::spawn(move || {
+                let events = PasteFilter::new(stdin(), sender.clone()).events();
+                for event in events {
+                    let input = match event {
+                        Ok(event) => get_input(event),
+                        Err(_) => break,
+                    };
+                    if let Some(input) = input {
+                        if sender.send(input).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+            receiver
+        })
+    }
+}
+
+impl Frontend for TermionFrontend {
+    fn get_size(&self) -> Result<(usize, usize), String> {
+        let (columns, lines) = terminal_size().map_err(terminal_error)?;
+        let size = (columns as usize, lines as usize);
+        self.size.set(Some(size));
+        Ok(size)
+    }
+
+    fn set_raw_mode(&mut self, enabled: bool) -> Result<(), String> {
+        match (enabled, self.terminal.is_some()) {
+            (true, false) => {
+                let raw = stdout().into_raw_mode().map_err(terminal_error)?;
+                self.terminal = Some(MouseTerminal::from(raw));
+                self.write(ENABLE_BRACKETED_PASTE)
+            }
+            (false, true) => {
+                self.write(DISABLE_BRACKETED_PASTE)?;
+                self.flush()?;
+                // Mouse reporting and then raw mode are turned off on drop.
+                self.terminal = None;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) -> Result<(), String> {
+        match enabled {
+            true => self.write(&termion::screen::ToAlternateScreen.to_string()),
+            false => self.write(&termion::screen::ToMainScreen.to_string()),
+        }
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) -> Result<(), String> {
+        match visible {
+            true => self.write(termion::cursor::Show.as_ref()),
+            false => self.write(termion::cursor::Hide.as_ref()),
+        }
+    }
+
+    fn set_synchronized_update(&mut self, enabled: bool) -> Result<(), String> {
+        match enabled {
+            true => self.write(BEGIN_SYNCHRONIZED_UPDATE),
+            false => self.write(END_SYNCHRONIZED_UPDATE),
+        }
+    }
+
+    fn write(&mut self, text: &str) -> Result<(), String> {
+        match &mut self.terminal {
+            Some(terminal) => terminal.write_all(text.as_bytes()),
+            None => stdout().write_all(text.as_bytes()),
+        }
+        .map_err(terminal_error)
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        match &mut self.terminal {
+            Some(terminal) => terminal.flush(),
+            None => stdout().flush(),
+        }
+        .map_err(terminal_error)
+    }
+
+    fn read_input(&mut self, timeout: Duration) -> Result<Option<Input>, String> {
+        let previous_size = self.size.get();
+        if let Ok(size) = self.get_size() {
+            if previous_size.is_some_and(|previous_size| previous_size != size) {
+                return Ok(Some(Input::Resize(size.0, size.1)));
+            }
+        }
+        match self.get_inputs().recv_timeout(timeout) {
+            Ok(input) => Ok(Some(input)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err("Error: Terminal Input Closed".to_string()),
+        }
+    }
+
+    fn get_panic_restore(&self) -> fn() {
+        restore
+    }
+}
+
+// Raw mode stays on until the frontend is dropped while unwinding, since
+// termion keeps the previous mode in the `RawTerminal`.
+fn restore() {
+    let _ = write!(
+        stdout(),
+        "{END_SYNCHRONIZED_UPDATE}{DISABLE_BRACKETED_PASTE}{DISABLE_MOUSE}{}{}",
+        termion::cursor::Show,
+        termion::screen::ToMainScreen
+    );
+    let _ = stdout().flush();
+}
+
+fn get_input(event: Event) -> Option<Input> {
+    match event {
+        Event::Key(Key::Char(char)) => Some(Input::Char(char)),
+        Event::Key(Key::Up) => Some(Input::Up),
+        Event::Key(Key::Down) => Some(Input::Down),
+        Event::Key(Key::Left) => Some(Input::Left),
+        Event::Key(Key::Right) => Some(Input::Right),
+        Event::Key(Key::Backspace) => Some(Input::Backspace),
+        // Terminal positions start at one.
+        Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y)) => Some(Input::Click(
+            (x as usize).saturating_sub(1),
+            (y as usize).saturating_sub(1),
+        )),
+        _ => None,
+    }
+}
+
+/// Reads the input with the pasted text taken out and sent on its own, since
+/// termion does not parse the sequences around it.
+struct PasteFilter<R> {
+    source: R,
+    sender: Sender<Input>,
+    /// Bytes read and not handled yet.
+    buffer: Vec<u8>,
+    /// Text pasted so far, while inside the paste sequences.
+    paste: Option<Vec<u8>>,
+}
+
+impl<R: Read> PasteFilter<R> {
+    fn new(source: R, sender: Sender<Input>) -> Self {
+        Self {
+            source,
+            sender,
+            buffer: Vec::new(),
+            paste: None,
+        }
+    }
+
+    /// Moves the bytes before the next paste to `output`, sending the pastes
+    /// found on the way. None when more bytes are needed.
+    fn take(&mut self, output: &mut [u8]) -> Option<usize> {
+        loop {
+            if let Some(paste) = &mut self.paste {
+                match find(&self.buffer, PASTE_END) {
+                    Some(position) => {
+                        paste.extend(self.buffer.drain(..position));
+                        self.buffer.drain(..PASTE_END.len());
+                        let text = String::from_utf8_lossy(paste).into_owned();
+                        self.paste = None;
+                        let _ = self.sender.send(Input::Paste(text));
+                    }
+                    None => {
+                        // The end of the buffer may be the start of the sequence.
+                        let kept = self.buffer.len().min(PASTE_END.len() - 1);
+                        paste.extend(self.buffer.drain(..self.buffer.len() - kept));
+                        return None;
+                    }
+                }
+                continue;
+            }
+
+            let plain = match find(&self.buffer, PASTE_START) {
+                Some(0) => {
+                    self.buffer.drain(..PASTE_START.len());
+                    self.paste = Some(Vec::new());
+                    continue;
+                }
+                Some(position) => position,
+                // The end of the buffer may be the start of the sequence, which
+                // is kept until the next read tells.
+                None => self.buffer.len() - get_partial_length(&self.buffer, PASTE_START),
+            };
+            if plain == 0 {
+                return None;
+            }
+            let count = plain.min(output.len());
+            output[..count].copy_from_slice(&self.buffer[..count]);
+            self.buffer.drain(..count);
+            return Some(count);
+        }
+    }
+}
+
+impl<R: Read> Read for PasteFilter<R> {
+    fn read(&mut self, output: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(count) = self.take(output) {
+                return Ok(count);
+            }
+
+            let mut chunk = [0; 1024];
+            let count = self.source.read(&mut chunk)?;
+            if count == 0 {
+                // Nothing follows the bytes kept back, so they are not a paste.
+                let count = self.buffer.len().min(output.len());
+                output[..count].copy_from_slice(&self.buffer[..count]);
+                self.buffer.drain(..count);
+                return Ok(count);
+            }
+            self.buffer.extend_from_slice(&chunk[..count]);
+        }
+    }
+}
+
+// Length of the longest end of `bytes` that starts `sequence` without being all
+// of it.
+fn get_partial_length(bytes: &[u8], sequence: &[u8]) -> usize {
+    (1..sequence.len())
+        .rev()
+        .find(|length| bytes.ends_with(&sequence[..*length]))
+        .unwrap_or(0)
+}
+
+fn find(bytes: &[u8], sequence: &[u8]) -> Option<usize> {
+    bytes
+        .windows(sequence.len())
+        .position(|window| window == sequence)
+}
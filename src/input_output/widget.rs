@@ -0,0 +1,135 @@
+use crate::canvas::{BrailleTarget, Canvas, Rasterizer, Renderer, Shading, SquaredCanvas};
+use crate::entities::{Color, Scene};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color as TuiColor;
+use ratatui::widgets::{StatefulWidget, Widget};
+
+/// How the cells of the canvas are written in the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GlyphMode {
+    /// A glyph every three columns, as in the viewer.
+    #[default]
+    Ascii,
+    /// Braille dots, 2 by 4 cells per column.
+    Braille,
+}
+
+/// Settings of the 3D view of a scene in a ratatui buffer. It is a
+/// `StatefulWidget` whose state is the scene, so the application can keep
+/// turning it between frames; `SceneWidget` draws a borrowed scene instead.
+#[derive(Debug, Clone, Default)]
+pub struct SceneView {
+    pub glyph_mode: GlyphMode,
+    pub colored: bool,
+    pub shading: Shading,
+}
+
+impl SceneView {
+    pub fn new(glyph_mode: GlyphMode, colored: bool) -> SceneView {
+        SceneView {
+            glyph_mode,
+            colored,
+            shading: Shading::default(),
+        }
+    }
+
+    pub fn set_shading(&mut self, shading: Shading) {
+        self.shading = shading;
+    }
+
+    /// Draws `scene` on the largest square canvas that fits in `area`,
+    /// centered in it.
+    pub fn draw(&self, scene: &Scene, area: Rect, buffer: &mut Buffer) {
+        let area = area.intersection(buffer.area);
+        let (width, height) = (area.width as usize, area.height as usize);
+        let mut cols = match self.glyph_mode {
+            GlyphMode::Ascii => width.div_ceil(3).min(height),
+            GlyphMode::Braille => (2 * width).min(4 * height),
+        };
+        if cols.is_multiple_of(2) {
+            cols = cols.saturating_sub(1);
+        }
+        if cols == 0 {
+            return;
+        }
+
+        let mut canvas = SquaredCanvas::new(cols, 2.0 * scene.get_maximum_radius());
+        canvas.set_shading(self.shading.clone());
+        Rasterizer::new(self.shading.visualization).render(scene, &canvas);
+        let frame = canvas.get_frame();
+
+        let (frame_width, frame_height) = match self.glyph_mode {
+            GlyphMode::Ascii => (3 * cols - 2, cols),
+            GlyphMode::Braille => (cols.div_ceil(2), cols.div_ceil(4)),
+        };
+        let left = area.x + ((width - frame_width) / 2) as u16;
+        let top = area.y + ((height - frame_height) / 2) as u16;
+
+        let mut set_glyph = |column: usize, line: usize, char: char, color: Option<Color>| {
+            let position = (left + column as u16, top + line as u16);
+            if let Some(cell) = buffer.cell_mut(position) {
+                cell.set_char(char);
+                if let (true, Some(Color { r, g, b })) = (self.colored, color) {
+                    cell.set_fg(TuiColor::Rgb(r, g, b));
+                }
+            }
+        };
+
+        match self.glyph_mode {
+            GlyphMode::Ascii => {
+                for (line, cells) in frame.lines().enumerate() {
+                    for (col, cell) in cells.iter().enumerate() {
+                        if cell.char != ' ' {
+                            set_glyph(3 * col, line, cell.char, cell.color);
+                        }
+                    }
+                }
+            }
+            GlyphMode::Braille => {
+                for line in 0..frame_height {
+                    for column in 0..frame_width {
+                        if let Some((char, color)) = BrailleTarget::get_glyph(&frame, column, line)
+                        {
+                            set_glyph(column, line, char, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl StatefulWidget for &SceneView {
+    type State = Scene;
+
+    fn render(self, area: Rect, buffer: &mut Buffer, scene: &mut Scene) {
+        self.draw(scene, area, buffer);
+    }
+}
+
+impl StatefulWidget for SceneView {
+    type State = Scene;
+
+    fn render(self, area: Rect, buffer: &mut Buffer, scene: &mut Scene) {
+        self.draw(scene, area, buffer);
+    }
+}
+
+/// Draws a borrowed scene with the settings of a `SceneView`.
+pub struct SceneWidget<'a> {
+    pub scene: &'a Scene,
+    pub view: SceneView,
+}
+
+impl<'a> SceneWidget<'a> {
+    pub fn new(scene: &'a Scene, view: SceneView) -> SceneWidget<'a> {
+        SceneWidget { scene, view }
+    }
+}
+
+impl Widget for SceneWidget<'_> {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        self.view.draw(self.scene, area, buffer);
+    }
+}
@@ -0,0 +1,171 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::{Color, IndexedMesh3D};
+
+pub enum OutputFormat {
+    StlBinary,
+    StlAscii,
+    Obj,
+    PlyBinary,
+    PlyAscii,
+}
+
+/// Picks the output format from the file extension. `ascii` selects the text
+/// variant of STL and PLY; OBJ is always text.
+pub fn get_output_format(path: String, ascii: bool) -> Result<OutputFormat, String> {
+    let extension = path.split('.').next_back().unwrap();
+    match (extension, ascii) {
+        ("stl", false) => Ok(OutputFormat::StlBinary),
+        ("stl", true) => Ok(OutputFormat::StlAscii),
+        ("obj", _) => Ok(OutputFormat::Obj),
+        ("ply", false) => Ok(OutputFormat::PlyBinary),
+        ("ply", true) => Ok(OutputFormat::PlyAscii),
+        _ => Err(format!("Error: Unsupported File Type: {extension}")),
+    }
+}
+
+pub fn write_mesh(path: String, mesh: &IndexedMesh3D, format: OutputFormat) -> Result<(), String> {
+    let file = match File::create(&path) {
+        Ok(file) => file,
+        Err(error) => return Err(format!("Error: {error} Writing File: {path}")),
+    };
+    let mut writer = BufWriter::new(file);
+
+    let result = match format {
+        OutputFormat::StlBinary => write_stl_binary(&mut writer, mesh),
+        OutputFormat::StlAscii => write_stl_ascii(&mut writer, mesh),
+        OutputFormat::Obj => write_obj(&mut writer, mesh),
+        OutputFormat::PlyBinary => write_ply_binary(&mut writer, mesh),
+        OutputFormat::PlyAscii => write_ply_ascii(&mut writer, mesh),
+    };
+
+    match result.and_then(|_| writer.flush()) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(format!("Error: {error} Writing Mesh: {path}")),
+    }
+}
+
+/// Facet colors are written in the VisCAM and SolidView layout: bit 15 set, then
+/// red, green and blue in five bits each, blue in the lowest ones.
+pub fn write_stl_binary<W: Write>(writer: &mut W, mesh: &IndexedMesh3D) -> std::io::Result<()> {
+    let mut header = [0u8; 80];
+    let title = b"binary STL written by sx3d";
+    header[..title.len()].copy_from_slice(title);
+    writer.write_all(&header)?;
+    writer.write_all(&(mesh.triangles.len() as u32).to_le_bytes())?;
+
+    for (triangle, indexed_triangle) in mesh.iter().zip(mesh.triangles.iter()) {
+        for value in triangle.normal.0 {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        for vertex in triangle.vertices.iter() {
+            for value in vertex.0 {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+
+        let attribute = match indexed_triangle.color {
+            Some(Color { r, g, b }) => {
+                0x8000 | ((r as u16 >> 3) << 10) | ((g as u16 >> 3) << 5) | (b as u16 >> 3)
+            }
+            None => 0,
+        };
+        writer.write_all(&attribute.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+pub fn write_stl_ascii<W: Write>(writer: &mut W, mesh: &IndexedMesh3D) -> std::io::Result<()> {
+    writeln!(writer, "solid sx3d")?;
+
+    for triangle in mesh.iter() {
+        let [nx, ny, nz] = triangle.normal.0;
+        writeln!(writer, "  facet normal {nx:e} {ny:e} {nz:e}")?;
+        writeln!(writer, "    outer loop")?;
+        for vertex in triangle.vertices.iter() {
+            let [x, y, z] = vertex.0;
+            writeln!(writer, "      vertex {x:e} {y:e} {z:e}")?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+
+    writeln!(writer, "endsolid sx3d")
+}
+
+/// Writes one `vn` per face, since the mesh only stores face normals.
+pub fn write_obj<W: Write>(writer: &mut W, mesh: &IndexedMesh3D) -> std::io::Result<()> {
+    writeln!(writer, "# written by sx3d")?;
+
+    for vertex in mesh.vertices.iter() {
+        let [x, y, z] = vertex.0;
+        writeln!(writer, "v {x} {y} {z}")?;
+    }
+
+    for triangle in mesh.triangles.iter() {
+        let [x, y, z] = triangle.normal.0;
+        writeln!(writer, "vn {x} {y} {z}")?;
+    }
+
+    for (index, triangle) in mesh.triangles.iter().enumerate() {
+        let [a, b, c] = triangle.vertices_indices.map(|vertex| vertex + 1);
+        let n = index + 1;
+        writeln!(writer, "f {a}//{n} {b}//{n} {c}//{n}")?;
+    }
+
+    Ok(())
+}
+
+fn write_ply_header<W: Write>(
+    writer: &mut W,
+    mesh: &IndexedMesh3D,
+    format: &str,
+) -> std::io::Result<()> {
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {format} 1.0")?;
+    writeln!(writer, "comment written by sx3d")?;
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    writeln!(writer, "property float x")?;
+    writeln!(writer, "property float y")?;
+    writeln!(writer, "property float z")?;
+    writeln!(writer, "element face {}", mesh.triangles.len())?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "end_header")
+}
+
+pub fn write_ply_ascii<W: Write>(writer: &mut W, mesh: &IndexedMesh3D) -> std::io::Result<()> {
+    write_ply_header(writer, mesh, "ascii")?;
+
+    for vertex in mesh.vertices.iter() {
+        let [x, y, z] = vertex.0;
+        writeln!(writer, "{x} {y} {z}")?;
+    }
+
+    for triangle in mesh.triangles.iter() {
+        let [a, b, c] = triangle.vertices_indices;
+        writeln!(writer, "3 {a} {b} {c}")?;
+    }
+
+    Ok(())
+}
+
+pub fn write_ply_binary<W: Write>(writer: &mut W, mesh: &IndexedMesh3D) -> std::io::Result<()> {
+    write_ply_header(writer, mesh, "binary_little_endian")?;
+
+    for vertex in mesh.vertices.iter() {
+        for value in vertex.0 {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    for triangle in mesh.triangles.iter() {
+        writer.write_all(&[3u8])?;
+        for index in triangle.vertices_indices {
+            writer.write_all(&(index as i32).to_le_bytes())?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::{Layer, Vector2D};
+
+/// Writes one SVG file per layer, named `layer_0001.svg` and so on, all with the
+/// same view box so they can be flipped through like frames.
+pub fn write_layers_svg(directory: String, layers: &[Layer]) -> Result<(), String> {
+    if let Err(error) = create_dir_all(&directory) {
+        return Err(format!("Error: {error} Creating Directory: {directory}"));
+    }
+
+    let bounds = get_layers_bounds(layers);
+
+    for (index, layer) in layers.iter().enumerate() {
+        let path = Path::new(&directory).join(format!("layer_{:04}.svg", index + 1));
+        let path = path.to_string_lossy().to_string();
+
+        let file = match File::create(&path) {
+            Ok(file) => file,
+            Err(error) => return Err(format!("Error: {error} Writing File: {path}")),
+        };
+        let mut writer = BufWriter::new(file);
+
+        if let Err(error) = write_layer_svg(&mut writer, layer, bounds).and_then(|_| writer.flush())
+        {
+            return Err(format!("Error: {error} Writing Layer: {path}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the contours of a layer as a single even-odd filled path, so holes
+/// show up as holes.
+pub fn write_layer_svg<W: Write>(
+    writer: &mut W,
+    layer: &Layer,
+    bounds: [Vector2D; 2],
+) -> std::io::Result<()> {
+    let [min, max] = bounds;
+    let width = (max[0] - min[0]).max(f32::EPSILON);
+    let height = (max[1] - min[1]).max(f32::EPSILON);
+    let margin = width.max(height) * 0.05;
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        min[0] - margin,
+        -max[1] - margin,
+        width + 2.0 * margin,
+        height + 2.0 * margin
+    )?;
+    writeln!(writer, "  <title>Layer at {}</title>", layer.height)?;
+
+    let mut data = String::new();
+    for contour in layer.contours.iter() {
+        for (index, point) in contour.points.iter().enumerate() {
+            let command = if index == 0 { 'M' } else { 'L' };
+            // SVG grows downwards, the model grows upwards.
+            data.push_str(&format!("{command}{} {} ", point[0], -point[1]));
+        }
+        if contour.closed {
+            data.push_str("Z ");
+        }
+    }
+
+    writeln!(
+        writer,
+        r#"  <path d="{}" fill="lightgray" fill-rule="evenodd" stroke="black" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+        data.trim_end()
+    )?;
+    writeln!(writer, "</svg>")
+}
+
+pub fn get_layers_bounds(layers: &[Layer]) -> [Vector2D; 2] {
+    let mut min = Vector2D::new(f32::INFINITY, f32::INFINITY);
+    let mut max = Vector2D::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+    for point in layers
+        .iter()
+        .flat_map(|layer| layer.contours.iter())
+        .flat_map(|contour| contour.points.iter())
+    {
+        min = Vector2D::new(min[0].min(point[0]), min[1].min(point[1]));
+        max = Vector2D::new(max[0].max(point[0]), max[1].max(point[1]));
+    }
+
+    if min[0] > max[0] {
+        return [Vector2D::new(0.0, 0.0), Vector2D::new(0.0, 0.0)];
+    }
+
+    [min, max]
+}
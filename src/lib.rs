@@ -1,6 +1,7 @@
 pub mod canvas;
 pub mod entities;
 pub mod input_output;
+mod parallel;
 
 pub use canvas::*;
 pub use entities::*;
@@ -1,8 +1,70 @@
-use sx3d::input_output::{get_file_path, Console};
+use std::process::exit;
+use sx3d::input_output::{convert, get_args, render, slice_to_svg, Command, Console, Frontend};
 
 fn main() {
-    let path = get_file_path();
+    let mut args = get_args();
 
-    let mut console = Console::new();
-    console.start(path);
+    match args.command {
+        Some(Command::Convert(convert_args)) => {
+            if let Err(error) = convert(&convert_args.into()) {
+                eprintln!("{error}");
+                exit(1);
+            }
+        }
+        Some(Command::Slice(slice_args)) => {
+            match slice_to_svg(slice_args.input, slice_args.output, slice_args.layer_height) {
+                Ok(layers) => println!("{layers} layers written"),
+                Err(error) => {
+                    eprintln!("{error}");
+                    exit(1);
+                }
+            }
+        }
+        Some(Command::Render(mut render_args)) => {
+            let result = render_args
+                .shading
+                .apply_config()
+                .and_then(|()| render(&render_args.into()));
+            if let Err(error) = result {
+                eprintln!("{error}");
+                exit(1);
+            }
+        }
+        None => {
+            if let Err(error) = args.shading.apply_config() {
+                eprintln!("{error}");
+                exit(1);
+            }
+            let mut console = Console::new();
+            console.set_cull_mode(args.cull.into());
+            console.set_layer_height(args.layer_height);
+            console.set_layout(args.layout.into());
+            console.set_transforms(args.transforms.clone());
+            console.set_colored(args.color);
+            console.set_shading(args.shading.get_shading());
+            console.set_shadows(args.get_shadows());
+            console.set_ambient_occlusion(args.ao);
+            let result =
+                get_frontend().and_then(|mut frontend| console.start(args.paths, &mut *frontend));
+            if let Err(error) = result {
+                eprintln!("{error}");
+                exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "termion-frontend")]
+fn get_frontend() -> Result<Box<dyn Frontend>, String> {
+    Ok(Box::new(sx3d::input_output::TermionFrontend::new()))
+}
+
+#[cfg(all(feature = "crossterm-frontend", not(feature = "termion-frontend")))]
+fn get_frontend() -> Result<Box<dyn Frontend>, String> {
+    Ok(Box::new(sx3d::input_output::CrosstermFrontend::new()))
+}
+
+#[cfg(not(any(feature = "termion-frontend", feature = "crossterm-frontend")))]
+fn get_frontend() -> Result<Box<dyn Frontend>, String> {
+    Err("Error: The Viewer Needs The termion-frontend Or crossterm-frontend Feature".to_string())
 }
@@ -0,0 +1,41 @@
+//! Parallel iterators of rayon with the `parallel` feature, and the sequential
+//! ones of the standard library under the same names without it.
+
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::prelude::*;
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) use sequential::*;
+
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    use std::slice::{Iter, IterMut};
+
+    pub(crate) trait IntoParallelRefIterator<T> {
+        fn par_iter(&self) -> Iter<'_, T>;
+    }
+
+    impl<T> IntoParallelRefIterator<T> for [T] {
+        fn par_iter(&self) -> Iter<'_, T> {
+            self.iter()
+        }
+    }
+
+    pub(crate) trait IntoParallelRefMutIterator<T> {
+        fn par_iter_mut(&mut self) -> IterMut<'_, T>;
+    }
+
+    impl<T> IntoParallelRefMutIterator<T> for [T] {
+        fn par_iter_mut(&mut self) -> IterMut<'_, T> {
+            self.iter_mut()
+        }
+    }
+
+    pub(crate) trait ParallelBridge: Iterator + Sized {
+        fn par_bridge(self) -> Self {
+            self
+        }
+    }
+
+    impl<I: Iterator> ParallelBridge for I {}
+}
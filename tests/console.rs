@@ -0,0 +1,185 @@
+#![cfg(feature = "stl")]
+
+use std::collections::VecDeque;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use sx3d::input_output::{Console, Frontend, Input};
+
+static RESTORED_BY_HOOK: AtomicBool = AtomicBool::new(false);
+
+/// Frontend reading scripted inputs, keeping the state of the terminal and
+/// what is written on each screen. It panics once the inputs run out if
+/// `panicking` is set.
+struct ScriptedFrontend {
+    inputs: VecDeque<Input>,
+    panicking: bool,
+    alternate_output: String,
+    main_output: String,
+    raw_mode: bool,
+    alternate_screen: bool,
+    cursor_visible: bool,
+    synchronized: bool,
+    unsynchronized_writes: usize,
+    /// Synchronized updates, one for every frame written.
+    frames: usize,
+}
+
+impl ScriptedFrontend {
+    fn new(inputs: Vec<Input>) -> ScriptedFrontend {
+        ScriptedFrontend {
+            inputs: inputs.into(),
+            panicking: false,
+            alternate_output: String::new(),
+            main_output: String::new(),
+            raw_mode: false,
+            alternate_screen: false,
+            cursor_visible: true,
+            synchronized: false,
+            unsynchronized_writes: 0,
+            frames: 0,
+        }
+    }
+
+    fn is_restored(&self) -> bool {
+        !self.raw_mode && !self.alternate_screen && self.cursor_visible && !self.synchronized
+    }
+}
+
+impl Frontend for ScriptedFrontend {
+    fn get_size(&self) -> Result<(usize, usize), String> {
+        Ok((80, 24))
+    }
+
+    fn set_raw_mode(&mut self, enabled: bool) -> Result<(), String> {
+        self.raw_mode = enabled;
+        Ok(())
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) -> Result<(), String> {
+        self.alternate_screen = enabled;
+        Ok(())
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) -> Result<(), String> {
+        self.cursor_visible = visible;
+        Ok(())
+    }
+
+    fn set_synchronized_update(&mut self, enabled: bool) -> Result<(), String> {
+        if enabled {
+            self.frames += 1;
+        }
+        self.synchronized = enabled;
+        Ok(())
+    }
+
+    fn write(&mut self, text: &str) -> Result<(), String> {
+        match self.alternate_screen {
+            true => {
+                if !self.synchronized {
+                    self.unsynchronized_writes += 1;
+                }
+                self.alternate_output.push_str(text);
+            }
+            false => self.main_output.push_str(text),
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn read_input(&mut self, _timeout: Duration) -> Result<Option<Input>, String> {
+        match self.inputs.pop_front() {
+            Some(input) => Ok(Some(input)),
+            None if self.panicking => panic!("Input failed"),
+            None => Err("Error: Terminal Input Closed".to_string()),
+        }
+    }
+
+    fn get_panic_restore(&self) -> fn() {
+        || RESTORED_BY_HOOK.store(true, Ordering::SeqCst)
+    }
+}
+
+fn start(frontend: &mut ScriptedFrontend) -> Result<(), String> {
+    Console::new().start(vec!["examples/cube_binary.stl".to_string()], frontend)
+}
+
+#[test]
+fn it_should_draw_frames_on_the_alternate_screen_until_quitting() {
+    let mut frontend = ScriptedFrontend::new(vec![
+        Input::Char('\n'),
+        Input::Left,
+        Input::Char('q'),
+        Input::Char('\n'),
+    ]);
+
+    start(&mut frontend).unwrap();
+
+    let output = &frontend.alternate_output;
+    assert_eq!(frontend.frames, 3);
+    assert!(output.starts_with("\x1b[2J\x1b[1;1HTo start press Enter key.\x1b[2;1H"));
+    assert_eq!(output.matches("\x1b[2J").count(), 1);
+    assert_eq!(frontend.unsynchronized_writes, 0);
+    assert_eq!(frontend.main_output, "Goodbye!\n");
+    assert!(frontend.is_restored());
+}
+
+#[test]
+fn it_should_not_take_pasted_text_for_key_presses() {
+    let mut frontend =
+        ScriptedFrontend::new(vec![Input::Paste("q\n".to_string()), Input::Char('\n')]);
+
+    start(&mut frontend).unwrap();
+
+    assert_eq!(frontend.frames, 2);
+}
+
+#[test]
+fn it_should_not_write_frames_that_did_not_change() {
+    let mut frontend = ScriptedFrontend::new(vec![Input::Char('\n'), Input::Char('\n')]);
+
+    start(&mut frontend).unwrap();
+
+    assert_eq!(frontend.frames, 2);
+}
+
+#[test]
+fn it_should_restore_the_terminal_after_a_panic() {
+    let mut frontend = ScriptedFrontend::new(vec![Input::Char('\n')]);
+    frontend.panicking = true;
+
+    let result = catch_unwind(AssertUnwindSafe(|| start(&mut frontend)));
+
+    assert!(result.is_err());
+    assert!(RESTORED_BY_HOOK.load(Ordering::SeqCst));
+    assert!(frontend.is_restored());
+}
+
+#[test]
+fn it_should_fail_on_missing_files_before_touching_the_terminal() {
+    let mut frontend = ScriptedFrontend::new(vec![]);
+
+    let result = Console::new().start(vec!["examples/missing.stl".to_string()], &mut frontend);
+
+    assert!(result.is_err());
+    assert!(frontend.alternate_output.is_empty() && frontend.main_output.is_empty());
+    assert!(!frontend.raw_mode);
+}
+
+#[test]
+fn it_should_write_the_whole_frame_again_after_a_resize() {
+    let mut frontend = ScriptedFrontend::new(vec![Input::Char('\n'), Input::Resize(40, 10)]);
+
+    start(&mut frontend).unwrap();
+
+    let output = &frontend.alternate_output;
+    assert_eq!(frontend.frames, 3);
+    assert_eq!(output.matches("\x1b[2J").count(), 2);
+    // Nothing is written past the tenth line of the resized terminal.
+    let resized = &output[output.rfind("\x1b[2J").unwrap()..];
+    assert!(!resized.contains("\x1b[11;1H"));
+}
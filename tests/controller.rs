@@ -0,0 +1,62 @@
+#![cfg(feature = "stl")]
+
+use sx3d::canvas::{BrailleTarget, SquaredCanvas};
+use sx3d::input_output::{read_mesh, Controller};
+use sx3d::{Frame, Object, Scene, Vector3D};
+
+fn controller() -> Controller {
+    let object = Object::new(read_mesh("examples/cube_binary.stl".to_string()).unwrap());
+    let scene = Scene::new(
+        object,
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    );
+    let diameter = 2.0 * scene.get_maximum_radius();
+    Controller::new(scene, SquaredCanvas::new(9, diameter))
+}
+
+fn text(frame: &Frame) -> Vec<String> {
+    frame
+        .lines()
+        .map(|line| line.iter().map(|cell| cell.char).collect())
+        .collect()
+}
+
+#[test]
+fn it_should_write_the_frames_with_its_target() {
+    let mut controller = controller();
+    controller.set_target(Box::new(BrailleTarget::new(false, "\r\n")));
+
+    let frame = controller.enter_key();
+
+    assert_eq!((frame.cols, frame.rows), (5, 3));
+    assert!(frame
+        .cells
+        .iter()
+        .any(|cell| ('\u{2801}'..='\u{28ff}').contains(&cell.char)));
+}
+
+#[test]
+fn it_should_put_the_cursor_on_the_cell_clicked() {
+    let mut controller = controller();
+
+    let frame = controller.click(12, 4);
+
+    assert_eq!(frame.get(12, 4).unwrap().char, '+');
+    // The picked triangle is described under the view.
+    assert!(text(&frame)[9].starts_with("Triangle"));
+}
+
+#[test]
+fn it_should_put_the_measurements_beside_the_view_when_there_is_room() {
+    let mut wide = controller();
+    wide.set_terminal_width(80);
+    let mut narrow = controller();
+    narrow.set_terminal_width(40);
+
+    let wide_text = text(&wide.measure_key());
+    let narrow_text = text(&narrow.measure_key());
+
+    assert!(wide_text[0][25..].starts_with("  Click or press space"));
+    assert!(narrow_text[9].starts_with("Click or press space"));
+}
@@ -0,0 +1,132 @@
+use sx3d::canvas::{find_edges, thicken_edges, Surface};
+use sx3d::Vector3D;
+
+fn facing(depth: f32) -> Option<Surface> {
+    Some(Surface {
+        depth,
+        normal: Vector3D::new(0.0, 0.0, 1.0),
+    })
+}
+
+// Glyphs of the edges of a grid, with `.` for surfaces without an edge.
+fn draw(surfaces: &[Option<Surface>], cols: usize) -> Vec<String> {
+    let edges = find_edges(surfaces, cols, 1.0);
+    let glyphs: Vec<char> = surfaces
+        .iter()
+        .zip(edges)
+        .map(|(surface, edge)| match (surface, edge) {
+            (_, Some(edge)) => edge.glyph(),
+            (Some(_), None) => '.',
+            (None, None) => ' ',
+        })
+        .collect();
+
+    glyphs
+        .chunks(cols)
+        .map(|row| row.iter().collect())
+        .collect()
+}
+
+#[test]
+fn it_should_outline_a_square_with_straight_glyphs() {
+    let mut surfaces = vec![None; 25];
+    for row in 1..4 {
+        for col in 1..4 {
+            surfaces[row * 5 + col] = facing(0.0);
+        }
+    }
+
+    let rows = draw(&surfaces, 5);
+
+    assert_eq!(rows[1], " /-\\ ");
+    assert_eq!(rows[2], " |.| ");
+    assert_eq!(rows[3], " \\_/ ");
+}
+
+#[test]
+fn it_should_follow_diagonal_silhouettes() {
+    // Triangle with its right angle at the bottom right.
+    let mut surfaces = vec![None; 16];
+    for row in 0..4 {
+        for col in (3 - row)..4 {
+            surfaces[row * 4 + col] = facing(0.0);
+        }
+    }
+
+    let edges = find_edges(&surfaces, 4, 1.0);
+
+    assert_eq!(edges[6].unwrap().glyph(), '/');
+    assert_eq!(edges[9].unwrap().glyph(), '/');
+    assert!(edges[10].is_none());
+}
+
+#[test]
+fn it_should_give_silhouettes_to_the_closer_side() {
+    let surfaces: Vec<Option<Surface>> = (0..30)
+        .map(|index| facing(if index % 6 < 3 { 10.0 } else { 0.0 }))
+        .collect();
+
+    let rows = draw(&surfaces, 6);
+
+    assert_eq!(&rows[2][1..5], ".|..");
+}
+
+#[test]
+fn it_should_not_find_edges_on_sloped_surfaces() {
+    // A plane rising two cells in depth per cell to the right.
+    let normal = Vector3D::new(-2.0, 0.0, 1.0);
+    let surfaces: Vec<Option<Surface>> = (0..9)
+        .map(|index| {
+            Some(Surface {
+                depth: 2.0 * (index % 3) as f32,
+                normal,
+            })
+        })
+        .collect();
+
+    let edges = find_edges(&surfaces, 3, 1.0);
+
+    assert!(edges[4].is_none());
+}
+
+#[test]
+fn it_should_find_creases_between_faces() {
+    // A ridge between the third and the fourth column.
+    let surfaces: Vec<Option<Surface>> = (0..35)
+        .map(|index| {
+            let col = (index % 7) as f32;
+            let normal = if col < 2.5 {
+                Vector3D::new(-1.0, 0.0, 1.0)
+            } else {
+                Vector3D::new(1.0, 0.0, 1.0)
+            };
+            Some(Surface {
+                depth: 10.0 - (col - 2.5).abs(),
+                normal,
+            })
+        })
+        .collect();
+
+    let rows = draw(&surfaces, 7);
+
+    assert_eq!(&rows[2][1..6], ".||..");
+}
+
+#[test]
+fn it_should_thicken_outlines_across_the_edges() {
+    let mut surfaces = vec![None; 25];
+    for row in 1..4 {
+        for col in 1..4 {
+            surfaces[row * 5 + col] = facing(0.0);
+        }
+    }
+
+    let outline = thicken_edges(&find_edges(&surfaces, 5, 1.0), 5);
+
+    // The border of the square and the ring around it, but not the corners of
+    // the grid or the center of the square.
+    assert!(outline[6] && outline[5] && outline[1]);
+    assert!(outline[13] && outline[14]);
+    assert!(!outline[12]);
+    assert!(!outline[0]);
+}
@@ -0,0 +1,51 @@
+use sx3d::{IndexedMesh3D, IndexedTriangle3D, Vector3D};
+
+// Floor of side 2 made of four triangles around a center vertex, with a small
+// roof floating just above the center.
+fn floor_under_a_roof() -> IndexedMesh3D {
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+    let down = Vector3D::new(0.0, 0.0, -1.0);
+    IndexedMesh3D::new(
+        vec![
+            Vector3D::new(-1.0, -1.0, 0.0),
+            Vector3D::new(1.0, -1.0, 0.0),
+            Vector3D::new(1.0, 1.0, 0.0),
+            Vector3D::new(-1.0, 1.0, 0.0),
+            Vector3D::new(0.0, 0.0, 0.0),
+            Vector3D::new(-0.5, -0.5, 0.1),
+            Vector3D::new(-0.5, 0.5, 0.1),
+            Vector3D::new(0.5, 0.5, 0.1),
+            Vector3D::new(0.5, -0.5, 0.1),
+        ],
+        vec![
+            IndexedTriangle3D::new(up, [0, 1, 4]),
+            IndexedTriangle3D::new(up, [1, 2, 4]),
+            IndexedTriangle3D::new(up, [2, 3, 4]),
+            IndexedTriangle3D::new(up, [3, 0, 4]),
+            IndexedTriangle3D::new(down, [5, 6, 7]),
+            IndexedTriangle3D::new(down, [5, 7, 8]),
+        ],
+    )
+}
+
+#[test]
+fn it_should_darken_the_vertices_under_other_faces() {
+    let mut mesh = floor_under_a_roof();
+    mesh.bake_ambient_occlusion(64);
+
+    let center = mesh.triangles[0].occlusion.unwrap()[2];
+    let corner = mesh.triangles[0].occlusion.unwrap()[0];
+    assert!(center > 0.8, "{center}");
+    assert!(corner < 0.2, "{corner}");
+}
+
+#[test]
+fn it_should_not_bake_without_rays() {
+    let mut mesh = floor_under_a_roof();
+    mesh.bake_ambient_occlusion(0);
+
+    assert!(mesh
+        .triangles
+        .iter()
+        .all(|triangle| triangle.occlusion.is_none()));
+}
@@ -0,0 +1,139 @@
+use sx3d::{Aabb, Bvh, IndexedMesh3D, IndexedTriangle3D, Vector3D};
+
+// Row of `count` unit squares along X at height 0, facing up.
+fn strip(count: usize) -> IndexedMesh3D {
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    for index in 0..count {
+        let x = index as f32;
+        let first = vertices.len();
+        vertices.extend([
+            Vector3D::new(x, 0.0, 0.0),
+            Vector3D::new(x + 1.0, 0.0, 0.0),
+            Vector3D::new(x + 1.0, 1.0, 0.0),
+            Vector3D::new(x, 1.0, 0.0),
+        ]);
+        triangles.push(IndexedTriangle3D::new(up, [first, first + 1, first + 2]));
+        triangles.push(IndexedTriangle3D::new(up, [first, first + 2, first + 3]));
+    }
+    IndexedMesh3D::new(vertices, triangles)
+}
+
+#[test]
+fn it_should_find_the_triangles_a_ray_hits() {
+    let mesh = strip(20);
+    let bvh = Bvh::new(&mesh);
+    let down = Vector3D::new(0.0, 0.0, -1.0);
+
+    for x in [0.5, 7.25, 19.9] {
+        let origin = Vector3D::new(x, 0.5, 1.0);
+        assert!(bvh.is_hit(&mesh, &origin, &down, 0.0, 2.0));
+    }
+    assert!(!bvh.is_hit(&mesh, &Vector3D::new(20.5, 0.5, 1.0), &down, 0.0, 2.0));
+    assert!(!bvh.is_hit(&mesh, &Vector3D::new(3.5, 1.5, 1.0), &down, 0.0, 2.0));
+}
+
+#[test]
+fn it_should_only_count_hits_within_the_distances() {
+    let mesh = strip(5);
+    let bvh = Bvh::new(&mesh);
+    let origin = Vector3D::new(2.5, 0.5, 1.0);
+    let down = Vector3D::new(0.0, 0.0, -1.0);
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+
+    assert!(!bvh.is_hit(&mesh, &origin, &down, 0.0, 0.5));
+    assert!(!bvh.is_hit(&mesh, &origin, &down, 1.5, 2.0));
+    assert!(!bvh.is_hit(&mesh, &origin, &up, 0.0, 10.0));
+}
+
+#[test]
+fn it_should_bound_points() {
+    let aabb = Aabb::from_points(&[Vector3D::new(1.0, -2.0, 0.0), Vector3D::new(-1.0, 3.0, 0.5)]);
+
+    assert_eq!([aabb.min[0], aabb.min[1], aabb.min[2]], [-1.0, -2.0, 0.0]);
+    assert_eq!([aabb.max[0], aabb.max[1], aabb.max[2]], [1.0, 3.0, 0.5]);
+    assert_eq!(aabb.longest_axis(), 1);
+}
+
+// Square of side 2 at height `z`, facing up.
+fn square(z: f32) -> IndexedMesh3D {
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+    IndexedMesh3D::new(
+        vec![
+            Vector3D::new(-1.0, -1.0, z),
+            Vector3D::new(1.0, -1.0, z),
+            Vector3D::new(1.0, 1.0, z),
+            Vector3D::new(-1.0, 1.0, z),
+        ],
+        vec![
+            IndexedTriangle3D::new(up, [0, 1, 2]),
+            IndexedTriangle3D::new(up, [0, 2, 3]),
+        ],
+    )
+}
+
+#[test]
+fn it_should_find_the_closest_hit() {
+    let mut mesh = square(0.0);
+    mesh.extend(&square(1.0));
+    let bvh = Bvh::new(&mesh);
+    let down = Vector3D::new(0.0, 0.0, -1.0);
+
+    let hit = bvh
+        .intersect(&mesh, &Vector3D::new(0.5, -0.5, 3.0), &down, 10.0)
+        .unwrap();
+    assert_eq!(hit.triangle, 2);
+    assert_eq!(hit.distance, 2.0);
+    assert_eq!([hit.point[0], hit.point[1], hit.point[2]], [0.5, -0.5, 1.0]);
+
+    let below = bvh.intersect(&mesh, &Vector3D::new(0.5, -0.5, 0.5), &down, 10.0);
+    assert_eq!(below.unwrap().triangle, 0);
+    assert!(bvh
+        .intersect(&mesh, &Vector3D::new(0.5, -0.5, 3.0), &down, 1.0)
+        .is_none());
+}
+
+#[test]
+fn it_should_find_the_nearest_point() {
+    let mesh = strip(20);
+    let bvh = Bvh::new(&mesh);
+
+    let above = bvh
+        .nearest_point(&mesh, &Vector3D::new(12.5, 0.25, 2.0))
+        .unwrap();
+    assert_eq!(above.distance, 2.0);
+    assert_eq!(above.point[0], 12.5);
+
+    let beyond = bvh
+        .nearest_point(&mesh, &Vector3D::new(23.0, 5.0, 0.0))
+        .unwrap();
+    assert_eq!(beyond.distance, 5.0);
+    assert_eq!([beyond.point[0], beyond.point[1]], [20.0, 1.0]);
+}
+
+#[test]
+fn it_should_find_the_triangles_overlapping_a_box() {
+    let mesh = strip(20);
+    let bvh = Bvh::new(&mesh);
+    let aabb = Aabb::from_points(&[Vector3D::new(5.5, 0.2, -1.0), Vector3D::new(6.5, 0.8, 1.0)]);
+
+    let mut triangles = bvh.overlapping(&mesh, &aabb);
+    triangles.sort_unstable();
+
+    assert_eq!(triangles, vec![10, 11, 12, 13]);
+}
+
+#[test]
+fn it_should_follow_the_mesh_when_refitted() {
+    let mut mesh = strip(20);
+    let mut bvh = Bvh::new(&mesh);
+    mesh.translate(Vector3D::new(0.0, 0.0, 5.0));
+    bvh.refit(&mesh);
+    let down = Vector3D::new(0.0, 0.0, -1.0);
+
+    let hit = bvh
+        .intersect(&mesh, &Vector3D::new(14.5, 0.5, 10.0), &down, 20.0)
+        .unwrap();
+    assert_eq!(hit.distance, 5.0);
+}
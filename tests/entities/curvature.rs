@@ -0,0 +1,62 @@
+use sx3d::input_output::read_mesh;
+use sx3d::{IndexedMesh3D, IndexedTriangle3D, Vector3D};
+
+#[test]
+fn it_should_measure_the_curvature_of_a_sphere() {
+    let mut mesh = read_mesh("examples/sphere.stl".to_string()).unwrap();
+    mesh.move_to_center();
+    let radius = mesh.vertices[0].norm();
+    mesh.bake_curvature();
+
+    // Averaged over the corners, as the faces of a coarse sphere are not even.
+    let curvatures: Vec<_> = mesh
+        .triangles
+        .iter()
+        .flat_map(|triangle| triangle.curvature.unwrap())
+        .collect();
+    let count = curvatures.len() as f32;
+    let mean = curvatures
+        .iter()
+        .map(|curvature| curvature.mean)
+        .sum::<f32>()
+        / count;
+    let gaussian = curvatures
+        .iter()
+        .map(|curvature| curvature.gaussian)
+        .sum::<f32>()
+        / count;
+
+    assert!((mean * radius - 1.0).abs() < 0.1, "{mean}");
+    assert!((gaussian * radius * radius - 1.0).abs() < 0.1, "{gaussian}");
+}
+
+#[test]
+fn it_should_give_no_curvature_to_flat_surfaces() {
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+    // Square split around a center vertex, with every triangle on its own vertices.
+    let corners = [
+        Vector3D::new(-1.0, -1.0, 0.0),
+        Vector3D::new(1.0, -1.0, 0.0),
+        Vector3D::new(1.0, 1.0, 0.0),
+        Vector3D::new(-1.0, 1.0, 0.0),
+    ];
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    for index in 0..4 {
+        let first = vertices.len();
+        vertices.extend([
+            corners[index],
+            corners[(index + 1) % 4],
+            Vector3D::new(0.0, 0.0, 0.0),
+        ]);
+        triangles.push(IndexedTriangle3D::new(up, [first, first + 1, first + 2]));
+    }
+    let mut mesh = IndexedMesh3D::new(vertices, triangles);
+    mesh.bake_curvature();
+
+    for triangle in mesh.triangles.iter() {
+        let center = triangle.curvature.unwrap()[2];
+        assert!(center.mean.abs() < 1e-4);
+        assert!(center.gaussian.abs() < 1e-4);
+    }
+}
@@ -0,0 +1,107 @@
+use sx3d::input_output::read_mesh;
+use sx3d::{
+    mesh::IndexedMesh3D, CalculateMaximumRadius, CalculateNormal, DecimationTarget,
+    IndexedTriangle3D, Vector2D, Vector3D,
+};
+
+fn flat_grid(size: usize) -> IndexedMesh3D {
+    let mut vertices = Vec::new();
+    for y in 0..=size {
+        for x in 0..=size {
+            vertices.push(Vector3D::new(x as f32, y as f32, 0.0));
+        }
+    }
+
+    let mut triangles = Vec::new();
+    let row = size + 1;
+    for y in 0..size {
+        for x in 0..size {
+            let i = y * row + x;
+            for vertices_indices in [[i, i + 1, i + row + 1], [i, i + row + 1, i + row]] {
+                let normal = vertices_indices.map(|v| vertices[v]).calculate_normal();
+                triangles.push(IndexedTriangle3D::new(normal, vertices_indices));
+            }
+        }
+    }
+
+    IndexedMesh3D::new(vertices, triangles)
+}
+
+#[test]
+fn it_should_reach_the_target_triangle_count() {
+    let mesh = read_mesh("examples/sphere.stl".to_string()).unwrap();
+    let target = mesh.triangles.len() / 4;
+
+    let decimated = mesh.decimate(DecimationTarget::Triangles(target));
+
+    assert!(decimated.triangles.len() <= target);
+    assert!(decimated.triangles.len() > target / 2);
+
+    let radius = mesh.vertices.calculate_maximum_radius();
+    let decimated_radius = decimated.vertices.calculate_maximum_radius();
+    assert!((radius - decimated_radius).abs() < radius * 0.1);
+}
+
+#[test]
+fn it_should_collapse_flat_regions_without_moving_the_border() {
+    let mesh = flat_grid(8);
+
+    let decimated = mesh.decimate(DecimationTarget::MaximumError(1e-6));
+
+    assert!(decimated.triangles.len() < mesh.triangles.len() / 4);
+    for vertex in decimated.vertices.iter() {
+        assert!(vertex[2].abs() < 1e-4);
+        assert!(vertex[0] >= -1e-4 && vertex[0] <= 8.0001);
+        assert!(vertex[1] >= -1e-4 && vertex[1] <= 8.0001);
+    }
+
+    let area: f32 = decimated
+        .iter()
+        .map(|triangle| {
+            let [p0, p1, p2] = triangle.vertices;
+            (p1 - p0).cross_product(&(p2 - p0)).norm() / 2.0
+        })
+        .sum();
+    assert!((area - 64.0).abs() < 1e-2);
+}
+
+#[test]
+fn it_should_keep_the_mesh_when_the_target_is_not_lower() {
+    let mesh = flat_grid(2);
+
+    let decimated = mesh.decimate(DecimationTarget::Triangles(mesh.triangles.len()));
+
+    assert_eq!(decimated.triangles.len(), mesh.triangles.len());
+}
+
+#[test]
+fn it_should_not_leave_corner_values_on_the_vertices_moved() {
+    let mut mesh = flat_grid(8);
+    for triangle in mesh.triangles.iter_mut() {
+        let corners = triangle
+            .vertices_indices
+            .map(|vertex| mesh.vertices[vertex]);
+        triangle.uvs = Some(corners.map(|corner| Vector2D::new(corner[0] / 8.0, corner[1] / 8.0)));
+        triangle.occlusion = Some(corners.map(|corner| corner[0] / 8.0));
+    }
+
+    let decimated = mesh.decimate(DecimationTarget::Triangles(mesh.triangles.len() / 4));
+
+    for triangle in decimated.triangles.iter() {
+        let corners = triangle
+            .vertices_indices
+            .map(|vertex| decimated.vertices[vertex]);
+        if let Some(uvs) = triangle.uvs {
+            for (uv, corner) in uvs.iter().zip(corners) {
+                assert!((uv[0] * 8.0 - corner[0]).abs() < 1e-4);
+                assert!((uv[1] * 8.0 - corner[1]).abs() < 1e-4);
+            }
+        }
+        let occlusion = triangle.occlusion.unwrap();
+        assert!(occlusion.iter().all(|value| (0.0..=1.0).contains(value)));
+    }
+    assert!(decimated
+        .triangles
+        .iter()
+        .any(|triangle| triangle.uvs.is_none()));
+}
@@ -0,0 +1,50 @@
+use sx3d::input_output::read_mesh;
+use sx3d::{angle, circle_radius, distance, Vector3D};
+
+#[test]
+fn it_should_measure_distances_and_angles() {
+    let a = Vector3D::new(1.0, 0.0, 0.0);
+    let vertex = Vector3D::new(0.0, 0.0, 0.0);
+    let b = Vector3D::new(0.0, 2.0, 0.0);
+
+    assert!((distance(&a, &b) - 5.0_f32.sqrt()).abs() < 1e-6);
+    assert!((angle(&a, &vertex, &b).unwrap() - 90.0).abs() < 1e-4);
+    assert!(angle(&a, &vertex, &vertex).is_none());
+}
+
+#[test]
+fn it_should_find_the_radius_of_the_circle_through_three_points() {
+    let radius = circle_radius(
+        &Vector3D::new(2.0, 0.0, 1.0),
+        &Vector3D::new(0.0, 2.0, 1.0),
+        &Vector3D::new(-2.0, 0.0, 1.0),
+    )
+    .unwrap();
+    assert!((radius - 2.0).abs() < 1e-5);
+
+    assert!(circle_radius(
+        &Vector3D::new(0.0, 0.0, 0.0),
+        &Vector3D::new(1.0, 1.0, 1.0),
+        &Vector3D::new(2.0, 2.0, 2.0),
+    )
+    .is_none());
+}
+
+#[test]
+fn it_should_measure_the_flat_face_of_a_triangle() {
+    let mesh = read_mesh("examples/cube_binary.stl".to_string()).unwrap();
+
+    for triangle in 0..mesh.triangles.len() {
+        let face = mesh.get_face(triangle);
+        assert_eq!(face.len(), 2);
+        assert!((mesh.get_area(&face) - 900.0).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn it_should_not_spread_faces_over_curved_surfaces() {
+    let mesh = read_mesh("examples/sphere.stl".to_string()).unwrap();
+
+    let triangle = mesh.triangles.len() / 2;
+    assert!(mesh.get_face(triangle).len() <= 2);
+}
@@ -1,3 +1,21 @@
+mod ambient_occlusion;
+mod bvh;
+#[cfg(feature = "stl")]
+mod curvature;
+#[cfg(feature = "stl")]
+mod decimation;
+#[cfg(feature = "stl")]
+mod measurement;
+#[cfg(all(feature = "stl", feature = "obj"))]
 mod object;
+mod repair;
+#[cfg(feature = "stl")]
+mod scene;
+#[cfg(all(feature = "stl", feature = "obj"))]
+mod section;
+mod shadow_map;
+#[cfg(feature = "stl")]
+mod slicer;
 mod triangles;
 mod tuples;
+mod visible_mesh;
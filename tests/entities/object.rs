@@ -1,5 +1,5 @@
-use sx3d::{mesh::IndexedMesh3D, IndexedTriangle3D, Object, Vector3D};
 use assert_float_eq::*;
+use sx3d::{mesh::IndexedMesh3D, Detail, IndexedTriangle3D, Object, Transform, Vector3D};
 
 #[test]
 fn it_should_create_object() {
@@ -9,31 +9,19 @@ fn it_should_create_object() {
         Vector3D::new(0.0, 1.0, 0.0),
         Vector3D::new(0.0, 0.0, 1.0),
     ];
-    let triangle_1 = IndexedTriangle3D {
-        normal: Vector3D::new(0.0, 0.0, -1.0),
-        vertices_indices: [0, 1, 2],
-    };
+    let triangle_1 = IndexedTriangle3D::new(Vector3D::new(0.0, 0.0, -1.0), [0, 1, 2]);
 
-    let triangle_2 = IndexedTriangle3D {
-        normal: Vector3D::new(0.0, -1.0, 0.0),
-        vertices_indices: [0, 1, 3],
-    };
+    let triangle_2 = IndexedTriangle3D::new(Vector3D::new(0.0, -1.0, 0.0), [0, 1, 3]);
 
-    let triangle_3 = IndexedTriangle3D {
-        normal: Vector3D::new(-1.0, 0.0, 0.0),
-        vertices_indices: [0, 2, 3],
-    };
+    let triangle_3 = IndexedTriangle3D::new(Vector3D::new(-1.0, 0.0, 0.0), [0, 2, 3]);
 
     let x = (1.0_f32 / 3.0_f32).sqrt();
-    let triangle_4 = IndexedTriangle3D {
-        normal: Vector3D::new(x, x, x),
-        vertices_indices: [1, 2, 3],
-    };
+    let triangle_4 = IndexedTriangle3D::new(Vector3D::new(x, x, x), [1, 2, 3]);
 
-    let mesh = IndexedMesh3D {
+    let mesh = IndexedMesh3D::new(
         vertices,
-        triangles: vec![triangle_1, triangle_2, triangle_3, triangle_4],
-    };
+        vec![triangle_1, triangle_2, triangle_3, triangle_4],
+    );
 
     let object = Object::new(mesh);
 
@@ -42,3 +30,76 @@ fn it_should_create_object() {
     let expected = (11.0_f32).sqrt() / 4.0_f32; // sqrt(0.75^2 + 0.25^2 + 0.25^2)
     assert_f32_near!(*object.get_maximum_radius(), expected);
 }
+
+#[test]
+fn it_should_build_a_reduced_mesh_only_for_dense_meshes() {
+    let mesh = sx3d::input_output::read_mesh("examples/sphere.stl".to_string()).unwrap();
+    let triangles = mesh.triangles.len();
+    let mut object = Object::new(mesh);
+
+    object.build_reduced_mesh(triangles);
+    assert_eq!(
+        object.get_mesh_with_detail(Detail::Reduced).triangles.len(),
+        triangles
+    );
+
+    object.build_reduced_mesh(triangles / 2);
+    assert!(object.get_mesh_with_detail(Detail::Reduced).triangles.len() <= triangles / 2);
+    assert_eq!(
+        object.get_mesh_with_detail(Detail::Full).triangles.len(),
+        triangles
+    );
+}
+
+#[test]
+fn it_should_leave_hidden_groups_out_of_the_shown_mesh() {
+    let mesh = sx3d::input_output::read_mesh("examples/two_cubes.obj".to_string()).unwrap();
+    let mut object = Object::new(mesh);
+
+    object.set_group_visible(1, false);
+    let shown = object.get_shown_mesh(Detail::Full);
+    assert_eq!(shown.triangles.len(), 12);
+    assert!(shown
+        .triangles
+        .iter()
+        .all(|triangle| triangle.group == Some(0)));
+
+    object.set_group_visible(1, true);
+    assert_eq!(object.get_shown_mesh(Detail::Full).triangles.len(), 24);
+}
+
+#[test]
+fn it_should_keep_the_bvh_on_the_moved_mesh() {
+    let mesh = sx3d::input_output::read_mesh("examples/sphere.stl".to_string()).unwrap();
+    let mut object = Object::new(mesh);
+    object.build_bvh();
+    object.rotate_mesh((&0.5, &0.3, &0.0));
+    object.translate(Vector3D::new(10.0, 0.0, 0.0));
+
+    let radius = *object.get_maximum_radius();
+    let hit = object
+        .get_bvh()
+        .unwrap()
+        .intersect(
+            object.get_mesh(),
+            &Vector3D::new(10.0, 0.0, 2.0 * radius),
+            &Vector3D::new(0.0, 0.0, -1.0),
+            4.0 * radius,
+        )
+        .unwrap();
+
+    // The sphere is made of flat faces, so the hit is a little inside it.
+    assert!(hit.distance > 0.9 * radius && hit.distance < 1.1 * radius);
+}
+
+#[test]
+fn it_should_read_transforms_with_the_rotation_in_degrees() {
+    let transform: Transform = "1,-2,3,90,0,0,2".parse().unwrap();
+
+    assert_eq!(transform.translation[1], -2.0);
+    assert_f32_near!(transform.rotation.0, std::f32::consts::FRAC_PI_2);
+    assert_eq!(transform.scale, 2.0);
+    assert_eq!("1,2,3".parse::<Transform>().unwrap().scale, 1.0);
+    assert!("1,2".parse::<Transform>().is_err());
+    assert!("1,2,3,0,0,0,0".parse::<Transform>().is_err());
+}
@@ -0,0 +1,49 @@
+use sx3d::{mesh::IndexedMesh3D, IndexedTriangle3D, Vector3D};
+
+fn triangle(vertices_indices: [usize; 3]) -> IndexedTriangle3D {
+    IndexedTriangle3D::new(Vector3D::new(0.0, 0.0, 1.0), vertices_indices)
+}
+
+#[test]
+fn it_should_weld_close_vertices() {
+    let mesh = IndexedMesh3D::new(
+        vec![
+            Vector3D::new(0.0, 0.0, 0.0),
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(1.0, 0.0, 0.0001),
+            Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(1.0, 1.0, 0.0),
+        ],
+        vec![triangle([0, 1, 2]), triangle([3, 5, 4])],
+    );
+
+    let welded = mesh.weld(0.001);
+
+    assert_eq!(welded.vertices.len(), 4);
+    assert_eq!(welded.triangles[1].vertices_indices, [1, 3, 2]);
+}
+
+#[test]
+fn it_should_remove_degenerate_and_duplicated_triangles() {
+    let mesh = IndexedMesh3D::new(
+        vec![
+            Vector3D::new(0.0, 0.0, 0.0),
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(2.0, 0.0, 0.0),
+            Vector3D::new(9.0, 9.0, 9.0),
+        ],
+        vec![
+            triangle([0, 1, 2]),
+            triangle([1, 2, 0]),
+            triangle([0, 0, 2]),
+            triangle([0, 1, 3]),
+        ],
+    );
+
+    let repaired = mesh.repair();
+
+    assert_eq!(repaired.triangles.len(), 1);
+    assert_eq!(repaired.vertices.len(), 3);
+}
@@ -0,0 +1,166 @@
+use sx3d::input_output::read_mesh;
+use sx3d::{CullMode, Layout, Object, Scene, Shadows, Transform, Vector2D, Vector3D};
+
+fn cube() -> Object {
+    Object::new(read_mesh("examples/cube_binary.stl".to_string()).unwrap())
+}
+
+fn two_cubes(layout: Layout) -> Scene {
+    let mut scene = Scene::new(
+        cube(),
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    );
+    scene.add_object(cube());
+    scene.arrange(layout);
+    scene
+}
+
+#[test]
+fn it_should_overlay_objects_at_the_origin() {
+    let scene = two_cubes(Layout::Overlay);
+    let radius = *scene.get_objects()[0].get_maximum_radius();
+
+    assert!((scene.get_maximum_radius() - radius).abs() < 1e-5);
+}
+
+#[test]
+fn it_should_place_objects_side_by_side() {
+    let scene = two_cubes(Layout::SideBySide);
+    let [first, second] = [0, 1].map(|index| *scene.get_objects()[index].get_center());
+    let radius = *scene.get_objects()[0].get_maximum_radius();
+
+    assert!((second[0] - first[0] - 2.1 * radius).abs() < 1e-4);
+    assert!((first[0] + second[0]).abs() < 1e-4);
+    assert!((scene.get_maximum_radius() - 2.05 * radius).abs() < 1e-4);
+}
+
+#[test]
+fn it_should_only_render_visible_objects() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    assert_eq!(scene.get_visible_meshes().len(), 2);
+
+    scene.get_objects_mut()[1].set_visible(false);
+
+    assert_eq!(scene.get_visible_meshes().len(), 1);
+}
+
+#[test]
+fn it_should_only_build_a_shadow_map_with_shadows_enabled() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    assert!(scene.get_shadow_map().is_none());
+
+    scene.set_shadows(Shadows {
+        enabled: true,
+        ..Shadows::default()
+    });
+    assert!(scene.get_shadow_map().is_some());
+}
+
+#[test]
+fn it_should_pick_the_front_triangle_of_the_object_under_a_point() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    scene.build_bvhs();
+    let center = *scene.get_objects()[1].get_center();
+
+    let pick = scene
+        .pick(&Vector2D::new(center[0] + 0.1, center[1] + 0.2))
+        .unwrap();
+    assert_eq!(pick.object, 1);
+    let normal = scene.get_objects()[1].get_mesh().triangles[pick.triangle].normal;
+    assert!(normal[2] > 0.99);
+    assert!(pick.point[2] > center[2]);
+
+    assert!(scene.pick(&Vector2D::new(0.0, 0.0)).is_none());
+}
+
+#[test]
+fn it_should_look_through_hidden_objects_and_culled_faces() {
+    let mut scene = two_cubes(Layout::Overlay);
+    scene.build_bvhs();
+    scene.get_objects_mut()[0].set_visible(false);
+
+    let pick = scene.pick(&Vector2D::new(0.1, 0.2)).unwrap();
+    assert_eq!(pick.object, 1);
+
+    scene.set_cull_mode(CullMode::Front);
+    let pick = scene.pick(&Vector2D::new(0.1, 0.2)).unwrap();
+    let normal = scene.get_objects()[1].get_mesh().triangles[pick.triangle].normal;
+    assert!(normal[2] < -0.99);
+}
+
+#[test]
+fn it_should_give_points_in_the_coordinates_of_the_file() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    scene.rotate_delta_y(&0.7);
+    let object = &scene.get_objects()[1];
+    let vertex = object.get_mesh().vertices[0];
+
+    let original = read_mesh("examples/cube_binary.stl".to_string())
+        .unwrap()
+        .vertices[0];
+    let point = scene.to_file_coordinates(1, &vertex);
+    for axis in 0..3 {
+        assert!((point[axis] - original[axis]).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn it_should_measure_transformed_objects_in_the_coordinates_of_the_file() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    scene.apply_transforms(&[
+        Transform::default(),
+        Transform {
+            translation: Vector3D::new(5.0, 0.0, 0.0),
+            rotation: (0.0, 0.5, 0.0),
+            scale: 2.0,
+        },
+    ]);
+    scene.rotate_delta_x(&0.4);
+    let vertices = &scene.get_objects()[1].get_mesh().vertices;
+
+    let original = read_mesh("examples/cube_binary.stl".to_string())
+        .unwrap()
+        .vertices;
+    for index in [0, 1] {
+        let point = scene.to_file_coordinates(1, &vertices[index]);
+        let back = scene.from_file_coordinates(1, &point);
+        for axis in 0..3 {
+            assert!((point[axis] - original[index][axis]).abs() < 1e-3);
+            assert!((back[axis] - vertices[index][axis]).abs() < 1e-3);
+        }
+    }
+    let seen = (vertices[1] - vertices[0]).norm();
+    let file = (original[1] - original[0]).norm();
+    assert!((seen - 2.0 * file).abs() < 1e-3);
+    let bounds = scene.get_file_bounds(1);
+    assert!((bounds.max[0] - 30.0).abs() < 1e-3);
+    let face = scene.get_objects()[1].get_mesh().get_face(0);
+    assert!((scene.get_file_area(1, &face) - 900.0).abs() < 1e-1);
+}
+
+#[test]
+fn it_should_go_back_from_the_coordinates_of_the_file() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    scene.rotate_delta_x(&0.4);
+    scene.rotate_delta_y(&0.7);
+    let point = Vector3D::new(3.0, -2.0, 5.0);
+
+    let file_point = scene.to_file_coordinates(1, &point);
+    let back = scene.from_file_coordinates(1, &file_point);
+    for axis in 0..3 {
+        assert!((back[axis] - point[axis]).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn it_should_bound_objects_in_the_axes_of_their_file() {
+    let mut scene = two_cubes(Layout::SideBySide);
+    scene.rotate_delta_y(&0.7);
+
+    let bounds = scene.get_file_bounds(1);
+    for axis in 0..3 {
+        assert!(bounds.min[axis].abs() < 1e-3);
+        assert!((bounds.max[axis] - 30.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,69 @@
+use sx3d::input_output::read_mesh;
+use sx3d::{Object, Plane, Vector3D};
+
+fn centered_cube() -> sx3d::mesh::IndexedMesh3D {
+    let mesh = read_mesh("examples/cube_binary.stl".to_string()).unwrap();
+    Object::new(mesh).get_mesh().clone()
+}
+
+#[test]
+fn it_should_discard_the_geometry_above_the_plane() {
+    let mesh = centered_cube();
+    let plane = Plane::new(Vector3D::new(0.0, 0.0, 1.0), 0.0);
+
+    let clipped = mesh.clip(&plane);
+
+    assert!(clipped.triangles.len() > 2);
+    for triangle in clipped.iter() {
+        for vertex in triangle.vertices.iter() {
+            assert!(plane.distance(vertex) <= 1e-5);
+        }
+    }
+}
+
+#[test]
+fn it_should_chain_the_section_into_a_closed_contour() {
+    let mesh = centered_cube();
+    let plane = Plane::new(Vector3D::new(0.0, 0.0, 1.0), 0.0);
+
+    let contours = mesh.section(&plane);
+
+    assert_eq!(contours.len(), 1);
+    assert!(contours[0].closed);
+    assert!(contours[0].points.len() >= 4);
+    for point in contours[0].points.iter() {
+        assert!(plane.distance(point).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn it_should_not_find_sections_outside_the_mesh() {
+    let mesh = centered_cube();
+    let plane = Plane::new(Vector3D::new(1.0, 0.0, 0.0), 1000.0);
+
+    assert!(mesh.section(&plane).is_empty());
+    assert_eq!(mesh.clip(&plane).triangles.len(), mesh.triangles.len());
+}
+
+#[test]
+fn it_should_interpolate_the_texture_coordinates_of_the_cut() {
+    let mesh = read_mesh("examples/textured_cube.obj".to_string()).unwrap();
+    let mesh = Object::new(mesh).get_mesh().clone();
+    let plane = Plane::new(Vector3D::new(1.0, 0.0, 0.0), 0.0);
+
+    let clipped = mesh.clip(&plane);
+
+    // Every face the plane crosses is cut through its middle, where one of the
+    // texture coordinates is one half.
+    let mut cut_corners = 0;
+    for triangle in clipped.triangles.iter() {
+        let uvs = triangle.uvs.unwrap();
+        for (index, uv) in triangle.vertices_indices.iter().zip(uvs.iter()) {
+            if clipped.vertices[*index][0].abs() < 1e-5 {
+                assert!((uv[0] - 0.5).abs() < 1e-5 || (uv[1] - 0.5).abs() < 1e-5);
+                cut_corners += 1;
+            }
+        }
+    }
+    assert!(cut_corners > 0);
+}
@@ -0,0 +1,64 @@
+use sx3d::{IndexedMesh3D, IndexedTriangle3D, ShadowMap, Shadows, Vector3D};
+
+// Square of side `2 * half` at height `z`, facing up.
+fn square(half: f32, z: f32) -> IndexedMesh3D {
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+    IndexedMesh3D::new(
+        vec![
+            Vector3D::new(-half, -half, z),
+            Vector3D::new(half, -half, z),
+            Vector3D::new(half, half, z),
+            Vector3D::new(-half, half, z),
+        ],
+        vec![
+            IndexedTriangle3D::new(up, [0, 1, 2]),
+            IndexedTriangle3D::new(up, [0, 2, 3]),
+        ],
+    )
+}
+
+fn floor_under_a_roof(light: Vector3D, softness: usize) -> ShadowMap {
+    let floor = square(2.0, 0.0);
+    let roof = square(0.5, 1.0);
+    let shadows = Shadows {
+        enabled: true,
+        softness,
+        resolution: 64,
+        ..Shadows::default()
+    };
+
+    ShadowMap::new(&[&floor, &roof], &light, &shadows)
+}
+
+#[test]
+fn it_should_shadow_what_is_behind_an_occluder() {
+    let shadow_map = floor_under_a_roof(Vector3D::new(0.0, 0.0, -1.0), 0);
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+
+    assert_eq!(shadow_map.light_at(&Vector3D::new(0.0, 0.0, 0.0), &up), 0.0);
+    assert_eq!(shadow_map.light_at(&Vector3D::new(1.5, 1.5, 0.0), &up), 1.0);
+    assert_eq!(shadow_map.light_at(&Vector3D::new(0.0, 0.0, 1.0), &up), 1.0);
+}
+
+#[test]
+fn it_should_not_shadow_sloped_surfaces_with_themselves() {
+    let shadow_map = floor_under_a_roof(Vector3D::new(-1.0, 0.0, -1.0), 3);
+    let up = Vector3D::new(0.0, 0.0, 1.0);
+
+    // The light travels towards -X, and so does the shadow of the roof.
+    assert_eq!(
+        shadow_map.light_at(&Vector3D::new(-1.0, 0.0, 0.0), &up),
+        0.0
+    );
+    for x in [0.6, 1.2, 1.8] {
+        assert_eq!(shadow_map.light_at(&Vector3D::new(x, 0.3, 0.0), &up), 1.0);
+    }
+}
+
+#[test]
+fn it_should_soften_the_borders_of_the_shadows() {
+    let shadow_map = floor_under_a_roof(Vector3D::new(0.0, 0.0, -1.0), 2);
+
+    let light = shadow_map.light_at(&Vector3D::new(0.5, 0.0, 0.0), &Vector3D::new(0.0, 0.0, 1.0));
+    assert!(light > 0.0 && light < 1.0);
+}
@@ -0,0 +1,54 @@
+use sx3d::input_output::read_mesh;
+
+#[test]
+fn it_should_slice_a_cube_into_square_layers() {
+    let mesh = read_mesh("examples/cube_binary.stl".to_string()).unwrap();
+    let bottom = mesh
+        .vertices
+        .iter()
+        .map(|v| v[2])
+        .fold(f32::INFINITY, f32::min);
+    let top = mesh
+        .vertices
+        .iter()
+        .map(|v| v[2])
+        .fold(f32::NEG_INFINITY, f32::max);
+    let layer_height = (top - bottom) / 4.0;
+
+    let layers = mesh.slice(layer_height);
+
+    assert_eq!(layers.len(), 4);
+    for (index, layer) in layers.iter().enumerate() {
+        let expected = bottom + (index as f32 + 0.5) * layer_height;
+        assert!((layer.height - expected).abs() < 1e-4);
+        assert_eq!(layer.contours.len(), 1);
+        assert!(layer.contours[0].closed);
+    }
+}
+
+#[test]
+fn it_should_not_slice_with_an_invalid_layer_height() {
+    let mesh = read_mesh("examples/cube_binary.stl".to_string()).unwrap();
+
+    assert!(mesh.slice(0.0).is_empty());
+}
+
+#[test]
+fn it_should_find_the_hole_of_a_torus() {
+    let mesh = read_mesh("examples/torus.stl".to_string()).unwrap();
+    let bottom = mesh
+        .vertices
+        .iter()
+        .map(|v| v[2])
+        .fold(f32::INFINITY, f32::min);
+    let top = mesh
+        .vertices
+        .iter()
+        .map(|v| v[2])
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    let layers = mesh.slice((top - bottom) / 3.0);
+
+    assert_eq!(layers[1].contours.len(), 2);
+    assert!(layers[1].contours.iter().all(|contour| contour.closed));
+}
@@ -0,0 +1,49 @@
+use sx3d::{mesh::IndexedMesh3D, CullMode, IndexedTriangle3D, Vector3D, VisibleIndexedMesh3D};
+
+fn facing_mesh() -> IndexedMesh3D {
+    let vertices = vec![
+        Vector3D::new(0.0, 0.0, 0.0),
+        Vector3D::new(1.0, 0.0, 0.0),
+        Vector3D::new(0.0, 1.0, 0.0),
+    ];
+    let front = IndexedTriangle3D::new(Vector3D::new(0.0, 0.0, 1.0), [0, 1, 2]);
+    let back = IndexedTriangle3D::new(Vector3D::new(0.0, 0.0, -1.0), [0, 2, 1]);
+
+    IndexedMesh3D::new(vertices, vec![front, back])
+}
+
+fn visible(cull_mode: CullMode) -> VisibleIndexedMesh3D {
+    let light = Vector3D::new(0.0, 0.0, -1.0);
+    let observer = Vector3D::new(0.0, 0.0, -1.0);
+    VisibleIndexedMesh3D::new(&facing_mesh(), &light, &observer, cull_mode)
+}
+
+#[test]
+fn it_should_cull_back_faces() {
+    let mesh = visible(CullMode::Back);
+    assert_eq!(mesh.triangles.len(), 1);
+    assert!(!mesh.triangles[0].back_face);
+    assert_eq!(mesh.triangles[0].shadow_value, 1.0);
+}
+
+#[test]
+fn it_should_cull_front_faces() {
+    let mesh = visible(CullMode::Front);
+    assert_eq!(mesh.triangles.len(), 1);
+    assert!(mesh.triangles[0].back_face);
+    assert_eq!(mesh.triangles[0].shadow_value, 1.0);
+}
+
+#[test]
+fn it_should_keep_both_sides_without_culling() {
+    let mesh = visible(CullMode::None);
+    assert_eq!(mesh.triangles.len(), 2);
+    assert_eq!(mesh.triangles.iter().filter(|t| t.back_face).count(), 1);
+}
+
+#[test]
+fn cull_modes_should_cycle() {
+    assert_eq!(CullMode::Back.next(), CullMode::Front);
+    assert_eq!(CullMode::Front.next(), CullMode::None);
+    assert_eq!(CullMode::None.next(), CullMode::Back);
+}
@@ -0,0 +1,89 @@
+use sx3d::canvas::{AsciiTarget, Frame, FrameDiff, RenderTarget};
+use sx3d::Color;
+
+const RED: Color = Color::new(255, 0, 0);
+
+#[test]
+fn it_should_clear_the_screen_and_write_the_first_frame_whole() {
+    let mut diff = FrameDiff::new();
+
+    let output = diff.update(&Frame::from_text("ab\n c\n"));
+
+    assert_eq!(output, "\x1b[2J\x1b[1;1Hab\x1b[2;2Hc");
+}
+
+#[test]
+fn it_should_write_only_the_runs_that_changed() {
+    let mut diff = FrameDiff::new();
+    diff.update(&Frame::from_text("abcdefghijkl\nabc\n"));
+
+    assert_eq!(diff.update(&Frame::from_text("abcdefghijkl\nabc\n")), "");
+    // Short gaps are written over, longer ones are jumped.
+    let output = diff.update(&Frame::from_text("xbxdefghijkx\nabc\n"));
+
+    assert_eq!(output, "\x1b[1;1Hxbx\x1b[1;12Hx");
+}
+
+#[test]
+fn it_should_erase_what_the_previous_frame_covered() {
+    let mut diff = FrameDiff::new();
+    diff.update(&Frame::from_text("abc\nde\n"));
+
+    let output = diff.update(&Frame::from_text("a\n"));
+
+    assert_eq!(output, "\x1b[1;2H  \x1b[2;1H  ");
+}
+
+#[test]
+fn it_should_set_each_color_once_and_reset_it_at_the_end() {
+    let mut frame = Frame::new(5, 1);
+    for (cell, char) in frame.cells.iter_mut().zip("ab cd".chars()) {
+        cell.char = char;
+        cell.color = Some(RED);
+    }
+    frame.cells[4].color = None;
+    let mut diff = FrameDiff::new();
+
+    let output = diff.update(&frame);
+
+    assert_eq!(output, "\x1b[2J\x1b[1;1H\x1b[38;2;255;0;0mab c\x1b[39md");
+}
+
+#[test]
+fn it_should_read_the_colors_of_the_ascii_target() {
+    let mut frame = Frame::new(2, 1);
+    frame.cells[0].char = 'a';
+    frame.cells[0].color = Some(RED);
+    frame.cells[1].char = 'b';
+    let text = AsciiTarget::new(true, "\r\n").draw(&frame);
+
+    let read = Frame::from_text(&text);
+
+    assert_eq!((read.cols, read.rows), (4, 1));
+    assert_eq!(read.cells[0].color, Some(RED));
+    assert_eq!(read.cells[3].char, 'b');
+    assert_eq!(read.cells[3].color, None);
+}
+
+#[test]
+fn it_should_write_everything_again_after_a_reset() {
+    let mut diff = FrameDiff::new();
+    let frame = Frame::from_text("ab\n");
+    diff.update(&frame);
+
+    diff.reset();
+
+    assert_eq!(diff.update(&frame), "\x1b[2J\x1b[1;1Hab");
+}
+
+#[test]
+fn it_should_fit_frames_in_the_terminal_as_it_wraps_them() {
+    let frame = Frame::from_text("abcde\n\nfg  \nh\n").fit(3, 3);
+
+    assert_eq!((frame.cols, frame.rows), (3, 3));
+    let text: Vec<String> = frame
+        .lines()
+        .map(|line| line.iter().map(|cell| cell.char).collect())
+        .collect();
+    assert_eq!(text, vec!["abc", "de ", "   "]);
+}
@@ -0,0 +1,68 @@
+use std::panic::{self, catch_unwind};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use sx3d::input_output::{Frontend, Input, TerminalGuard};
+
+static PREVIOUS_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+static LATER_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+/// Frontend that does nothing, with the default panic restore.
+struct NullFrontend;
+
+impl Frontend for NullFrontend {
+    fn get_size(&self) -> Result<(usize, usize), String> {
+        Ok((80, 24))
+    }
+
+    fn set_raw_mode(&mut self, _enabled: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn set_alternate_screen(&mut self, _enabled: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn set_cursor_visible(&mut self, _visible: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn set_synchronized_update(&mut self, _enabled: bool) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn write(&mut self, _text: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn read_input(&mut self, _timeout: Duration) -> Result<Option<Input>, String> {
+        Ok(None)
+    }
+}
+
+// The panic hook is global to the process, so this is the only test here.
+#[test]
+fn it_should_put_back_the_previous_panic_hook_unless_another_was_set() {
+    let mut frontend = NullFrontend;
+    panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLED.store(true, Ordering::SeqCst)
+    }));
+
+    drop(TerminalGuard::new(&mut frontend).unwrap());
+    let _ = catch_unwind(|| panic!("After the first guard"));
+
+    assert!(PREVIOUS_HOOK_CALLED.swap(false, Ordering::SeqCst));
+
+    let guard = TerminalGuard::new(&mut frontend).unwrap();
+    panic::set_hook(Box::new(|_| {
+        LATER_HOOK_CALLED.store(true, Ordering::SeqCst)
+    }));
+    drop(guard);
+    let _ = catch_unwind(|| panic!("After the second guard"));
+
+    assert!(LATER_HOOK_CALLED.load(Ordering::SeqCst));
+    assert!(!PREVIOUS_HOOK_CALLED.load(Ordering::SeqCst));
+}
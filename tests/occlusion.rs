@@ -0,0 +1,37 @@
+use sx3d::canvas::{screen_space_occlusion, Surface};
+use sx3d::Vector3D;
+
+// Grid of 9 by 9 cells facing the observer at depth 1, with the center at `depth`.
+fn grid_with_center(depth: f32) -> Vec<Option<Surface>> {
+    (0..81)
+        .map(|index| {
+            Some(Surface {
+                depth: if index == 40 { depth } else { 1.0 },
+                normal: Vector3D::new(0.0, 0.0, 1.0),
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn it_should_occlude_the_bottom_of_pits() {
+    let occlusion = screen_space_occlusion(&grid_with_center(0.0), 9, 1.0);
+
+    assert!(occlusion[40] > 0.0);
+    assert_eq!(occlusion[0], 0.0);
+}
+
+#[test]
+fn it_should_not_occlude_flat_or_raised_surfaces() {
+    let occlusion = screen_space_occlusion(&grid_with_center(2.0), 9, 1.0);
+
+    assert_eq!(occlusion[40], 0.0);
+    assert_eq!(occlusion[10], 0.0);
+}
+
+#[test]
+fn it_should_ignore_surfaces_far_in_front() {
+    let occlusion = screen_space_occlusion(&grid_with_center(-10.0), 9, 1.0);
+
+    assert_eq!(occlusion[40], 0.0);
+}
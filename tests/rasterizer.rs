@@ -0,0 +1,51 @@
+use sx3d::canvas::{Canvas, Rasterizer, Renderer, SquaredCanvas};
+use sx3d::{mesh::IndexedMesh3D, IndexedTriangle3D, Object, Scene, Vector3D};
+
+// A flat triangle at z = 0, and a tilted one crossing it, with a mean depth
+// behind it but in front of it where x > 4.
+fn scene(triangles: &[usize]) -> Scene {
+    let vertices = vec![
+        Vector3D::new(-9.0, -9.0, 0.0),
+        Vector3D::new(9.0, -9.0, 0.0),
+        Vector3D::new(0.0, 9.0, 0.0),
+        Vector3D::new(-8.0, -8.0, -6.0),
+        Vector3D::new(8.0, -8.0, 2.0),
+        Vector3D::new(8.0, 8.0, 2.0),
+    ];
+    let tilted = Vector3D::new(-1.0 / 5.0_f32.sqrt(), 0.0, 2.0 / 5.0_f32.sqrt());
+    let all = [
+        IndexedTriangle3D::new(Vector3D::new(0.0, 0.0, 1.0), [0, 1, 2]),
+        IndexedTriangle3D::new(tilted, [3, 4, 5]),
+    ];
+    let mesh = IndexedMesh3D::new(
+        vertices,
+        triangles.iter().map(|index| all[*index]).collect(),
+    );
+    Scene::new(
+        Object::new(mesh),
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    )
+}
+
+// Glyph drawn at the point of the scene (x, y).
+fn char_at(triangles: &[usize], x: f32, y: f32) -> char {
+    let scene = scene(triangles);
+    let canvas = SquaredCanvas::new(21, 2.0 * scene.get_maximum_radius());
+    Rasterizer::default().render(&scene, &canvas);
+    let (col, row) = canvas.coordinates_to_indexes(&x, &y);
+    canvas
+        .get_frame()
+        .get((col + 10) as usize, (10 - row) as usize)
+        .unwrap()
+        .char
+}
+
+#[test]
+fn it_should_keep_the_closest_triangle_at_each_cell() {
+    for (x, y, front) in [(6.0, -6.0, 1), (-4.0, -6.0, 0)] {
+        let back = 1 - front;
+        assert_ne!(char_at(&[front], x, y), char_at(&[back], x, y));
+        assert_eq!(char_at(&[0, 1], x, y), char_at(&[front], x, y));
+    }
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "stl")]
+
+use sx3d::canvas::{ray_trace, Canvas, Rasterizer, RayTracer, RayTracing, Renderer, SquaredCanvas};
+use sx3d::input_output::{read_mesh, render_frame, Render};
+use sx3d::{CullMode, Layout, Object, Scene, Shading, Vector2D, Vector3D};
+
+fn scene(path: &str) -> Scene {
+    let object = Object::new(read_mesh(path.to_string()).unwrap());
+    let mut scene = Scene::new(
+        object,
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    );
+    scene.rotate_delta_x(&0.6);
+    scene.rotate_delta_y(&0.4);
+    scene.build_bvhs();
+    scene
+}
+
+// Centers of a grid of 21 by 21 cells over the whole scene.
+fn grid(scene: &Scene) -> (Vec<Vector2D>, f32) {
+    let cell_size = 2.0 * scene.get_maximum_radius() / 21.0;
+    let centers = (-10..=10)
+        .flat_map(|row| {
+            (-10..=10).map(move |col| Vector2D::new(col as f32 * cell_size, row as f32 * cell_size))
+        })
+        .collect();
+    (centers, cell_size)
+}
+
+fn total_light(scene: &Scene, ray_tracing: &RayTracing) -> f32 {
+    let (centers, cell_size) = grid(scene);
+    ray_trace(scene, &centers, cell_size, ray_tracing)
+        .iter()
+        .flatten()
+        .map(|cell| cell.light.max(0.0))
+        .sum()
+}
+
+#[test]
+fn it_should_only_fill_the_cells_the_object_covers() {
+    let scene = scene("examples/cube_binary.stl");
+    let (centers, cell_size) = grid(&scene);
+
+    let cells = ray_trace(&scene, &centers, cell_size, &RayTracing::default());
+    assert!(cells[220].is_some());
+    assert!(cells[0].is_none());
+    assert!(cells[440].is_none());
+}
+
+#[test]
+fn it_should_darken_the_faces_in_shadow() {
+    let scene = scene("examples/torus.stl");
+    let lit = RayTracing {
+        shadows: false,
+        ..RayTracing::default()
+    };
+
+    assert!(total_light(&scene, &RayTracing::default()) < total_light(&scene, &lit));
+}
+
+#[test]
+fn it_should_reflect_the_empty_background_on_mirrors() {
+    let scene = scene("examples/cube_binary.stl");
+    let mirror = RayTracing {
+        reflectivity: 1.0,
+        ..RayTracing::default()
+    };
+
+    assert_eq!(total_light(&scene, &mirror), 0.0);
+}
+
+fn cube_render(ray_tracing: Option<RayTracing>) -> Render {
+    Render {
+        paths: vec!["examples/cube_binary.stl".to_string()],
+        output: None,
+        size: 21,
+        layout: Layout::Assembly,
+        transforms: Vec::new(),
+        colored: false,
+        braille: false,
+        cull_mode: CullMode::Back,
+        rotation: (0.6, 0.4, 0.0),
+        shading: Shading::default(),
+        shadows: true,
+        ambient_occlusion: None,
+        ray_tracing,
+    }
+}
+
+#[test]
+fn it_should_render_the_same_frame_size_as_the_rasterizer() {
+    let rasterized = render_frame(&cube_render(None)).unwrap();
+    let ray_traced = render_frame(&cube_render(Some(RayTracing::default()))).unwrap();
+
+    assert_eq!(ray_traced.lines().count(), 21);
+    assert_eq!(
+        ray_traced.lines().map(str::len).collect::<Vec<_>>(),
+        rasterized.lines().map(str::len).collect::<Vec<_>>()
+    );
+    assert!(ray_traced.chars().any(|char| char != ' ' && char != '\n'));
+}
+
+#[test]
+fn it_should_not_render_without_cells() {
+    let render = Render {
+        size: 0,
+        ..cube_render(None)
+    };
+
+    assert!(render_frame(&render).is_err());
+}
+
+#[test]
+fn it_should_draw_the_same_cells_with_both_renderers() {
+    let scene = scene("examples/cube_binary.stl");
+    let canvas = SquaredCanvas::new(21, 2.0 * scene.get_maximum_radius());
+    let covered = |canvas: &SquaredCanvas| {
+        let frame = canvas.get_frame();
+        frame
+            .cells
+            .iter()
+            .map(|cell| cell.char != ' ')
+            .collect::<Vec<bool>>()
+    };
+
+    Rasterizer::default().render(&scene, &canvas);
+    let rasterized = covered(&canvas);
+    RayTracer::new(RayTracing::default()).render(&scene, &canvas);
+    let traced = covered(&canvas);
+
+    let center = 10 + 10 * 21;
+    assert!(rasterized[center] && traced[center]);
+    assert!(!rasterized[0] && !traced[0]);
+    let differences = rasterized.iter().zip(traced).filter(|(a, b)| **a != *b);
+    assert!(differences.count() <= 21);
+}
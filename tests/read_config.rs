@@ -0,0 +1,42 @@
+use sx3d::input_output::{read_config, Config};
+use sx3d::Dithering;
+
+#[test]
+fn it_should_read_the_shading_defaults() {
+    let text =
+        "# Shading\nramp = \" .oO@\"\ndither = floyd-steinberg\n\ngamma = 1.5\ncontrast=0.8\n";
+
+    let config = read_config(text.as_bytes()).unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            ramp: Some(" .oO@".to_string()),
+            dithering: Some(Dithering::FloydSteinberg),
+            gamma: Some(1.5),
+            contrast: Some(0.8),
+        }
+    );
+}
+
+#[test]
+fn it_should_leave_out_what_the_config_does_not_set() {
+    let config = read_config("ramp = blocks\n".as_bytes()).unwrap();
+
+    assert_eq!(config.ramp.as_deref(), Some("blocks"));
+    assert_eq!(config.dithering, None);
+    assert_eq!(config.gamma, None);
+}
+
+#[test]
+fn it_should_reject_unknown_keys_and_invalid_values() {
+    assert!(read_config("glyphs = abc\n".as_bytes()).is_err());
+    assert!(read_config("dither = random\n".as_bytes()).is_err());
+    assert!(read_config("gamma = bright\n".as_bytes()).is_err());
+    assert!(read_config("ramp\n".as_bytes()).is_err());
+    assert!(read_config("gamma = 0\n".as_bytes()).is_err());
+    assert!(read_config("gamma = -2\n".as_bytes()).is_err());
+    assert!(read_config("gamma = NaN\n".as_bytes()).is_err());
+    assert!(read_config("contrast = -1\n".as_bytes()).is_err());
+    assert!(read_config("contrast = inf\n".as_bytes()).is_err());
+}
@@ -0,0 +1,127 @@
+#![cfg(all(feature = "stl", feature = "obj"))]
+
+use sx3d::input_output::{read_mesh, read_mtl};
+use sx3d::Color;
+
+#[test]
+fn it_should_keep_obj_objects_as_groups() {
+    let mesh = read_mesh("examples/two_cubes.obj".to_string()).unwrap();
+
+    let names: Vec<&str> = mesh
+        .groups
+        .iter()
+        .map(|group| group.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["left/body", "right/body"]);
+    assert_eq!(mesh.triangles.len(), 24);
+    assert!(mesh.triangles[..12]
+        .iter()
+        .all(|triangle| triangle.group == Some(0)));
+    assert!(mesh.triangles[12..]
+        .iter()
+        .all(|triangle| triangle.group == Some(1)));
+}
+
+#[test]
+fn it_should_read_the_materials_of_the_groups() {
+    let mesh = read_mesh("examples/two_cubes.obj".to_string()).unwrap();
+
+    let red = mesh.get_material(Some(0)).unwrap();
+    assert_eq!(red.name, "red");
+    assert_eq!(red.diffuse, Color::new(204, 26, 26));
+    assert_eq!(red.shininess, 32.0);
+
+    let blue = mesh.get_material(Some(1)).unwrap();
+    assert_eq!(blue.diffuse, Color::new(26, 51, 230));
+    assert_eq!(blue.opacity, 0.75);
+}
+
+#[test]
+fn it_should_put_ungrouped_obj_faces_in_one_group() {
+    let mesh = read_mesh("examples/cube.obj".to_string()).unwrap();
+
+    assert_eq!(mesh.groups.len(), 1);
+    assert!(mesh.groups[0].material.is_none());
+}
+
+#[test]
+fn it_should_use_the_mtl_defaults() {
+    let materials = read_mtl("newmtl plain\nillum 2\n".as_bytes()).unwrap();
+
+    assert_eq!(materials.len(), 1);
+    assert_eq!(materials[0].diffuse, Color::from_unit(0.8, 0.8, 0.8));
+    assert_eq!(materials[0].opacity, 1.0);
+}
+
+#[test]
+fn it_should_read_single_values_as_grays_and_skip_other_color_forms() {
+    let mtl = "newmtl part\nKd 0.5\nKa spectral ambient.rfl 1.0\nKs xyz 1 1 1\nNs high\n";
+
+    let materials = read_mtl(mtl.as_bytes()).unwrap();
+
+    assert_eq!(materials[0].diffuse, Color::from_unit(0.5, 0.5, 0.5));
+    let defaults = read_mtl("newmtl plain\n".as_bytes()).unwrap();
+    assert_eq!(materials[0].ambient, defaults[0].ambient);
+    assert_eq!(materials[0].specular, defaults[0].specular);
+    assert_eq!(materials[0].shininess, defaults[0].shininess);
+}
+
+#[test]
+fn it_should_reject_colors_before_newmtl() {
+    assert!(read_mtl("Kd 1 0 0\n".as_bytes()).is_err());
+}
+
+fn binary_stl(header: &[u8], attributes: &[u16]) -> String {
+    let path = std::env::temp_dir()
+        .join(format!("sx3d_{}_{}.stl", std::process::id(), attributes[0]))
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let mut data = vec![0u8; 80];
+    data[..header.len()].copy_from_slice(header);
+    data.extend((attributes.len() as u32).to_le_bytes());
+    for (index, attribute) in attributes.iter().enumerate() {
+        let x = index as f32;
+        let facet = [0.0, 0.0, 1.0, x, 0.0, 0.0, x + 1.0, 0.0, 0.0, x, 1.0, 0.0];
+        facet
+            .iter()
+            .for_each(|value| data.extend(f32::to_le_bytes(*value)));
+        data.extend(attribute.to_le_bytes());
+    }
+    std::fs::write(&path, data).unwrap();
+    path
+}
+
+#[test]
+fn it_should_read_viscam_facet_colors() {
+    // Bit 15 marks the colored facets; blue is in the lowest bits.
+    let path = binary_stl(b"solidview", &[0x8000 | 31 << 10, 0x8000 | 31, 0]);
+
+    let mesh = read_mesh(path).unwrap();
+
+    assert_eq!(mesh.triangles[0].color, Some(Color::new(255, 0, 0)));
+    assert_eq!(mesh.triangles[1].color, Some(Color::new(0, 0, 255)));
+    assert_eq!(mesh.triangles[2].color, None);
+}
+
+#[test]
+fn it_should_read_materialise_facet_colors() {
+    // A clear bit 15 gives the facet its own color, red in the lowest bits.
+    let path = binary_stl(b"COLOR=\x00\xff\x00\xff MATERIAL=", &[31, 0x8000]);
+
+    let mesh = read_mesh(path).unwrap();
+
+    assert_eq!(mesh.triangles[0].color, Some(Color::new(255, 0, 0)));
+    assert_eq!(mesh.triangles[1].color, Some(Color::new(0, 255, 0)));
+}
+
+#[test]
+fn it_should_not_color_plain_stl_files() {
+    let mesh = read_mesh("examples/cube_binary.stl".to_string()).unwrap();
+
+    assert!(mesh
+        .triangles
+        .iter()
+        .all(|triangle| triangle.color.is_none()));
+}
@@ -0,0 +1,124 @@
+use std::env::temp_dir;
+use std::fs::write;
+use sx3d::input_output::read_texture;
+use sx3d::{Color, Vector2D};
+
+fn temp_path(name: &str) -> String {
+    temp_dir()
+        .join(format!("sx3d_{}_{name}", std::process::id()))
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+const RED: Color = Color::new(255, 0, 0);
+const BLUE: Color = Color::new(0, 0, 255);
+
+#[test]
+fn it_should_read_ascii_ppm() {
+    let texture = read_texture("examples/checker.ppm".to_string()).unwrap();
+
+    assert_eq!((texture.width, texture.height), (8, 8));
+    assert_eq!(texture.pixels[0], Color::new(230, 200, 60));
+    assert_eq!(texture.pixels[2], Color::new(40, 40, 160));
+}
+
+#[test]
+fn it_should_read_binary_ppm() {
+    let path = temp_path("texture.ppm");
+    let mut data = b"P6\n# two pixels\n2 1\n255\n".to_vec();
+    data.extend([255, 0, 0, 0, 0, 255]);
+    write(&path, data).unwrap();
+
+    let texture = read_texture(path).unwrap();
+
+    assert_eq!(texture.pixels, vec![RED, BLUE]);
+}
+
+#[test]
+fn it_should_reject_ppm_sizes_and_maximums_out_of_range() {
+    for (name, header) in [
+        ("wide.ppm", "P6 6148914691236517206 1 255\n"),
+        ("huge.ppm", "P6 4294967296 4294967296 255\n"),
+        ("deep.ppm", "P3 1 1 18446744073709551615\n"),
+    ] {
+        let path = temp_path(name);
+        let mut data = header.as_bytes().to_vec();
+        data.extend(b"1 1 1\n");
+        write(&path, data).unwrap();
+
+        assert!(read_texture(path).is_err());
+    }
+}
+
+#[test]
+#[cfg(feature = "png")]
+fn it_should_read_png() {
+    let path = temp_path("texture.png");
+    let mut encoder = png::Encoder::new(std::fs::File::create(&path).unwrap(), 1, 2);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer
+        .write_image_data(&[255, 0, 0, 255, 0, 0, 255, 255])
+        .unwrap();
+    writer.finish().unwrap();
+
+    let texture = read_texture(path).unwrap();
+
+    assert_eq!((texture.width, texture.height), (1, 2));
+    assert_eq!(texture.pixels, vec![RED, BLUE]);
+}
+
+#[test]
+fn it_should_read_run_length_encoded_tga_from_the_bottom() {
+    let path = temp_path("texture.tga");
+    let mut data = vec![0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 2, 0, 24, 0];
+    // Bottom row: two blue pixels in one run. Top row: two raw red pixels.
+    data.extend([0x81, 255, 0, 0]);
+    data.extend([0x01, 0, 0, 255, 0, 0, 255]);
+    write(&path, data).unwrap();
+
+    let texture = read_texture(path).unwrap();
+
+    assert_eq!(texture.pixels, vec![RED, RED, BLUE, BLUE]);
+}
+
+#[test]
+fn it_should_sample_with_v_growing_upwards() {
+    let texture = sx3d::Texture::new(1, 2, vec![RED, BLUE]);
+
+    assert_eq!(texture.sample(&Vector2D::new(0.5, 0.9)), RED);
+    assert_eq!(texture.sample(&Vector2D::new(0.5, 0.1)), BLUE);
+    assert_eq!(texture.sample(&Vector2D::new(1.5, 1.1)), BLUE);
+}
+
+#[test]
+#[cfg(feature = "obj")]
+fn it_should_load_the_textures_and_uvs_of_obj_files() {
+    let mesh = sx3d::input_output::read_mesh("examples/textured_cube.obj".to_string()).unwrap();
+
+    let material = mesh.get_material(mesh.triangles[0].group).unwrap();
+    assert_eq!(material.diffuse_map.as_deref(), Some("checker.ppm"));
+    assert_eq!(material.texture.as_ref().unwrap().width, 8);
+    assert!(mesh.triangles.iter().all(|triangle| triangle.uvs.is_some()));
+}
+
+#[test]
+#[cfg(feature = "obj")]
+fn it_should_use_the_diffuse_color_when_the_texture_cannot_be_read() {
+    let directory = temp_path("jpg_texture");
+    std::fs::create_dir_all(&directory).unwrap();
+    let obj = format!("{directory}/textured_cube.obj");
+    std::fs::copy("examples/textured_cube.obj", &obj).unwrap();
+    let mtl = "newmtl checker\nKd 1 0 0\nmap_Kd photo.jpg\n";
+    write(format!("{directory}/textured_cube.mtl"), mtl).unwrap();
+    write(format!("{directory}/photo.jpg"), [0xff, 0xd8, 0xff, 0xe0]).unwrap();
+
+    let mesh = sx3d::input_output::read_mesh(obj).unwrap();
+
+    let material = mesh.get_material(mesh.triangles[0].group).unwrap();
+    assert_eq!(material.diffuse_map.as_deref(), Some("photo.jpg"));
+    assert!(material.texture.is_none());
+    assert_eq!(material.diffuse, RED);
+}
@@ -1,4 +1,5 @@
-use sx3d::canvas::{shade_to_char, GRAYSCALE_LUT};
+use sx3d::canvas::{shade_to_char, Dithering, Ramp, Shade, Shading, Toon, GRAYSCALE_LUT};
+use sx3d::Color;
 
 #[test]
 fn shades_are_clamped() {
@@ -15,3 +16,116 @@ fn shade_zero_and_one_boundaries() {
     assert_eq!(z, GRAYSCALE_LUT[0]);
     assert_eq!(o, GRAYSCALE_LUT[GRAYSCALE_LUT.len() - 1]);
 }
+
+fn flat(value: f32, cells: usize) -> Vec<Option<Shade>> {
+    vec![
+        Some(Shade {
+            value,
+            back_face: false,
+        });
+        cells
+    ]
+}
+
+fn two_glyphs(dithering: Dithering) -> Shading {
+    Shading {
+        ramp: vec!['.', '#'],
+        dithering,
+        ..Shading::default()
+    }
+}
+
+#[test]
+fn presets_have_their_sizes() {
+    assert_eq!(Ramp::Standard.chars(), GRAYSCALE_LUT.to_vec());
+    assert_eq!(Ramp::Classic.chars().len(), 70);
+    assert_eq!(Ramp::Blocks.chars(), vec!['░', '▒', '▓', '█']);
+    assert_eq!(Ramp::from_name("digits").unwrap().chars().len(), 10);
+    assert_eq!(Ramp::from_name("@%#"), None);
+}
+
+#[test]
+fn the_curve_applies_contrast_then_gamma() {
+    let shading = Shading {
+        gamma: 2.0,
+        contrast: 2.0,
+        ..Shading::default()
+    };
+
+    assert_eq!(shading.apply_curve(0.5), 0.5_f32.sqrt());
+    assert_eq!(shading.apply_curve(0.75), 1.0);
+    assert_eq!(shading.apply_curve(0.2), 0.0);
+}
+
+#[test]
+fn without_dithering_mid_shades_round_to_one_glyph() {
+    let glyphs = two_glyphs(Dithering::None).to_glyphs(&flat(0.4, 16), 4);
+
+    assert!(glyphs.iter().all(|glyph| *glyph == Some('.')));
+}
+
+#[test]
+fn ordered_dithering_mixes_glyphs_in_proportion() {
+    let glyphs = two_glyphs(Dithering::Bayer).to_glyphs(&flat(0.25, 16), 4);
+
+    let bright = glyphs.iter().filter(|glyph| **glyph == Some('#')).count();
+    assert_eq!(bright, 4);
+}
+
+#[test]
+fn error_diffusion_keeps_the_mean_shade() {
+    let glyphs = two_glyphs(Dithering::FloydSteinberg).to_glyphs(&flat(0.5, 64), 8);
+
+    let bright = glyphs.iter().filter(|glyph| **glyph == Some('#')).count();
+    assert!((30..=34).contains(&bright));
+}
+
+#[test]
+fn unshaded_cells_get_no_glyph() {
+    let mut shades = flat(1.0, 4);
+    shades[1] = None;
+
+    let glyphs = two_glyphs(Dithering::FloydSteinberg).to_glyphs(&shades, 2);
+
+    assert_eq!(glyphs, vec![Some('#'), None, Some('#'), Some('#')]);
+}
+
+#[test]
+fn toon_bands_flatten_the_light() {
+    let shading = Shading {
+        toon: Some(Toon {
+            bands: 3,
+            outline: '#',
+            outline_color: None,
+        }),
+        ..Shading::default()
+    };
+
+    assert_eq!(shading.band(0.2), 0.0);
+    assert_eq!(shading.band(0.5), 0.5);
+    assert_eq!(shading.band(0.6), 0.5);
+    assert_eq!(shading.band(1.0), 1.0);
+    assert_eq!(Shading::default().band(0.6), 0.6);
+}
+
+#[test]
+fn colors_are_parsed_from_hex() {
+    assert_eq!("#ff8000".parse(), Ok(Color::new(255, 128, 0)));
+    assert_eq!("00ff00".parse(), Ok(Color::new(0, 255, 0)));
+    assert!("#ff80".parse::<Color>().is_err());
+    assert!("#gg0000".parse::<Color>().is_err());
+}
+
+#[test]
+fn diverging_colors_go_from_blue_to_red() {
+    let (cold, middle, hot) = (
+        Color::diverging(0.0),
+        Color::diverging(0.5),
+        Color::diverging(1.0),
+    );
+
+    assert!(cold.b > cold.r);
+    assert!(hot.r > hot.b);
+    assert_eq!(middle.r, middle.b);
+    assert_eq!(Color::diverging(-1.0), cold);
+}
@@ -0,0 +1,91 @@
+use sx3d::canvas::{AsciiTarget, BrailleTarget, Cell, Frame, PpmTarget, RenderTarget, SvgTarget};
+use sx3d::Color;
+
+fn frame(chars: &[&str]) -> Frame {
+    let mut frame = Frame::new(chars[0].chars().count(), chars.len());
+    for (cell, char) in frame
+        .cells
+        .iter_mut()
+        .zip(chars.iter().flat_map(|row| row.chars()))
+    {
+        cell.char = char;
+    }
+    frame
+}
+
+#[test]
+fn it_should_write_the_cells_two_spaces_apart() {
+    let text = AsciiTarget::new(false, "\r\n").draw(&frame(&["ab", " c"]));
+
+    assert_eq!(text, "a  b\r\n   c\r\n");
+    assert_eq!(AsciiTarget::default().get_size(2, 2), (4, 2));
+}
+
+#[test]
+fn it_should_color_the_glyphs_and_reset_the_color_at_the_end_of_lines() {
+    let mut frame = frame(&["ab"]);
+    frame.cells[0].color = Some(Color::new(1, 2, 3));
+    frame.cells[1].color = Some(Color::new(1, 2, 3));
+
+    let text = AsciiTarget::new(true, "\n").draw(&frame);
+    assert_eq!(text, "\x1b[38;2;1;2;3ma  b\x1b[39m\n");
+}
+
+#[test]
+fn it_should_find_the_cell_at_a_position_of_the_text() {
+    let ascii = AsciiTarget::default();
+    assert_eq!(ascii.get_cell_at(0, 0, 5, 5), Some((0, 0)));
+    assert_eq!(ascii.get_cell_at(7, 2, 5, 5), Some((2, 2)));
+    assert_eq!(ascii.get_cell_at(15, 0, 5, 5), None);
+
+    let braille = BrailleTarget::default();
+    assert_eq!(braille.get_size(5, 5), (3, 2));
+    assert_eq!(braille.get_cell_at(2, 1, 5, 5), Some((4, 4)));
+    assert_eq!(braille.get_cell_at(3, 0, 5, 5), None);
+}
+
+#[test]
+fn it_should_turn_on_more_braille_dots_on_brighter_cells() {
+    let mut dark = Frame::new(2, 4);
+    dark.cells.iter_mut().for_each(|cell| {
+        *cell = Cell {
+            char: '.',
+            color: None,
+            light: Some(0.1),
+        }
+    });
+    let mut bright = dark.clone();
+    bright
+        .cells
+        .iter_mut()
+        .for_each(|cell| cell.light = Some(1.0));
+
+    let dots = |text: String| (text.chars().next().unwrap() as u32 - 0x2800).count_ones();
+    let target = BrailleTarget::default();
+    assert_eq!(dots(target.draw(&dark)), 1);
+    assert_eq!(dots(target.draw(&bright)), 8);
+    assert_eq!(target.draw(&Frame::new(3, 5)), "  \n  \n");
+}
+
+#[test]
+fn it_should_paint_every_cell_as_a_square_of_pixels() {
+    let mut frame = frame(&["a ", "  "]);
+    frame.cells[1].light = Some(1.0);
+
+    let image = PpmTarget { cell_size: 2 }.draw(&frame);
+    let header = b"P6\n4 4\n255\n";
+    assert_eq!(&image[..header.len()], header);
+    let pixels = &image[header.len()..];
+    assert_eq!(pixels.len(), 3 * 16);
+    assert_eq!(&pixels[..3], &[255, 255, 255]);
+    assert_eq!(&pixels[3 * 12..3 * 13], &[0, 0, 0]);
+}
+
+#[test]
+fn it_should_escape_the_glyphs_of_svg_images() {
+    let svg = SvgTarget::default().draw(&frame(&["& "]));
+
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<text").count(), 1);
+    assert!(svg.contains(">&amp;</text>"));
+}
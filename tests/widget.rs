@@ -0,0 +1,71 @@
+#![cfg(all(feature = "ratatui", feature = "stl"))]
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color as TuiColor;
+use ratatui::widgets::{StatefulWidget, Widget};
+use sx3d::input_output::{read_mesh, GlyphMode, SceneView, SceneWidget};
+use sx3d::{Color, Object, Scene, Vector3D};
+
+fn scene() -> Scene {
+    let mut object = Object::new(read_mesh("examples/cube_binary.stl".to_string()).unwrap());
+    object.set_color(Some(Color::new(10, 20, 30)));
+    let mut scene = Scene::new(
+        object,
+        Vector3D::new(-1.0, -1.0, -1.0),
+        Vector3D::new(0.0, 0.0, -1.0),
+    );
+    scene.rotate_delta_x(&0.6);
+    scene.rotate_delta_y(&0.4);
+    scene
+}
+
+fn symbols(buffer: &Buffer) -> String {
+    buffer.content.iter().map(|cell| cell.symbol()).collect()
+}
+
+#[test]
+fn it_should_draw_the_scene_in_the_middle_of_the_area() {
+    let scene = scene();
+    let area = Rect::new(0, 0, 40, 10);
+    let mut buffer = Buffer::empty(area);
+
+    SceneWidget::new(&scene, SceneView::new(GlyphMode::Ascii, false)).render(area, &mut buffer);
+
+    // Nine cells, three columns apart, from column 7.
+    let center = buffer.cell((19, 4)).unwrap().symbol();
+    assert_ne!(center, " ");
+    assert_eq!(buffer.cell((20, 4)).unwrap().symbol(), " ");
+    assert!(symbols(&buffer).chars().take(6).all(|char| char == ' '));
+}
+
+#[test]
+fn it_should_draw_braille_dots_in_the_colors_of_the_objects() {
+    let mut scene = scene();
+    let area = Rect::new(2, 1, 20, 10);
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 24, 12));
+
+    SceneView::new(GlyphMode::Braille, true).render(area, &mut buffer, &mut scene);
+
+    let dots: Vec<_> = buffer
+        .content
+        .iter()
+        .filter(|cell| cell.symbol() != " ")
+        .collect();
+    assert!(!dots.is_empty());
+    assert!(dots
+        .iter()
+        .all(|cell| ('\u{2800}'..='\u{28ff}').contains(&cell.symbol().chars().next().unwrap())));
+    assert!(dots.iter().any(|cell| matches!(cell.fg, TuiColor::Rgb(..))));
+    assert_eq!(buffer.cell((0, 0)).unwrap().symbol(), " ");
+}
+
+#[test]
+fn it_should_leave_areas_too_small_for_a_cell_empty() {
+    let scene = scene();
+    let area = Rect::new(0, 0, 0, 3);
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 3));
+
+    SceneWidget::new(&scene, SceneView::default()).render(area, &mut buffer);
+    assert_eq!(symbols(&buffer).trim(), "");
+}
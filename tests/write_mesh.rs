@@ -0,0 +1,128 @@
+#![cfg(all(feature = "stl", feature = "obj"))]
+
+use std::env::temp_dir;
+use std::fs::read_to_string;
+use sx3d::input_output::{convert, get_output_format, read_mesh, write_mesh, Conversion};
+
+fn temp_path(name: &str) -> String {
+    temp_dir()
+        .join(format!("sx3d_{}_{name}", std::process::id()))
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+fn round_trip(name: &str, ascii: bool) {
+    let mesh = read_mesh("examples/cube.obj".to_string()).unwrap();
+    let path = temp_path(name);
+
+    let format = get_output_format(path.clone(), ascii).unwrap();
+    write_mesh(path.clone(), &mesh, format).unwrap();
+    let result = read_mesh(path).unwrap();
+
+    assert_eq!(result.triangles.len(), mesh.triangles.len());
+    assert_eq!(result.vertices.len(), 8);
+}
+
+#[test]
+fn it_should_round_trip_binary_stl() {
+    round_trip("cube_binary.stl", false);
+}
+
+#[test]
+fn it_should_round_trip_ascii_stl() {
+    round_trip("cube_ascii.stl", true);
+}
+
+#[test]
+fn it_should_round_trip_obj() {
+    round_trip("cube.obj", false);
+}
+
+#[test]
+fn it_should_round_trip_binary_ply() {
+    round_trip("cube_binary.ply", false);
+}
+
+#[test]
+fn it_should_round_trip_ascii_ply() {
+    round_trip("cube_ascii.ply", true);
+}
+
+#[test]
+fn it_should_write_ascii_ply() {
+    let mesh = read_mesh("examples/cube_ascii.stl".to_string()).unwrap();
+    let path = temp_path("cube.ply");
+
+    write_mesh(
+        path.clone(),
+        &mesh,
+        get_output_format(path.clone(), true).unwrap(),
+    )
+    .unwrap();
+    let ply = read_to_string(path).unwrap();
+
+    assert!(ply.starts_with("ply\nformat ascii 1.0\n"));
+    assert!(ply.contains("element vertex 8\n"));
+    assert!(ply.contains("element face 12\n"));
+    assert_eq!(
+        ply.lines().filter(|line| line.starts_with("3 ")).count(),
+        12
+    );
+}
+
+#[test]
+fn it_should_reject_unknown_output_formats() {
+    assert!(get_output_format("cube.gltf".to_string(), false).is_err());
+}
+
+#[test]
+fn it_should_convert_with_transforms() {
+    let output = temp_path("converted.stl");
+    let conversion = Conversion {
+        input: "examples/cube.obj".to_string(),
+        output: output.clone(),
+        center: true,
+        scale: Some(2.0),
+        repair: true,
+        ..Default::default()
+    };
+
+    convert(&conversion).unwrap();
+    let result = read_mesh(output).unwrap();
+
+    assert_eq!(result.triangles.len(), 12);
+
+    // Centered and doubled, so the half width matches the original full width.
+    let original = read_mesh("examples/cube.obj".to_string()).unwrap();
+    let width = |xs: Vec<f32>| {
+        xs.iter().cloned().fold(f32::MIN, f32::max) - xs.iter().cloned().fold(f32::MAX, f32::min)
+    };
+    let original_width = width(original.vertices.iter().map(|v| v[0]).collect());
+    let half_width = result
+        .vertices
+        .iter()
+        .map(|v| v[0].abs())
+        .fold(0.0, f32::max);
+    assert!((half_width - original_width).abs() < 1e-4);
+}
+
+#[test]
+fn it_should_round_trip_stl_facet_colors() {
+    let mut mesh = read_mesh("examples/cube_binary.stl".to_string()).unwrap();
+    mesh.triangles[0].color = Some(sx3d::Color::new(255, 0, 132));
+    let path = temp_path("colored.stl");
+
+    write_mesh(
+        path.clone(),
+        &mesh,
+        get_output_format(path.clone(), false).unwrap(),
+    )
+    .unwrap();
+    let result = read_mesh(path).unwrap();
+
+    assert_eq!(result.triangles[0].color, mesh.triangles[0].color);
+    assert!(result.triangles[1..]
+        .iter()
+        .all(|triangle| triangle.color.is_none()));
+}
@@ -0,0 +1,27 @@
+use sx3d::input_output::{get_layers_bounds, write_layer_svg};
+use sx3d::{Layer, LayerContour, Vector2D};
+
+#[test]
+fn it_should_write_closed_contours_as_an_even_odd_path() {
+    let layer = Layer {
+        height: 1.0,
+        contours: vec![LayerContour {
+            points: vec![
+                Vector2D::new(0.0, 0.0),
+                Vector2D::new(2.0, 0.0),
+                Vector2D::new(2.0, 1.0),
+            ],
+            closed: true,
+        }],
+    };
+    let bounds = get_layers_bounds(std::slice::from_ref(&layer));
+    let mut svg = Vec::new();
+
+    write_layer_svg(&mut svg, &layer, bounds).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(r#"d="M0 -0 L2 -0 L2 -1 Z""#));
+    assert!(svg.contains(r#"fill-rule="evenodd""#));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}